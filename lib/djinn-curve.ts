@@ -0,0 +1,183 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// DJINN CURVE (WASM): the on-chain curve, fee and payout math for scripts
+// ═══════════════════════════════════════════════════════════════════════════════
+// Every number comes from the djinn-curve crate the program itself uses.
+//
+// - Exact API: lamports and share units (shares * 1e9) as bigint, passed straight
+//   through to the crate. These match the chain to the lamport.
+// - Script API: the core-amm.ts simulation functions, in SOL and whole shares as JS
+//   numbers. Conversions round to the nearest representable double, so results are
+//   approximate once supplies or amounts pass 2^53 units (~9M shares / ~9M SOL).
+//
+// The frontend (QuickBetModal, DjinnChart, app/market/[slug]/page.tsx and
+// useCerberusTrigger) still prices with core-amm.ts. Moving it over needs a browser
+// build of the package (this one targets Node) and async WASM init in those
+// components; that is out of scope here.
+//
+// Build the package first:
+//   npm run build:curve-wasm   (wasm-pack --target nodejs, into crates/djinn-curve-wasm/pkg)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+import * as curve from '../programs/djinn-market/crates/djinn-curve-wasm/pkg/djinn_curve_wasm.js';
+import {
+    calculateImpliedProbability,
+    getIgnitionProgress,
+    PHASE3_START,
+    TOTAL_SUPPLY,
+    type MarketState,
+    type TradeSimulation,
+} from './core-amm';
+
+export { calculateImpliedProbability, type MarketState, type TradeSimulation };
+
+const LAMPORTS_PER_SOL = 1e9;
+const SHARE_SCALE = 1e9; // On-chain supplies are shares * 1e9
+
+// --- UNIT CONVERSION (the script API works in SOL and whole shares) ---
+
+const toLamports = (sol: number): bigint => BigInt(Math.floor(sol * LAMPORTS_PER_SOL));
+const toUnits = (shares: number): bigint => BigInt(Math.floor(shares * SHARE_SCALE));
+const toSol = (lamports: bigint): number => Number(lamports) / LAMPORTS_PER_SOL;
+const toShares = (units: bigint): number => Number(units) / SHARE_SCALE;
+
+/** Unwrap a crate result, throwing on the FFI_ERROR sentinel */
+function checked(value: bigint, what: string): bigint {
+    if (value === curve.ffiError()) {
+        throw new Error(`djinn-curve: ${what} failed`);
+    }
+    return value;
+}
+
+// --- EXACT API (lamports and share units) ---
+
+/** Spot price at `supply` share units, in lamports per whole share */
+export function spotPriceLamports(supply: bigint): bigint {
+    return checked(curve.spotPrice(supply), 'spotPrice');
+}
+
+/** Lamports to move the supply from `supplyOld` to `supplyNew` share units */
+export function costLamports(supplyOld: bigint, supplyNew: bigint): bigint {
+    return checked(curve.cost(supplyOld, supplyNew), 'cost');
+}
+
+/** buy_shares with `solIn` lamports (entry fee included) at `supply` share units */
+export function quoteBuy(solIn: bigint, supply: bigint): { fee: bigint; shares: bigint } {
+    const fee = checked(curve.entryFee(solIn), 'entryFee');
+    const shares = checked(curve.sharesFromSol(solIn - fee, supply), 'sharesFromSol');
+    return { fee, shares };
+}
+
+/** sell_shares of `shares` units at `supply` share units; `net` is what the seller receives */
+export function quoteSell(shares: bigint, supply: bigint): { refund: bigint; fee: bigint; net: bigint } {
+    const refund = costLamports(supply - shares, supply);
+    const fee = checked(curve.exitFee(refund), 'exitFee');
+    return { refund, fee, net: refund - fee };
+}
+
+/** A winning position's share of a `pot`-lamport resolution pot, in lamports */
+export function payoutLamports(pot: bigint, shares: bigint, totalWinningShares: bigint): bigint {
+    return checked(curve.proRataPayout(pot, shares, totalWinningShares), 'proRataPayout');
+}
+
+// --- CURVE (SOL and whole shares) ---
+
+/** Spot price at `sharesSupply` whole shares, in SOL per share */
+export function getSpotPrice(sharesSupply: number): number {
+    return toSol(spotPriceLamports(toUnits(sharesSupply)));
+}
+
+/** SOL to move the supply from `supplyOld` to `supplyNew` whole shares */
+export function getCost(supplyOld: number, supplyNew: number): number {
+    return toSol(costLamports(toUnits(supplyOld), toUnits(supplyNew)));
+}
+
+/** Largest supply whose spot price stays at or below `priceSol` (bisection on the monotone curve) */
+export function getSupplyAtPrice(priceSol: number): number {
+    const target = toLamports(priceSol);
+    let low = 0n;
+    let high = toUnits(TOTAL_SUPPLY);
+    while (high - low > BigInt(SHARE_SCALE)) {
+        const mid = (low + high) / 2n;
+        if (spotPriceLamports(mid) <= target) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    return toShares(low);
+}
+
+// --- TRADES (flat ProtocolConfig launch fees, user-created market) ---
+
+function tradeSimulation(
+    input: number,
+    output: number,
+    fee: bigint,
+    netInvested: number,
+    averageEntryPrice: number,
+    startSupply: number,
+    finalSupply: number,
+): TradeSimulation {
+    const creatorCut = checked(curve.creatorFeeCut(fee, false), 'creatorFeeCut');
+    const startPrice = getSpotPrice(startSupply);
+    const endPrice = getSpotPrice(finalSupply);
+    const priceImpact = startPrice > 0 ? (Math.abs(endPrice - startPrice) / startPrice) * 100 : 0;
+    return {
+        inputAmount: input,
+        sharesReceived: output,
+        priceImpact,
+        feeTotal: toSol(fee),
+        feeProtocol: toSol(fee - creatorCut),
+        feeCreator: toSol(creatorCut),
+        netInvested,
+        averageEntryPrice,
+        startPrice,
+        endPrice,
+        isEndgame: finalSupply > TOTAL_SUPPLY * 0.95,
+        warningSlippage: priceImpact > 15.0,
+        currentMcap: endPrice * finalSupply,
+        ignitionProgress: getIgnitionProgress(finalSupply),
+        isViralMode: finalSupply >= PHASE3_START,
+    };
+}
+
+/** Buy with `amountSol` (entry fee included) through the buy_shares math; see quoteBuy for exact amounts */
+export function simulateBuy(amountSol: number, marketState: MarketState): TradeSimulation {
+    const solIn = toLamports(amountSol);
+    const quote = quoteBuy(solIn, toUnits(marketState.totalSharesMinted));
+    const fee = quote.fee;
+    const shares = toShares(quote.shares);
+
+    const startSupply = marketState.totalSharesMinted;
+    return tradeSimulation(
+        amountSol,
+        shares,
+        fee,
+        toSol(solIn - fee),
+        shares > 0 ? amountSol / shares : 0,
+        startSupply,
+        startSupply + shares,
+    );
+}
+
+/** Sell `sharesToSell`; `sharesReceived` holds the SOL paid out after the exit fee */
+export function simulateSell(sharesToSell: number, marketState: MarketState): TradeSimulation {
+    const startSupply = marketState.totalSharesMinted;
+    const shares = Math.min(sharesToSell, startSupply);
+    const { refund, fee, net } = quoteSell(toUnits(shares), toUnits(startSupply));
+
+    return tradeSimulation(
+        shares,
+        toSol(net),
+        fee,
+        toSol(refund),
+        shares > 0 ? toSol(refund) / shares : 0,
+        startSupply,
+        startSupply - shares,
+    );
+}
+
+/** A winning position's share of the resolution pot, in SOL */
+export function estimatePayout(shares: number, totalWinningShares: number, potSol: number): number {
+    return toSol(payoutLamports(toLamports(potSol), toUnits(shares), toUnits(totalWinningShares)));
+}
@@ -0,0 +1,69 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// DJINN CURVE WASM - Library builds of djinn-curve for JS and native callers
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Build:
+//   wasm-pack build crates/djinn-curve-wasm --release --target nodejs   (scripts, pkg/)
+//   wasm-pack build crates/djinn-curve-wasm --release --target web      (frontend)
+//   cargo build -p djinn-curve-wasm --release   (native .so / .dylib with the djinn_* C exports)
+//
+// Supplies (shares * 1e9) and lamports cross the boundary as u64, i.e. JS BigInt.
+// Any error, or a result that does not fit in u64, returns FFI_ERROR (u64::MAX).
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use wasm_bindgen::prelude::*;
+
+pub use djinn_curve::ffi::*;
+
+/// The error sentinel every export returns on failure
+#[wasm_bindgen(js_name = ffiError)]
+pub fn ffi_error() -> u64 {
+    FFI_ERROR
+}
+
+/// Spot price at `supply`, in lamports per whole share
+#[wasm_bindgen(js_name = spotPrice)]
+pub fn spot_price(supply: u64) -> u64 {
+    djinn_spot_price(supply)
+}
+
+/// Lamports to move the supply from `supply_old` to `supply_new`
+#[wasm_bindgen]
+pub fn cost(supply_old: u64, supply_new: u64) -> u64 {
+    djinn_cost(supply_old, supply_new)
+}
+
+/// Shares bought with `sol_in` lamports (after fees) at `supply`, rounded down
+#[wasm_bindgen(js_name = sharesFromSol)]
+pub fn shares_from_sol(sol_in: u64, supply: u64) -> u64 {
+    djinn_shares_from_sol(sol_in, supply)
+}
+
+#[wasm_bindgen(js_name = entryFee)]
+pub fn entry_fee(sol_in: u64) -> u64 {
+    djinn_entry_fee(sol_in)
+}
+
+#[wasm_bindgen(js_name = exitFee)]
+pub fn exit_fee(refund: u64) -> u64 {
+    djinn_exit_fee(refund)
+}
+
+/// Creator cut of a trading fee
+#[wasm_bindgen(js_name = creatorFeeCut)]
+pub fn creator_fee_cut(fee: u64, creator_is_treasury: bool) -> u64 {
+    djinn_creator_fee_cut(fee, creator_is_treasury)
+}
+
+/// Insurance cut of a trading fee; the treasury keeps the rest
+#[wasm_bindgen(js_name = insuranceFeeCut)]
+pub fn insurance_fee_cut(fee: u64, creator_is_treasury: bool) -> u64 {
+    djinn_insurance_fee_cut(fee, creator_is_treasury)
+}
+
+/// A winning position's share of the resolution pot snapshot
+#[wasm_bindgen(js_name = proRataPayout)]
+pub fn pro_rata_payout(pot: u64, shares: u64, total_winning_shares: u64) -> u64 {
+    djinn_pro_rata_payout(pot, shares, total_winning_shares)
+}
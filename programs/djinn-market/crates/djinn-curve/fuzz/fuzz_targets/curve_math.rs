@@ -0,0 +1,73 @@
+#![no_main]
+
+// Curve math fuzzer: every entry point must return an error rather than panic or wrap
+// for any u128 input, and the in-range results must stay consistent.
+//
+// Run with `cargo fuzz run curve_math` from programs/djinn-market/crates/djinn-curve.
+
+use djinn_curve::curve::PiecewiseCurve;
+use djinn_curve::lmsr::{
+    lmsr_buy_cost, lmsr_sell_refund, lmsr_shares_for, lmsr_shares_to_release, LMSR_MAX_LIQUIDITY,
+    LMSR_MIN_LIQUIDITY,
+};
+use djinn_curve::{
+    calculate_cost, calculate_curve_area, calculate_shares_from_sol, calculate_spot_price, BondingCurve,
+    CurveConfig, CurveKind, TOTAL_SUPPLY,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u128, u128, u128, u8)| {
+    let (a, b, c, selector) = input;
+
+    // Full u128 input space: must not panic
+    let _ = calculate_spot_price(a);
+    let _ = calculate_curve_area(a);
+    let _ = calculate_cost(a, b);
+    let _ = calculate_shares_from_sol(c, a);
+
+    // In-range invariants on the global curve
+    let mut supplies = [a % (TOTAL_SUPPLY + 1), b % (TOTAL_SUPPLY + 1), c % (TOTAL_SUPPLY + 1)];
+    supplies.sort_unstable();
+    let [x, y, z] = supplies;
+    let whole = calculate_cost(x, z).unwrap();
+    let split = calculate_cost(x, y).unwrap() + calculate_cost(y, z).unwrap();
+    assert_eq!(whole, split, "path dependence: {} -> {} -> {}", x, y, z);
+    assert!(calculate_spot_price(x).unwrap() <= calculate_spot_price(z).unwrap());
+
+    let sol_in = c % 1_000_000_000_000_000_000;
+    let shares = calculate_shares_from_sol(sol_in, x).unwrap();
+    assert!(calculate_cost(x, x + shares).unwrap() <= sol_in);
+    if let Ok(released) = PiecewiseCurve.shares_to_release(sol_in, z) {
+        assert!(calculate_cost(z - released, z).unwrap() >= sol_in);
+    }
+
+    // Per-market curves with fuzzed parameters
+    let kind = match selector % 4 {
+        0 => CurveKind::Piecewise,
+        1 => CurveKind::Linear,
+        2 => CurveKind::LmsrStyle,
+        _ => CurveKind::ConstantProduct,
+    };
+    let curve = CurveConfig { kind, param_a: a, param_b: b };
+    if curve.validate().is_ok() {
+        let _ = curve.cost(c, a);
+        let _ = curve.shares_for(c, b);
+        let shares = curve.shares_for(sol_in, x).unwrap();
+        assert!(curve.cost(x, x + shares).unwrap() <= sol_in);
+        if let Ok(released) = curve.shares_to_release(sol_in, z) {
+            assert!(curve.cost(z - released, z).unwrap() >= sol_in);
+        }
+    }
+
+    // LMSR with fuzzed supplies and liquidity
+    let liquidity = LMSR_MIN_LIQUIDITY + c % (LMSR_MAX_LIQUIDITY - LMSR_MIN_LIQUIDITY + 1);
+    let outcomes = [x, y, z];
+    let outcome = (selector % 3) as usize;
+    let _ = lmsr_buy_cost(&[a, b, c], liquidity, outcome, a);
+    let _ = lmsr_sell_refund(&[a, b, c], liquidity, outcome, b);
+    let bought = lmsr_shares_for(&outcomes, liquidity, outcome, sol_in).unwrap();
+    assert!(lmsr_buy_cost(&outcomes, liquidity, outcome, bought).unwrap() <= sol_in);
+    if let Ok(released) = lmsr_shares_to_release(&outcomes, liquidity, outcome, sol_in) {
+        assert!(lmsr_sell_refund(&outcomes, liquidity, outcome, released).unwrap() >= sol_in);
+    }
+});
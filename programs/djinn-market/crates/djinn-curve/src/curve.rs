@@ -0,0 +1,378 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// BONDING CURVE MODULE - Pluggable per-market pricing curves
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Every curve is described by its cumulative area (lamports * SHARE_SCALE) and
+// the inverse of that area. Cost and share quotes are derived from those two, so
+// every curve is path-independent and rounds in favour of the vault.
+//
+// - Piecewise:       the global "Aggressive V4" 3-phase curve (default)
+// - Linear:          straight line from a start price to an end price at TOTAL_SUPPLY
+// - LmsrStyle:       p_max · σ(s / b), a binary LMSR price against a fixed counter-side
+// - ConstantProduct: x·y = k against virtual SOL / share reserves
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::fixed_point::*;
+use crate::math::{isqrt, mul_div};
+use crate::{CurveError, Result, SHARE_SCALE, TOTAL_SUPPLY};
+
+// Highest start/end/max price a creator may configure (10 SOL per share)
+pub const MAX_CURVE_PRICE: u128 = 10_000_000_000;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CURVE TRAIT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub trait BondingCurve {
+    /// Spot price at `supply`, in lamports per whole share
+    fn spot_price(&self, supply: u128) -> Result<u128>;
+
+    /// Cumulative area under the price curve up to `supply` (lamports * SHARE_SCALE)
+    fn area(&self, supply: u128) -> Result<u128>;
+
+    /// Largest supply whose cumulative area does not exceed `area`
+    fn supply_at_area(&self, area: u128) -> Result<u128>;
+
+    /// Highest supply the curve can issue
+    fn max_supply(&self) -> u128 {
+        TOTAL_SUPPLY
+    }
+
+    /// Cost in lamports to move from supply_old to supply_new
+    fn cost(&self, supply_old: u128, supply_new: u128) -> Result<u128> {
+        if supply_new <= supply_old {
+            return Ok(0);
+        }
+        let area_old = self.area(supply_old)? / SHARE_SCALE;
+        let area_new = self.area(supply_new)? / SHARE_SCALE;
+        // Every curve's area is non-decreasing, so this never underflows
+        area_new.checked_sub(area_old).ok_or(CurveError::MathError)
+    }
+
+    /// Shares bought with `sol_in` lamports at `supply_old`, rounded down
+    fn shares_for(&self, sol_in: u128, supply_old: u128) -> Result<u128> {
+        if sol_in == 0 {
+            return Ok(0);
+        }
+        // Highest cumulative area whose lamport floor is still covered by sol_in
+        let paid = (self.area(supply_old)? / SHARE_SCALE).checked_add(sol_in).ok_or(CurveError::MathError)?;
+        let target_area = paid
+            .checked_add(1)
+            .and_then(|p| p.checked_mul(SHARE_SCALE))
+            .ok_or(CurveError::MathError)?
+            - 1;
+        // Budget covers the rest of the curve: skip the inverse, whose intermediates could overflow
+        let max_supply = self.max_supply();
+        if target_area >= self.area(max_supply)? {
+            return Ok(max_supply.saturating_sub(supply_old));
+        }
+        let supply_new = self.supply_at_area(target_area)?.min(max_supply);
+        Ok(supply_new.saturating_sub(supply_old))
+    }
+
+    /// Fewest shares to sell at `supply_old` for a refund of at least `refund` lamports
+    fn shares_to_release(&self, refund: u128, supply_old: u128) -> Result<u128> {
+        if refund == 0 {
+            return Ok(0);
+        }
+        // Highest cumulative area whose lamport floor sits `refund` below supply_old's
+        let remaining = (self.area(supply_old)? / SHARE_SCALE)
+            .checked_sub(refund)
+            .ok_or(CurveError::InsufficientSupply)?;
+        let target_area = remaining
+            .checked_add(1)
+            .and_then(|r| r.checked_mul(SHARE_SCALE))
+            .ok_or(CurveError::MathError)?
+            - 1;
+        if self.area(0)? > target_area {
+            return Err(CurveError::InsufficientSupply);
+        }
+        let supply_new = self.supply_at_area(target_area)?.min(supply_old);
+        Ok(supply_old - supply_new)
+    }
+}
+
+/// Pins an approximate inverse to the largest x in [0, max] with area_at(x) <= area,
+/// galloping out from `guess` to bracket the answer and bisecting the bracket.
+pub fn settle_on_area<F>(guess: u128, max: u128, area: u128, area_at: F) -> Result<u128>
+where
+    F: Fn(u128) -> Result<u128>,
+{
+    settle_on_area_within(guess, 1, max, area, area_at)
+}
+
+/// settle_on_area for a guess known to be within about `width` of the answer: the
+/// gallop starts at that step, so bracketing takes one or two evaluations and the
+/// bisection about log2(width).
+pub fn settle_on_area_within<F>(guess: u128, width: u128, max: u128, area: u128, area_at: F) -> Result<u128>
+where
+    F: Fn(u128) -> Result<u128>,
+{
+    if area_at(max)? <= area {
+        return Ok(max);
+    }
+
+    // Bracket: area_at(low) <= area < area_at(high)
+    let guess = guess.min(max);
+    let (mut low, mut high);
+    let mut step = width.max(1);
+    if area_at(guess)? <= area {
+        low = guess;
+        loop {
+            high = low.saturating_add(step).min(max);
+            if area_at(high)? > area {
+                break;
+            }
+            low = high;
+            step = step.saturating_mul(2);
+        }
+    } else {
+        high = guess;
+        loop {
+            low = high.saturating_sub(step);
+            if low == 0 || area_at(low)? <= area {
+                break;
+            }
+            high = low;
+            step = step.saturating_mul(2);
+        }
+    }
+
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if area_at(mid)? <= area {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+    Ok(low)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CURVE IMPLEMENTATIONS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// The global 3-phase "Aggressive V4" curve
+pub struct PiecewiseCurve;
+
+impl BondingCurve for PiecewiseCurve {
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        crate::piecewise::calculate_spot_price(supply)
+    }
+
+    fn area(&self, supply: u128) -> Result<u128> {
+        crate::piecewise::calculate_curve_area(supply)
+    }
+
+    fn supply_at_area(&self, area: u128) -> Result<u128> {
+        crate::piecewise::supply_for_area(self, area)
+    }
+}
+
+/// P(s) = start + (end - start) · s / TOTAL_SUPPLY
+pub struct LinearCurve {
+    pub start_price: u128,
+    pub end_price: u128,
+}
+
+impl BondingCurve for LinearCurve {
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        let delta = mul_div(supply, self.end_price - self.start_price, TOTAL_SUPPLY)
+            .ok_or(CurveError::MathError)?;
+        self.start_price.checked_add(delta).ok_or(CurveError::MathError)
+    }
+
+    fn area(&self, supply: u128) -> Result<u128> {
+        let supply_sq = supply.checked_mul(supply).ok_or(CurveError::MathError)?;
+        let ramp = mul_div(supply_sq, self.end_price - self.start_price, 2 * TOTAL_SUPPLY)
+            .ok_or(CurveError::MathError)?;
+        self.start_price
+            .checked_mul(supply)
+            .and_then(|base| base.checked_add(ramp))
+            .ok_or(CurveError::MathError)
+    }
+
+    fn supply_at_area(&self, area: u128) -> Result<u128> {
+        // s = 2a / (start + √(start² + 2·(end - start)·a / TOTAL_SUPPLY)), scaled so end·s ≈ 1e18
+        let s = FIXED_ONE / self.end_price;
+        let disc = mul_div(area, 2 * (self.end_price - self.start_price) * s * s, TOTAL_SUPPLY)
+            .ok_or(CurveError::MathError)?;
+        let root = isqrt((self.start_price * self.start_price * s * s).checked_add(disc).ok_or(CurveError::MathError)?);
+        let double_area = area.checked_mul(2).ok_or(CurveError::MathError)?;
+        let guess = mul_div(double_area, s, self.start_price * s + root).ok_or(CurveError::MathError)?;
+        settle_on_area(guess, TOTAL_SUPPLY, area, |x| self.area(x))
+    }
+}
+
+/// P(s) = p_max · σ(s / b): the price of one side of a binary LMSR whose other side never trades
+pub struct LmsrStyleCurve {
+    pub max_price: u128,
+    pub liquidity: u128,
+}
+
+impl LmsrStyleCurve {
+    fn ratio(&self, supply: u128) -> Result<i128> {
+        let ratio = mul_div(supply, FIXED_ONE, self.liquidity).ok_or(CurveError::MathError)?;
+        i128::try_from(ratio).map_err(|_| CurveError::MathError)
+    }
+
+    fn depth(&self) -> Result<u128> {
+        self.max_price.checked_mul(self.liquidity).ok_or(CurveError::MathError)
+    }
+}
+
+impl BondingCurve for LmsrStyleCurve {
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        // p_max / (1 + e^(-s/b))
+        let decay = exp_fixed(-self.ratio(supply)?).ok_or(CurveError::MathError)?;
+        mul_div(self.max_price, FIXED_ONE, FIXED_ONE + decay).ok_or(CurveError::MathError)
+    }
+
+    fn area(&self, supply: u128) -> Result<u128> {
+        // p_max · b · (ln(1 + e^(s/b)) - ln 2), non-decreasing in s
+        let softplus = softplus_monotone(self.ratio(supply)?).ok_or(CurveError::MathError)?;
+        let excess = (softplus - LN_2).max(0) as u128;
+        mul_div(self.depth()?, excess, FIXED_ONE).ok_or(CurveError::MathError)
+    }
+
+    fn supply_at_area(&self, area: u128) -> Result<u128> {
+        // w = a / (p_max · b) + ln 2, then s / b = ln(e^w - 1) = w + ln(1 - e^-w)
+        let w = mul_div(area, FIXED_ONE, self.depth()?).ok_or(CurveError::MathError)?;
+        let guess = if w > 40 * FIXED_ONE {
+            mul_div(w, self.liquidity, FIXED_ONE).ok_or(CurveError::MathError)?
+        } else {
+            let w = w as i128 + LN_2;
+            let tail = exp_fixed(-w).ok_or(CurveError::MathError)?;
+            let log = ln_fixed(FIXED_ONE - tail).ok_or(CurveError::MathError)?;
+            let ratio = (w + log).max(0) as u128;
+            mul_div(ratio, self.liquidity, FIXED_ONE).ok_or(CurveError::MathError)?
+        };
+        settle_on_area(guess, TOTAL_SUPPLY, area, |x| self.area(x))
+    }
+}
+
+/// x·y = k against virtual reserves of `sol_reserve` lamports and `share_reserve` shares
+pub struct ConstantProductCurve {
+    pub sol_reserve: u128,
+    pub share_reserve: u128,
+}
+
+impl BondingCurve for ConstantProductCurve {
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        // SOL reserve / share reserve = V·Q / (Q - s)²
+        let remaining = self.share_reserve.checked_sub(supply).ok_or(CurveError::MathError)?;
+        let sol_side = mul_div(self.sol_reserve, self.share_reserve, remaining).ok_or(CurveError::MathError)?;
+        mul_div(sol_side, SHARE_SCALE, remaining).ok_or(CurveError::MathError)
+    }
+
+    fn area(&self, supply: u128) -> Result<u128> {
+        // Lamports paid from 0 to s: V·s / (Q - s)
+        let remaining = self.share_reserve.checked_sub(supply).ok_or(CurveError::MathError)?;
+        let scaled_reserve = self.sol_reserve.checked_mul(SHARE_SCALE).ok_or(CurveError::MathError)?;
+        mul_div(scaled_reserve, supply, remaining).ok_or(CurveError::MathError)
+    }
+
+    fn supply_at_area(&self, area: u128) -> Result<u128> {
+        // s = a·Q / (V·SHARE_SCALE + a)
+        let scaled_reserve = self.sol_reserve.checked_mul(SHARE_SCALE).ok_or(CurveError::MathError)?;
+        let denominator = scaled_reserve.checked_add(area).ok_or(CurveError::MathError)?;
+        let guess = mul_div(area, self.share_reserve, denominator).ok_or(CurveError::MathError)?;
+        settle_on_area(guess, TOTAL_SUPPLY, area, |x| self.area(x))
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PER-MARKET CURVE CONFIG
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CurveKind {
+    Piecewise = 0,       // Global 3-phase curve (params ignored)
+    Linear = 1,          // param_a = start price, param_b = end price (lamports / share)
+    LmsrStyle = 2,       // param_a = max price (lamports / share), param_b = liquidity b (shares * 1e9)
+    ConstantProduct = 3, // param_a = virtual SOL reserve (lamports), param_b = virtual share reserve
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveConfig {
+    pub kind: CurveKind,
+    pub param_a: u128,
+    pub param_b: u128,
+}
+
+impl CurveConfig {
+    pub fn piecewise() -> Self {
+        CurveConfig { kind: CurveKind::Piecewise, param_a: 0, param_b: 0 }
+    }
+
+    /// Reject parameters that would make the curve degenerate or overflow
+    pub fn validate(&self) -> Result<()> {
+        let valid = match self.kind {
+            CurveKind::Piecewise => true,
+            CurveKind::Linear => {
+                self.param_a > 0 && self.param_a <= self.param_b && self.param_b <= MAX_CURVE_PRICE
+            }
+            CurveKind::LmsrStyle => {
+                self.param_a > 0
+                    && self.param_a <= MAX_CURVE_PRICE
+                    && self.param_b >= SHARE_SCALE
+                    && self.param_b <= TOTAL_SUPPLY
+            }
+            CurveKind::ConstantProduct => {
+                // Share reserve must outlast TOTAL_SUPPLY by at least one share
+                self.param_a > 0
+                    && self.param_a <= FIXED_ONE
+                    && self.param_b >= TOTAL_SUPPLY + SHARE_SCALE
+                    && self.param_b <= 10 * TOTAL_SUPPLY
+            }
+        };
+        if !valid {
+            return Err(CurveError::InvalidParams);
+        }
+        Ok(())
+    }
+
+    fn linear(&self) -> LinearCurve {
+        LinearCurve { start_price: self.param_a, end_price: self.param_b }
+    }
+
+    fn lmsr_style(&self) -> LmsrStyleCurve {
+        LmsrStyleCurve { max_price: self.param_a, liquidity: self.param_b }
+    }
+
+    fn constant_product(&self) -> ConstantProductCurve {
+        ConstantProductCurve { sol_reserve: self.param_a, share_reserve: self.param_b }
+    }
+}
+
+impl BondingCurve for CurveConfig {
+    fn spot_price(&self, supply: u128) -> Result<u128> {
+        match self.kind {
+            CurveKind::Piecewise => PiecewiseCurve.spot_price(supply),
+            CurveKind::Linear => self.linear().spot_price(supply),
+            CurveKind::LmsrStyle => self.lmsr_style().spot_price(supply),
+            CurveKind::ConstantProduct => self.constant_product().spot_price(supply),
+        }
+    }
+
+    fn area(&self, supply: u128) -> Result<u128> {
+        match self.kind {
+            CurveKind::Piecewise => PiecewiseCurve.area(supply),
+            CurveKind::Linear => self.linear().area(supply),
+            CurveKind::LmsrStyle => self.lmsr_style().area(supply),
+            CurveKind::ConstantProduct => self.constant_product().area(supply),
+        }
+    }
+
+    fn supply_at_area(&self, area: u128) -> Result<u128> {
+        match self.kind {
+            CurveKind::Piecewise => PiecewiseCurve.supply_at_area(area),
+            CurveKind::Linear => self.linear().supply_at_area(area),
+            CurveKind::LmsrStyle => self.lmsr_style().supply_at_area(area),
+            CurveKind::ConstantProduct => self.constant_product().supply_at_area(area),
+        }
+    }
+}
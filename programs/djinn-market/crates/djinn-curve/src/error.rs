@@ -0,0 +1,30 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// ERRORS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurveError {
+    /// Overflow, underflow or division by zero in an intermediate
+    MathError,
+    /// Curve or market parameters outside their supported range
+    InvalidParams,
+    /// Not enough outstanding supply to release the requested amount
+    InsufficientSupply,
+}
+
+pub type Result<T> = core::result::Result<T, CurveError>;
+
+impl fmt::Display for CurveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurveError::MathError => write!(f, "Math error"),
+            CurveError::InvalidParams => write!(f, "Invalid curve parameters"),
+            CurveError::InsufficientSupply => write!(f, "Insufficient supply"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CurveError {}
@@ -0,0 +1,183 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// FEE MATH - Trading / resolution fees and how they are split
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Trading fees (entry 1%, exit 1%):
+// - User/Bot created market: 40% Creator / 50% Treasury / 10% Insurance
+// - G1 created market:       90% Treasury / 10% Insurance
+//
+// Resolution fee (2% of the pot): 50% Treasury / 50% Bounty (insurance vault)
+//
+// A market may replace the flat trading fees with a FeeSchedule: an entry fee that
+// ramps up approaching resolution, a discount for the market's first volume, and an
+// exit surcharge on positions held only briefly.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::math::mul_div;
+use crate::{CurveError, Result};
+
+// FEE CONSTANTS
+pub const ENTRY_FEE_BPS: u128 = 100;    // 1%
+pub const EXIT_FEE_BPS: u128 = 100;     // 1%
+pub const RESOLUTION_FEE_BPS: u128 = 200; // 2%
+pub const BPS_DENOMINATOR: u128 = 10_000;
+pub const MAX_TRADING_FEE_BPS: u128 = 1_000; // 10%, ceiling for any scheduled fee
+
+// TRADING FEE SPLIT (percent of the fee)
+pub const CREATOR_FEE_PCT: u128 = 40;
+pub const INSURANCE_FEE_PCT: u128 = 10;
+
+/// `amount` · bps / 10_000, rounded down
+pub fn fee_from_bps(amount: u128, bps: u128) -> Result<u128> {
+    mul_div(amount, bps, BPS_DENOMINATOR).ok_or(CurveError::MathError)
+}
+
+/// Entry fee taken from a buy
+pub fn entry_fee(sol_in: u128) -> Result<u128> {
+    fee_from_bps(sol_in, ENTRY_FEE_BPS)
+}
+
+/// Exit fee taken from a sell refund
+pub fn exit_fee(refund: u128) -> Result<u128> {
+    fee_from_bps(refund, EXIT_FEE_BPS)
+}
+
+/// Gross amount that still nets at least `net` once a `bps` fee is taken from it
+fn gross_for_net(net: u128, bps: u128) -> Result<u128> {
+    let kept = BPS_DENOMINATOR - bps;
+    Ok(net
+        .checked_mul(BPS_DENOMINATOR)
+        .and_then(|n| n.checked_add(kept - 1))
+        .ok_or(CurveError::MathError)?
+        / kept)
+}
+
+/// Fee on top of `net` lamports, grossed up so that `net + fee` charged `bps` nets at
+/// least `net`
+pub fn fee_for_net(net: u128, bps: u128) -> Result<u128> {
+    Ok(gross_for_net(net, bps)? - net)
+}
+
+/// Sell refund that leaves at least `net` lamports after a `bps` exit fee
+pub fn refund_for_net(net: u128, bps: u128) -> Result<u128> {
+    gross_for_net(net, bps)
+}
+
+/// Entry fee on top of `net` lamports, grossed up so that `net + fee` bought through
+/// `entry_fee` nets at least `net`
+pub fn entry_fee_for_net(net: u128) -> Result<u128> {
+    fee_for_net(net, ENTRY_FEE_BPS)
+}
+
+/// Sell refund that leaves at least `net` lamports after `exit_fee`
+pub fn exit_refund_for_net(net: u128) -> Result<u128> {
+    refund_for_net(net, EXIT_FEE_BPS)
+}
+
+/// Per-market trading fee schedule. All extras default to 0, which leaves the flat
+/// ENTRY_FEE_BPS / EXIT_FEE_BPS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub entry_bps: u128,
+    pub exit_bps: u128,
+    pub ramp_window_secs: u64,    // Entry fee ramps up over the last window before resolution
+    pub ramp_max_bps: u128,       // Extra entry bps reached at resolution time
+    pub early_volume: u128,       // Lamports of market volume that get the discount
+    pub early_discount_bps: u128, // Entry bps waived while volume < early_volume
+    pub min_hold_secs: u64,       // Exits sooner than this after a buy pay the surcharge
+    pub short_hold_bps: u128,     // Extra exit bps on short holds
+}
+
+impl FeeSchedule {
+    pub const FLAT: FeeSchedule = FeeSchedule {
+        entry_bps: ENTRY_FEE_BPS,
+        exit_bps: EXIT_FEE_BPS,
+        ramp_window_secs: 0,
+        ramp_max_bps: 0,
+        early_volume: 0,
+        early_discount_bps: 0,
+        min_hold_secs: 0,
+        short_hold_bps: 0,
+    };
+
+    /// Reject schedules that could charge more than MAX_TRADING_FEE_BPS or discount
+    /// below zero
+    pub fn validate(&self) -> Result<()> {
+        let max_entry = self.entry_bps.checked_add(self.ramp_max_bps).ok_or(CurveError::InvalidParams)?;
+        let max_exit = self.exit_bps.checked_add(self.short_hold_bps).ok_or(CurveError::InvalidParams)?;
+        if max_entry > MAX_TRADING_FEE_BPS
+            || max_exit > MAX_TRADING_FEE_BPS
+            || self.early_discount_bps > self.entry_bps
+        {
+            return Err(CurveError::InvalidParams);
+        }
+        Ok(())
+    }
+
+    /// Entry fee bps `secs_to_resolution` before resolution, after `volume` lamports of
+    /// market volume: the early discount, then a linear ramp over the final window
+    pub fn entry_bps_at(&self, secs_to_resolution: u64, volume: u128) -> u128 {
+        let mut bps = self.entry_bps;
+        if volume < self.early_volume {
+            bps -= self.early_discount_bps.min(bps);
+        }
+        if secs_to_resolution < self.ramp_window_secs {
+            let elapsed = (self.ramp_window_secs - secs_to_resolution) as u128;
+            bps += self.ramp_max_bps * elapsed / self.ramp_window_secs as u128;
+        }
+        bps
+    }
+
+    /// Exit fee bps for a position whose last buy was `held_secs` ago
+    pub fn exit_bps_at(&self, held_secs: u64) -> u128 {
+        if held_secs < self.min_hold_secs {
+            self.exit_bps + self.short_hold_bps
+        } else {
+            self.exit_bps
+        }
+    }
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        FeeSchedule::FLAT
+    }
+}
+
+/// Resolution fee taken from the pot
+pub fn resolution_fee(pot: u128) -> Result<u128> {
+    fee_from_bps(pot, RESOLUTION_FEE_BPS)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FeeSplit {
+    pub creator: u128,
+    pub treasury: u128,
+    pub insurance: u128,
+}
+
+/// Split a trading fee. The treasury absorbs rounding, so the parts always sum to `fee`.
+pub fn split_trading_fee(fee: u128, creator_is_treasury: bool) -> Result<FeeSplit> {
+    let insurance = mul_div(fee, INSURANCE_FEE_PCT, 100).ok_or(CurveError::MathError)?;
+    let creator = if creator_is_treasury {
+        0
+    } else {
+        mul_div(fee, CREATOR_FEE_PCT, 100).ok_or(CurveError::MathError)?
+    };
+    Ok(FeeSplit { creator, treasury: fee - creator - insurance, insurance })
+}
+
+/// Split a resolution fee into (treasury, bounty)
+pub fn split_resolution_fee(fee: u128) -> (u128, u128) {
+    let treasury = fee / 2;
+    (treasury, fee - treasury)
+}
+
+/// Relative move of the spot price caused by a trade, in bps of the pre-trade price
+pub fn price_impact_bps(spot_before: u128, spot_after: u128) -> Result<u128> {
+    if spot_before == 0 {
+        return Ok(0);
+    }
+    mul_div(spot_before.abs_diff(spot_after), BPS_DENOMINATOR, spot_before).ok_or(CurveError::MathError)
+}
@@ -0,0 +1,63 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// FFI - extern "C" exports for the WASM / native library build
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Built into a library by the djinn-curve-wasm crate, which links these symbols into its
+// native cdylib and wraps them with wasm-bindgen for JS (see its src/lib.rs).
+//
+// Supplies and lamports fit in u64 (TOTAL_SUPPLY = 1e18), which JS reads as BigInt.
+// Any error, or a result that does not fit in u64, returns FFI_ERROR.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::fees::{entry_fee, exit_fee, split_trading_fee};
+use crate::payout::pro_rata_payout;
+use crate::piecewise::{calculate_cost, calculate_shares_from_sol, calculate_spot_price};
+use crate::Result;
+
+pub const FFI_ERROR: u64 = u64::MAX;
+
+fn to_ffi(value: Result<u128>) -> u64 {
+    value.ok().and_then(|v| u64::try_from(v).ok()).unwrap_or(FFI_ERROR)
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_spot_price(supply: u64) -> u64 {
+    to_ffi(calculate_spot_price(supply as u128))
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_cost(supply_old: u64, supply_new: u64) -> u64 {
+    to_ffi(calculate_cost(supply_old as u128, supply_new as u128))
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_shares_from_sol(sol_in: u64, supply: u64) -> u64 {
+    to_ffi(calculate_shares_from_sol(sol_in as u128, supply as u128))
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_entry_fee(sol_in: u64) -> u64 {
+    to_ffi(entry_fee(sol_in as u128))
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_exit_fee(refund: u64) -> u64 {
+    to_ffi(exit_fee(refund as u128))
+}
+
+/// Creator cut of a trading fee (the treasury and insurance cuts follow from it)
+#[no_mangle]
+pub extern "C" fn djinn_creator_fee_cut(fee: u64, creator_is_treasury: bool) -> u64 {
+    to_ffi(split_trading_fee(fee as u128, creator_is_treasury).map(|split| split.creator))
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_insurance_fee_cut(fee: u64, creator_is_treasury: bool) -> u64 {
+    to_ffi(split_trading_fee(fee as u128, creator_is_treasury).map(|split| split.insurance))
+}
+
+#[no_mangle]
+pub extern "C" fn djinn_pro_rata_payout(pot: u64, shares: u64, total_winning_shares: u64) -> u64 {
+    to_ffi(pro_rata_payout(pot as u128, shares as u128, total_winning_shares as u128))
+}
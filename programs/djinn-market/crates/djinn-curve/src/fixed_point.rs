@@ -0,0 +1,110 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// FIXED-POINT MATH - Deterministic exp / ln in 1e18 integer arithmetic
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Values are scaled by FIXED_ONE (1e18). No floats: every validator computes the
+// exact same result. Relative error is ~1e-17 across the supported range.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub const FIXED_ONE: u128 = 1_000_000_000_000_000_000; // 1.0
+pub const FIXED_ONE_I: i128 = FIXED_ONE as i128;
+pub const LN_2: i128 = 693_147_180_559_945_309; // ln(2) * 1e18
+
+// Below this e^x rounds to zero at 1e18 precision; above it e^x overflows u128
+pub const EXP_MIN_INPUT: i128 = -42 * FIXED_ONE_I;
+pub const EXP_MAX_INPUT: i128 = 47 * FIXED_ONE_I;
+
+/// e^x for fixed-point x. Returns None if the result does not fit in u128.
+pub fn exp_fixed(x: i128) -> Option<u128> {
+    if x < EXP_MIN_INPUT {
+        return Some(0);
+    }
+    if x > EXP_MAX_INPUT {
+        return None;
+    }
+
+    // Range reduction: x = k·ln2 + r with |r| <= ln2 / 2
+    let k = (x + LN_2 / 2).div_euclid(LN_2);
+    let r = x - k * LN_2;
+
+    // Taylor series for e^r
+    let mut sum = FIXED_ONE_I;
+    let mut term = FIXED_ONE_I;
+    for n in 1..30 {
+        term = term * r / (n * FIXED_ONE_I);
+        if term == 0 {
+            break;
+        }
+        sum += term;
+    }
+
+    let base = sum as u128;
+    if k >= 0 {
+        base.checked_mul(1u128 << k)
+    } else {
+        Some(base >> (-k))
+    }
+}
+
+/// Natural log of fixed-point x (x > 0). Returns None for x == 0.
+pub fn ln_fixed(x: u128) -> Option<i128> {
+    if x == 0 {
+        return None;
+    }
+
+    // Normalise x = m · 2^k with m in [1, 2)
+    let mut m = x;
+    let mut k: i128 = 0;
+    if m >= 2 * FIXED_ONE {
+        let shift = 127 - (m / FIXED_ONE).leading_zeros();
+        m >>= shift;
+        k = shift as i128;
+    }
+    while m < FIXED_ONE {
+        m <<= 1;
+        k -= 1;
+    }
+
+    // ln(m) = 2·atanh(z) = 2·(z + z³/3 + z⁵/5 + ...) with z = (m - 1) / (m + 1) < 1/3
+    let z = ((m - FIXED_ONE) * FIXED_ONE / (m + FIXED_ONE)) as i128;
+    let z_sq = z * z / FIXED_ONE_I;
+    let mut power = z;
+    let mut sum = 0i128;
+    let mut n = 1i128;
+    while power != 0 {
+        sum += power / n;
+        power = power * z_sq / FIXED_ONE_I;
+        n += 2;
+    }
+
+    Some(k * LN_2 + 2 * sum)
+}
+
+/// Softplus ln(1 + e^x) for fixed-point x. Always positive, never overflows.
+pub fn softplus_fixed(x: i128) -> Option<i128> {
+    if x > 40 * FIXED_ONE_I {
+        // ln(1 + e^x) = x + ln(1 + e^-x), and e^-x is below 1e-17 here
+        return Some(x);
+    }
+    ln_fixed(FIXED_ONE + exp_fixed(x)?)
+}
+
+// Grid step for softplus_monotone (1e-9). Over one step softplus rises by σ(x)·1e-9,
+// over 4e4 units of 1e-18 for x >= -10, far more than softplus_fixed's ~1e-17 relative
+// error (a few hundred units at most): consecutive grid values always increase.
+pub const SOFTPLUS_STEP: i128 = 1_000_000_000;
+
+/// Softplus interpolated linearly between grid points SOFTPLUS_STEP apart, so that it
+/// never decreases (for x >= -10): the grid values increase and the interpolation never
+/// passes the next one. Within 1 unit of 1e-18 of the smooth curve (step² / 8).
+pub fn softplus_monotone(x: i128) -> Option<i128> {
+    let low_x = x.div_euclid(SOFTPLUS_STEP) * SOFTPLUS_STEP;
+    let frac = x - low_x;
+    let low = softplus_fixed(low_x)?;
+    if frac == 0 {
+        return Some(low);
+    }
+    let high = softplus_fixed(low_x + SOFTPLUS_STEP)?;
+    Some(low + (high - low) * frac / SOFTPLUS_STEP)
+}
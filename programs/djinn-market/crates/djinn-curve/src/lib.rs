@@ -0,0 +1,28 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// DJINN CURVE - Shared bonding curve, fee split and payout math
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// One source of truth for the on-chain program, the frontend (WASM) and scripts.
+// Pure integer math, no dependencies, `no_std` with `default-features = false`.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod error;
+pub mod curve;
+pub mod fees;
+pub mod fixed_point;
+pub mod lmsr;
+pub mod math;
+pub mod payout;
+pub mod piecewise;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub use curve::{settle_on_area, settle_on_area_within, BondingCurve, CurveConfig, CurveKind};
+pub use error::{CurveError, Result};
+pub use fees::*;
+pub use math::{isqrt, mul_div};
+pub use piecewise::*;
@@ -0,0 +1,171 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// LMSR MODULE - Logarithmic Market Scoring Rule for N-outcome markets
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Cost function: C(q) = b · ln(Σ e^(q_i / b))
+//
+// - Outcome prices p_i = e^(q_i / b) / Σ e^(q_j / b) always sum to 1
+// - One share unit pays out exactly 1 lamport if its outcome wins
+//   (a whole share, SHARE_SCALE units, pays 1 SOL)
+// - The creator funds C(0) = b · ln(N) at creation, which bounds the market
+//   maker's worst-case loss, so the vault always covers every winning share
+//
+// All sums use the log-sum-exp form (exponents relative to the largest supply),
+// so e^x never overflows no matter how lopsided the market gets.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::curve::settle_on_area;
+use crate::fixed_point::*;
+use crate::math::mul_div;
+use crate::{CurveError, Result, SHARE_SCALE, TOTAL_SUPPLY};
+
+// Liquidity bounds (share units): 1 share .. 1M shares of depth
+pub const LMSR_MIN_LIQUIDITY: u128 = SHARE_SCALE;
+pub const LMSR_MAX_LIQUIDITY: u128 = 1_000_000 * SHARE_SCALE;
+
+pub fn validate_liquidity(liquidity_b: u128) -> Result<()> {
+    if !(LMSR_MIN_LIQUIDITY..=LMSR_MAX_LIQUIDITY).contains(&liquidity_b) {
+        return Err(CurveError::InvalidParams);
+    }
+    Ok(())
+}
+
+/// (q_i - q_max) / b in fixed point (always <= 0)
+fn relative_exponent(q_i: u128, q_max: u128, b: u128) -> Result<i128> {
+    let gap = mul_div(q_max - q_i, FIXED_ONE, b).ok_or(CurveError::MathError)?;
+    Ok(-(gap.min(-EXP_MIN_INPUT as u128 + FIXED_ONE) as i128))
+}
+
+/// C(q) scaled by FIXED_ONE (lamports * 1e18)
+pub fn lmsr_cost_fixed(supplies: &[u128], b: u128) -> Result<u128> {
+    let q_max = supplies.iter().copied().max().unwrap_or(0);
+
+    let mut sum: u128 = 0;
+    for &q in supplies {
+        sum += exp_fixed(relative_exponent(q, q_max, b)?).ok_or(CurveError::MathError)?;
+    }
+
+    // The largest term is exactly FIXED_ONE, so ln(sum) >= 0
+    let ln_sum = ln_fixed(sum).ok_or(CurveError::MathError)?.max(0) as u128;
+    q_max
+        .checked_mul(FIXED_ONE)
+        .and_then(|base| b.checked_mul(ln_sum).and_then(|log| base.checked_add(log)))
+        .ok_or(CurveError::MathError)
+}
+
+/// Lamports the creator must deposit at creation: C(0) = b · ln(N), rounded up
+pub fn lmsr_funding(num_outcomes: u8, b: u128) -> Result<u128> {
+    let zeros = [0u128; 6];
+    Ok(lmsr_cost_fixed(&zeros[..num_outcomes as usize], b)?.div_ceil(FIXED_ONE))
+}
+
+/// Lamports to buy `shares` of `outcome`, rounded up in favour of the vault
+pub fn lmsr_buy_cost(supplies: &[u128], b: u128, outcome: usize, shares: u128) -> Result<u128> {
+    if shares == 0 {
+        return Ok(0);
+    }
+    let before = lmsr_cost_fixed(supplies, b)?;
+
+    let mut after_supplies = [0u128; 6];
+    after_supplies[..supplies.len()].copy_from_slice(supplies);
+    after_supplies[outcome] = after_supplies[outcome].checked_add(shares).ok_or(CurveError::MathError)?;
+    let after = lmsr_cost_fixed(&after_supplies[..supplies.len()], b)?;
+
+    Ok(after.saturating_sub(before).div_ceil(FIXED_ONE))
+}
+
+/// Lamports returned for selling `shares` of `outcome`, rounded down
+pub fn lmsr_sell_refund(supplies: &[u128], b: u128, outcome: usize, shares: u128) -> Result<u128> {
+    if shares == 0 {
+        return Ok(0);
+    }
+    let before = lmsr_cost_fixed(supplies, b)?;
+
+    let mut after_supplies = [0u128; 6];
+    after_supplies[..supplies.len()].copy_from_slice(supplies);
+    after_supplies[outcome] = after_supplies[outcome].checked_sub(shares).ok_or(CurveError::MathError)?;
+    let after = lmsr_cost_fixed(&after_supplies[..supplies.len()], b)?;
+
+    Ok(before.saturating_sub(after) / FIXED_ONE)
+}
+
+/// Shares of `outcome` bought with `sol_in` lamports, rounded down.
+///
+/// Closed form: with T = C(q) + sol_in,
+///   q_i' = T + b · ln(1 - Σ_{j≠i} e^((q_j - T) / b))
+/// which is then pinned exactly against lmsr_buy_cost.
+pub fn lmsr_shares_for(supplies: &[u128], b: u128, outcome: usize, sol_in: u128) -> Result<u128> {
+    if sol_in == 0 {
+        return Ok(0);
+    }
+    let q_i = supplies[outcome];
+    let max_shares = TOTAL_SUPPLY.saturating_sub(q_i);
+
+    let target = (lmsr_cost_fixed(supplies, b)? / FIXED_ONE).checked_add(sol_in).ok_or(CurveError::MathError)?;
+    let guess = supply_at_cost(supplies, b, outcome, target)?.saturating_sub(q_i);
+
+    settle_on_area(guess, max_shares, sol_in, |shares| lmsr_buy_cost(supplies, b, outcome, shares))
+}
+
+/// Fewest shares of `outcome` to sell for a refund of at least `refund` lamports.
+///
+/// Same closed form as lmsr_shares_for with T = C(q) - refund, pinned against
+/// lmsr_sell_refund.
+pub fn lmsr_shares_to_release(supplies: &[u128], b: u128, outcome: usize, refund: u128) -> Result<u128> {
+    if refund == 0 {
+        return Ok(0);
+    }
+    let q_i = supplies[outcome];
+
+    let target = (lmsr_cost_fixed(supplies, b)? / FIXED_ONE)
+        .checked_sub(refund)
+        .ok_or(CurveError::InsufficientSupply)?;
+    let guess = q_i.saturating_sub(supply_at_cost(supplies, b, outcome, target)?);
+
+    // Most shares whose refund still falls short, plus one
+    let short = settle_on_area(guess, q_i, refund - 1, |shares| lmsr_sell_refund(supplies, b, outcome, shares))?;
+    if short == q_i {
+        return Err(CurveError::InsufficientSupply);
+    }
+    Ok(short + 1)
+}
+
+/// Approximate supply of `outcome` at which C(q) reaches `target` lamports, the other
+/// supplies held fixed (0 when the others alone already cost more)
+fn supply_at_cost(supplies: &[u128], b: u128, outcome: usize, target: u128) -> Result<u128> {
+    let mut others: u128 = 0;
+    for (j, &q_j) in supplies.iter().enumerate() {
+        if j != outcome {
+            others += exp_fixed(relative_exponent(q_j.min(target), target, b)?).ok_or(CurveError::MathError)?;
+        }
+    }
+    if others >= FIXED_ONE {
+        return Ok(0);
+    }
+    let log = (-ln_fixed(FIXED_ONE - others).ok_or(CurveError::MathError)?).max(0) as u128;
+    let drop = mul_div(b, log, FIXED_ONE).ok_or(CurveError::MathError)?;
+    Ok(target.saturating_sub(drop))
+}
+
+/// Implied probability of `outcome` in fixed point (sums to FIXED_ONE across outcomes)
+pub fn lmsr_probability(supplies: &[u128], b: u128, outcome: usize) -> Result<u128> {
+    let q_max = supplies.iter().copied().max().unwrap_or(0);
+
+    let mut sum: u128 = 0;
+    let mut term: u128 = 0;
+    for (j, &q) in supplies.iter().enumerate() {
+        let e = exp_fixed(relative_exponent(q, q_max, b)?).ok_or(CurveError::MathError)?;
+        sum += e;
+        if j == outcome {
+            term = e;
+        }
+    }
+    mul_div(term, FIXED_ONE, sum).ok_or(CurveError::MathError)
+}
+
+/// Spot price of `outcome` in lamports per whole share (at most 1 SOL)
+pub fn lmsr_spot_price(supplies: &[u128], b: u128, outcome: usize) -> Result<u128> {
+    let probability = lmsr_probability(supplies, b, outcome)?;
+    mul_div(probability, SHARE_SCALE, FIXED_ONE).ok_or(CurveError::MathError)
+}
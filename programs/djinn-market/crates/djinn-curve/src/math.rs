@@ -0,0 +1,60 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// INTEGER HELPERS - 256-bit mul/div and integer square root
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// (a * b) / denominator, rounded down, with a 256-bit intermediate product.
+/// Returns None on division by zero or if the quotient does not fit in u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+
+    // Full 256-bit product as (hi, lo) from 64-bit limbs
+    const MASK: u128 = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & MASK);
+    let (b_hi, b_lo) = (b >> 64, b & MASK);
+    let ll = a_lo * b_lo;
+    let lh = a_lo * b_hi;
+    let hl = a_hi * b_lo;
+    let hh = a_hi * b_hi;
+    let mid = (ll >> 64) + (lh & MASK) + (hl & MASK);
+    let lo = (ll & MASK) | (mid << 64);
+    let hi = hh + (lh >> 64) + (hl >> 64) + (mid >> 64);
+
+    if hi == 0 {
+        return Some(lo / denominator);
+    }
+    if hi >= denominator {
+        return None;
+    }
+
+    // Long division of (hi, lo) by the denominator, one bit at a time
+    let mut rem = hi;
+    let mut quo: u128 = 0;
+    for i in (0..128).rev() {
+        let carry = rem >> 127;
+        rem = (rem << 1) | ((lo >> i) & 1);
+        quo <<= 1;
+        if carry == 1 || rem >= denominator {
+            rem = rem.wrapping_sub(denominator);
+            quo |= 1;
+        }
+    }
+    Some(quo)
+}
+
+/// Integer square root (floor) via Newton's method
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let bits = 128 - n.leading_zeros();
+    let mut x: u128 = 1 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
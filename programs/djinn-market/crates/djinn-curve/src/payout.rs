@@ -0,0 +1,70 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// PAYOUT MATH - What winners receive after resolution
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// - Curve markets: winners split the pot snapshot pro rata to their shares
+// - LMSR markets:  every winning share unit pays exactly 1 lamport; the resolution
+//   fee comes out of the surplus and the rest of the surplus returns to the creator
+// - Scalar markets: the pot splits between LONG and SHORT by where the resolved value
+//   falls between the bounds, then each side is paid pro rata to its shares
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::fees::{fee_from_bps, BPS_DENOMINATOR, RESOLUTION_FEE_BPS};
+use crate::math::mul_div;
+use crate::{CurveError, Result};
+
+/// pot · shares / total_winning_shares, rounded down (0 if nobody holds the winner)
+pub fn pro_rata_payout(pot: u128, shares: u128, total_winning_shares: u128) -> Result<u128> {
+    if total_winning_shares == 0 {
+        return Ok(0);
+    }
+    mul_div(pot, shares, total_winning_shares).ok_or(CurveError::MathError)
+}
+
+/// LMSR payout: 1 lamport per share unit, never more than the vault holds
+pub fn lmsr_payout(shares: u128, vault_balance: u128) -> u128 {
+    shares.min(vault_balance)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LmsrSettlement {
+    pub fee: u128,            // Resolution fee, capped at the surplus
+    pub creator_refund: u128, // Unused subsidy returned to the creator
+}
+
+/// Split an LMSR vault at resolution. `liability` is the winning outcome's supply.
+pub fn lmsr_settlement(vault_balance: u128, liability: u128) -> Result<LmsrSettlement> {
+    lmsr_settlement_at(vault_balance, liability, RESOLUTION_FEE_BPS)
+}
+
+/// lmsr_settlement with a resolution fee of `fee_bps` instead of RESOLUTION_FEE_BPS
+pub fn lmsr_settlement_at(vault_balance: u128, liability: u128, fee_bps: u128) -> Result<LmsrSettlement> {
+    let surplus = vault_balance.saturating_sub(liability);
+    let fee = fee_from_bps(vault_balance, fee_bps)?.min(surplus);
+    Ok(LmsrSettlement { fee, creator_refund: surplus - fee })
+}
+
+/// LONG's share of a scalar market's pot in bps: where `value` sits between `lower` and
+/// `upper`, clamped to the bounds (SHORT gets the rest)
+pub fn scalar_long_bps(value: i64, lower: i64, upper: i64) -> Result<u128> {
+    if upper <= lower {
+        return Err(CurveError::InvalidParams);
+    }
+    let clamped = value.clamp(lower, upper);
+    let offset = (clamped as i128 - lower as i128) as u128;
+    let range = (upper as i128 - lower as i128) as u128;
+    mul_div(offset, BPS_DENOMINATOR, range).ok_or(CurveError::MathError)
+}
+
+/// Split a scalar pot into (LONG, SHORT) side pots. A side nobody holds leaves its
+/// share to the other side, so the whole pot stays claimable.
+pub fn scalar_side_pots(pot: u128, long_bps: u128, long_supply: u128, short_supply: u128) -> Result<(u128, u128)> {
+    let long = match (long_supply, short_supply) {
+        (0, 0) => 0,
+        (_, 0) => pot,
+        (0, _) => 0,
+        _ => fee_from_bps(pot, long_bps)?,
+    };
+    Ok((long, pot - long))
+}
@@ -0,0 +1,478 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// DJINN CURVE V4 AGGRESSIVE: "EARLY BIRD REWARDS"
+// 3-Phase Piecewise Bonding Curve with Progressive Gains
+// Phase 1: Linear (0-50M → 6x) | Phase 2: Quadratic (50M-90M → 15x) | Phase 3: Sigmoid (90M+)
+// Progressive: 10M=2x, 20M=3x, 30M=4x, 40M=5x, 50M=6x
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use crate::curve::{settle_on_area_within, BondingCurve, PiecewiseCurve};
+use crate::fixed_point::{exp_fixed, ln_fixed, softplus_fixed, softplus_monotone, FIXED_ONE};
+use crate::math::{isqrt, mul_div};
+use crate::{CurveError, Result};
+
+// --- GLOBAL CONSTANTS ---
+pub const TOTAL_SUPPLY: u128 = 1_000_000_000_000_000_000; // 1B Shares * 1e9 (9 decimals)
+pub const ANCHOR_THRESHOLD: u128 = 100_000_000_000_000_000; // 100M * 1e9
+pub const SHARE_SCALE: u128 = 1_000_000_000; // 9 decimals per share
+
+// PHASE BOUNDARIES (Scaled by 1e9) - AGGRESSIVE CURVE
+pub const PHASE1_END: u128 = 100_000_000_000_000_000;  // 100M
+pub const PHASE2_END: u128 = 200_000_000_000_000_000;  // 200M
+pub const PHASE3_START: u128 = 200_000_000_000_000_000;
+
+// PRICE CONSTANTS (in Lamports, 1 SOL = 1e9 Lamports)
+pub const P_START: u128 = 1_000;       // 1000 lamports (0.000001 SOL) - Higher base for depth
+pub const P_50: u128 = 25_000;         // 25k - Steep slope for fast gains
+pub const P_90: u128 = 250_000;        // 250k - Quadratic acceleration
+pub const P_MAX: u128 = 950_000_000;   // 0.95 SOL Max
+
+// 🔥 OPTIMAL ANCHOR: 1M Shares (Aggressive Pump Mode)
+// 1 SOL Buy => ~2x Price
+pub const VIRTUAL_ANCHOR: u128 = 1_000_000_000_000_000; // 1M * 1e9 
+
+
+// SIGMOID (Phase 3): true logistic in 1e18 fixed-point
+// P = P_90 + (P_MAX - P_90) · (σ(t) - σ(-Y)) / (1 - σ(-Y)),  t = Y · (x - MIDPOINT) / MIDPOINT
+// where x is the supply past PHASE3_START. Starts exactly at P_90 and approaches P_MAX.
+// Philosophy: GRADUAL GROWTH for democratization
+// Y is solved so the slope at PHASE2_END matches the quadratic bridge:
+// (P_MAX - P_90) · Y · σ(-Y) / MIDPOINT = 2 · (P_90 - P_50) / (PHASE2_END - PHASE1_END)
+pub const SIGMOID_MIDPOINT: u128 = 400_000_000_000_000_000; // 400M past PHASE3_START
+pub const SIGMOID_STEEPNESS: u128 = 8_395_933_898_027_740_491; // Y * 1e18
+pub const K_SCALE_FACTOR: u128 = 1_000_000_000_000_000_000; // 1e18
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CURVE MATH (V4 AGGRESSIVE: 3-PHASE PIECEWISE)
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Linear slope for Phase 1: m = (P_50 - P_START) / PHASE1_END
+fn get_linear_slope() -> u128 {
+    // (25000 - 1000) / 100e15 → steeper slope for faster gains (constants, cannot overflow)
+    ((P_50 - P_START) * K_SCALE_FACTOR) / PHASE1_END
+}
+
+/// Calculate spot price at given supply (in nanoSOL/Lamports)
+pub fn calculate_spot_price(supply: u128) -> Result<u128> {
+    // VIRTUAL ANCHOR: We add this to make the curve start at a stable point (50/50 odds)
+    let effective_supply = supply.checked_add(VIRTUAL_ANCHOR).ok_or(CurveError::MathError)?;
+
+    if effective_supply <= PHASE1_END {
+        // PHASE 1: LINEAR RAMP
+        let slope = get_linear_slope();
+        let price_delta = mul_div(slope, effective_supply, K_SCALE_FACTOR).ok_or(CurveError::MathError)?;
+        Ok(P_START + price_delta)
+    } else if effective_supply <= PHASE2_END {
+        // PHASE 2: QUADRATIC BRIDGE
+        calculate_bridge_price(effective_supply)
+    } else {
+        // PHASE 3: SIGMOID
+        calculate_sigmoid_price(effective_supply)
+    }
+}
+
+/// Quadratic Bridge: P = P_50 + (P_90 - P_50) * t²
+fn calculate_bridge_price(supply: u128) -> Result<u128> {
+    let progress = supply.checked_sub(PHASE1_END).ok_or(CurveError::MathError)?;
+    let range = PHASE2_END - PHASE1_END; // 100M
+    
+    // Quadratic acceleration: P = P_50 + (P_90 - P_50) * (progress/range)²
+    let ratio = mul_div(progress, 1_000_000, range).ok_or(CurveError::MathError)?; // Scaled by 1e6
+    let ratio_sq = mul_div(ratio, ratio, 1_000_000).ok_or(CurveError::MathError)?; // (progress/range)² * 1e6
+    
+    let price_delta = mul_div(P_90 - P_50, ratio_sq, 1_000_000).ok_or(CurveError::MathError)?;
+    P_50.checked_add(price_delta).ok_or(CurveError::MathError)
+}
+
+/// Sigmoid Phase: P = P_90 + (P_MAX - P_90) * normalized_sigmoid(x - 200M)
+fn calculate_sigmoid_price(supply: u128) -> Result<u128> {
+    let x_rel = supply.checked_sub(PHASE3_START).ok_or(CurveError::MathError)?;
+    let floor = logistic(-(SIGMOID_STEEPNESS as i128))?;
+    let sig = logistic(sigmoid_exponent(x_rel)?)?;
+
+    // Normalised so that x_rel = 0 maps to exactly P_90
+    let norm_sig = sig.saturating_sub(floor);
+    let price_delta = mul_div(P_MAX - P_90, norm_sig, FIXED_ONE - floor).ok_or(CurveError::MathError)?;
+    P_90.checked_add(price_delta).ok_or(CurveError::MathError)
+}
+
+/// t = Y · (x - MIDPOINT) / MIDPOINT in fixed point
+fn sigmoid_exponent(x_rel: u128) -> Result<i128> {
+    let (gap, sign) = if x_rel >= SIGMOID_MIDPOINT {
+        (x_rel - SIGMOID_MIDPOINT, 1)
+    } else {
+        (SIGMOID_MIDPOINT - x_rel, -1)
+    };
+    let t = mul_div(gap, SIGMOID_STEEPNESS, SIGMOID_MIDPOINT).ok_or(CurveError::MathError)?;
+    let t = i128::try_from(t).map_err(|_| CurveError::MathError)?;
+    Ok(sign * t)
+}
+
+/// σ(t) = 1 / (1 + e^-t) in fixed point
+fn logistic(t: i128) -> Result<u128> {
+    let e = exp_fixed(-t).ok_or(CurveError::MathError)?;
+    mul_div(FIXED_ONE, FIXED_ONE, FIXED_ONE + e).ok_or(CurveError::MathError)
+}
+
+/// Area under Phase 1 from effective supply 0 to `x` (x <= PHASE1_END):
+/// ∫ P_START + slope·s/K ds = P_START·x + slope·x² / 2K
+fn linear_area(x: u128) -> Result<u128> {
+    let slope = get_linear_slope();
+    let x_sq = x.checked_mul(x).ok_or(CurveError::MathError)?;
+    let ramp = mul_div(x_sq, slope, 2 * K_SCALE_FACTOR).ok_or(CurveError::MathError)?;
+    P_START
+        .checked_mul(x)
+        .and_then(|base| base.checked_add(ramp))
+        .ok_or(CurveError::MathError)
+}
+
+/// Area under the quadratic bridge from PHASE1_END to PHASE1_END + `y`:
+/// ∫ P_50 + (P_90 - P_50)·(s/range)² ds = P_50·y + (P_90 - P_50)·y³ / 3range²
+fn bridge_area(y: u128) -> Result<u128> {
+    let range = PHASE2_END - PHASE1_END;
+    let y_sq = y.checked_mul(y).ok_or(CurveError::MathError)?;
+    let cube_over_range = mul_div(y_sq, y, range).ok_or(CurveError::MathError)?;
+    let accel = mul_div(cube_over_range, P_90 - P_50, 3 * range).ok_or(CurveError::MathError)?;
+    P_50
+        .checked_mul(y)
+        .and_then(|base| base.checked_add(accel))
+        .ok_or(CurveError::MathError)
+}
+
+/// Area under Phase 3 from PHASE3_START to PHASE3_START + `z`:
+/// ∫ σ(t(s)) ds = (MIDPOINT / Y) · (softplus(t(z)) - softplus(-Y)), so
+/// area = P_90·z + (P_MAX - P_90) · (∫σ - σ(-Y)·z) / (1 - σ(-Y))
+///      = (α·z + β·(softplus(t(z)) - softplus(-Y))) / (1 - σ(-Y))
+/// with α = P_90·(1 - σ(-Y)) - (P_MAX - P_90)·σ(-Y) > 0 and β = (P_MAX - P_90)·MIDPOINT / Y.
+///
+/// Monotone by construction: t(z) and softplus_monotone are non-decreasing, α and β are
+/// non-negative constants and each term is floored on its own, so area(z + 1) >= area(z)
+/// for every z and lamport-floored costs telescope exactly.
+fn sigmoid_area(z: u128) -> Result<u128> {
+    let floor = logistic(-(SIGMOID_STEEPNESS as i128))?;
+    let denominator = FIXED_ONE - floor;
+    let alpha = P_90 * denominator - (P_MAX - P_90) * floor;
+    let beta = mul_div((P_MAX - P_90) * SIGMOID_MIDPOINT, FIXED_ONE, SIGMOID_STEEPNESS).ok_or(CurveError::MathError)?;
+
+    let softplus_start = softplus_monotone(-(SIGMOID_STEEPNESS as i128)).ok_or(CurveError::MathError)?;
+    let softplus_end = softplus_monotone(sigmoid_exponent(z)?).ok_or(CurveError::MathError)?;
+    let log_gain = u128::try_from(softplus_end - softplus_start).map_err(|_| CurveError::MathError)?;
+
+    let linear = mul_div(alpha, z, denominator).ok_or(CurveError::MathError)?;
+    let logistic_rise = mul_div(beta, log_gain, denominator).ok_or(CurveError::MathError)?;
+    linear.checked_add(logistic_rise).ok_or(CurveError::MathError)
+}
+
+/// Exact area under the spot price curve from effective supply 0 to `effective_supply`.
+/// Units are lamports scaled by SHARE_SCALE, so that differences stay exact.
+fn curve_area_at(effective_supply: u128) -> Result<u128> {
+    if effective_supply <= PHASE1_END {
+        return linear_area(effective_supply);
+    }
+    let phase1 = linear_area(PHASE1_END)?;
+    if effective_supply <= PHASE2_END {
+        return Ok(phase1 + bridge_area(effective_supply - PHASE1_END)?);
+    }
+    let phase2 = bridge_area(PHASE2_END - PHASE1_END)?;
+    sigmoid_area(effective_supply - PHASE3_START)?
+        .checked_add(phase1 + phase2)
+        .ok_or(CurveError::MathError)
+}
+
+/// Cumulative curve area (lamports * SHARE_SCALE) up to `supply`, including the virtual anchor
+pub fn calculate_curve_area(supply: u128) -> Result<u128> {
+    let effective_supply = supply.checked_add(VIRTUAL_ANCHOR).ok_or(CurveError::MathError)?;
+    curve_area_at(effective_supply)
+}
+
+/// Calculate cost to buy from supply_old to supply_new
+///
+/// Closed-form integral of the 3-phase curve, split exactly at PHASE1_END / PHASE2_END.
+/// The cost is a difference of the cumulative area (floored to lamports), which never
+/// decreases, so it is path-independent: cost(a→c) == cost(a→b) + cost(b→c) for any
+/// a <= b <= c.
+pub fn calculate_cost(supply_old: u128, supply_new: u128) -> Result<u128> {
+    PiecewiseCurve.cost(supply_old, supply_new)
+}
+
+// Fixed-point scale for the square root in the quadratic inverse
+const SQRT_SCALE_LINEAR: u128 = 1_000_000_000_000; // 1e12
+
+/// Inverse of linear_area: x = 2a / (P_START + √(P_START² + 2·slope·a/K))
+fn invert_linear_area(area: u128) -> Result<u128> {
+    let s = SQRT_SCALE_LINEAR;
+    let slope = get_linear_slope();
+    let disc = mul_div(area, 2 * slope * s * s, K_SCALE_FACTOR).ok_or(CurveError::MathError)?;
+    let root = isqrt((P_START * P_START * s * s).checked_add(disc).ok_or(CurveError::MathError)?);
+    let double_area = area.checked_mul(2).ok_or(CurveError::MathError)?;
+    mul_div(double_area, s, P_START * s + root).ok_or(CurveError::MathError)
+}
+
+/// Cumulative area at `effective` supply less `base`, evaluated through `curve`
+fn area_above<C: BondingCurve>(curve: &C, effective: u128, base: u128) -> Result<u128> {
+    curve.area(effective - VIRTUAL_ANCHOR)?.checked_sub(base).ok_or(CurveError::MathError)
+}
+
+/// Inverse of bridge_area (the area past `phase1`): Newton's method on the cubic, started
+/// from the right of the root (area ≥ P_50·y) so that every step moves monotonically
+/// towards it.
+fn invert_bridge_area<C: BondingCurve>(curve: &C, phase1: u128, area: u128) -> Result<u128> {
+    let range = PHASE2_END - PHASE1_END;
+    let mut y = (area / P_50).min(range);
+    for _ in 0..12 {
+        let excess = area_above(curve, PHASE1_END + y, phase1)?.saturating_sub(area);
+        // Derivative (the spot price) carried with SHARE_SCALE extra precision
+        let price = P_50 * SHARE_SCALE
+            + mul_div(y * y, (P_90 - P_50) * SHARE_SCALE, range * range).ok_or(CurveError::MathError)?;
+        let step = mul_div(excess, SHARE_SCALE, price).ok_or(CurveError::MathError)?;
+        if step == 0 {
+            break;
+        }
+        y -= step;
+    }
+    Ok(y)
+}
+
+/// Upper bound on the root of sigmoid_area(z) = area from the logistic term alone:
+/// (P_MAX - P_90) / (1 - σ(-Y)) · (MIDPOINT / Y) · (softplus(t) - softplus(-Y)) = area,
+/// solved as t = ln(e^v - 1). The linear terms it drops add more area than they take
+/// (P_90 is above the floor's share of the price), so the true root lies left of it.
+fn sigmoid_root_bound(area: u128) -> Result<u128> {
+    let floor = logistic(-(SIGMOID_STEEPNESS as i128))?;
+    let sig_integral = mul_div(area, FIXED_ONE - floor, (P_MAX - P_90) * FIXED_ONE).ok_or(CurveError::MathError)?;
+    let log_gain = mul_div(sig_integral, SIGMOID_STEEPNESS, SIGMOID_MIDPOINT).ok_or(CurveError::MathError)?;
+    let softplus_start = softplus_fixed(-(SIGMOID_STEEPNESS as i128)).ok_or(CurveError::MathError)?;
+    let v = i128::try_from(log_gain).map_err(|_| CurveError::MathError)? + softplus_start;
+
+    // t = v + ln(1 - e^-v)
+    let tail = exp_fixed(-v).ok_or(CurveError::MathError)?;
+    let t = v + ln_fixed(FIXED_ONE.saturating_sub(tail).max(1)).ok_or(CurveError::MathError)?;
+    let offset = mul_div(t.unsigned_abs(), SIGMOID_MIDPOINT, SIGMOID_STEEPNESS).ok_or(CurveError::MathError)?;
+    Ok(if t >= 0 {
+        SIGMOID_MIDPOINT.saturating_add(offset + 1)
+    } else {
+        (SIGMOID_MIDPOINT + 1).saturating_sub(offset)
+    })
+}
+
+/// Inverse of sigmoid_area (the area past `phase2`): Newton's method started from the
+/// right of the root, at the
+/// tighter of area / P_90 and sigmoid_root_bound. Convex below the midpoint, so steps
+/// approach from the right; past it the first step lands left of the root and the rest
+/// approach from the left.
+///
+/// Newton stops once it is within two lamports of `area` (or its steps stop shrinking)
+/// and leaves the last few units to the bisection. Returns the estimate and its last
+/// step, which bounds how far it is from the exact answer.
+fn invert_sigmoid_area<C: BondingCurve>(curve: &C, phase2: u128, area: u128) -> Result<(u128, u128)> {
+    let max_z = TOTAL_SUPPLY + VIRTUAL_ANCHOR - PHASE3_START;
+    let mut z = (area / P_90).min(sigmoid_root_bound(area)?).min(max_z);
+    let mut last_step = u128::MAX;
+    for _ in 0..16 {
+        let current = area_above(curve, PHASE3_START + z, phase2)?;
+        let price = curve.spot_price(PHASE3_START + z - VIRTUAL_ANCHOR)?;
+        let gap = current.abs_diff(area);
+        let step = gap / price;
+        if gap <= 2 * SHARE_SCALE || step >= last_step {
+            return Ok((z, step));
+        }
+        last_step = step;
+        z = if current > area { z.saturating_sub(step) } else { (z + step).min(max_z) };
+    }
+    Ok((z, last_step))
+}
+
+/// Largest supply whose cumulative area does not exceed `area` (0 if none does).
+///
+/// The per-phase inverses land within a few units of the answer (Phase 3: within the
+/// width of its last Newton step); the result is then pinned exactly against the
+/// curve's area by galloping out from the guess and bisecting. Every area and price
+/// it needs goes through `curve` (PiecewiseCurve itself in production), so the tests
+/// below can count what it costs by wrapping the curve.
+pub(crate) fn supply_for_area<C: BondingCurve>(curve: &C, area: u128) -> Result<u128> {
+    let phase1 = curve.area(PHASE1_END - VIRTUAL_ANCHOR)?;
+    let phase2 = curve.area(PHASE2_END - VIRTUAL_ANCHOR)?;
+    let (guess, width) = if area <= phase1 {
+        (invert_linear_area(area)?, 1)
+    } else if area <= phase2 {
+        (PHASE1_END + invert_bridge_area(curve, phase1, area - phase1)?, 1)
+    } else {
+        let (z, width) = invert_sigmoid_area(curve, phase2, area - phase2)?;
+        (PHASE3_START + z, width)
+    };
+
+    let guess = guess.saturating_sub(VIRTUAL_ANCHOR);
+    settle_on_area_within(guess, width, TOTAL_SUPPLY, area, |supply| curve.area(supply))
+}
+
+/// Solve for shares received given SOL input (analytic per-phase inverse)
+///
+/// Rounds down: returns the largest share count whose cost does not exceed `sol_in`,
+/// i.e. calculate_cost(old, old + shares) <= sol_in < calculate_cost(old, old + shares + 1).
+/// Any sub-share remainder of `sol_in` stays in the vault. Takes under half the curve
+/// evaluations of the 30-step binary search it replaced (measured by the tests below).
+pub fn calculate_shares_from_sol(sol_in: u128, supply_old: u128) -> Result<u128> {
+    PiecewiseCurve.shares_for(sol_in, supply_old)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    const S: u128 = SHARE_SCALE;
+
+    /// The binary search calculate_shares_from_sol used before the analytic inverse:
+    /// halve [supply_old, TOTAL_SUPPLY] on the curve's cost, 30 times. Kept as the oracle,
+    /// with the documented rounding (largest supply whose cost is <= sol_in) and without
+    /// its 1_000-unit early exit. Returns the final (low, high) bracket.
+    fn binary_search(curve: &impl BondingCurve, sol_in: u128, supply_old: u128, steps: u32) -> (u128, u128) {
+        if curve.cost(supply_old, TOTAL_SUPPLY).unwrap() <= sol_in {
+            return (TOTAL_SUPPLY, TOTAL_SUPPLY);
+        }
+        let mut low = supply_old;
+        let mut high = TOTAL_SUPPLY;
+        for _ in 0..steps {
+            if high - low <= 1 {
+                break;
+            }
+            let mid = low + (high - low) / 2;
+            if curve.cost(supply_old, mid).unwrap() <= sol_in {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        (low, high)
+    }
+
+    /// Largest supply whose area is <= area, by plain bisection
+    fn bisect_area(area: u128) -> u128 {
+        let (mut low, mut high) = (0, TOTAL_SUPPLY);
+        if calculate_curve_area(high).unwrap() <= area {
+            return high;
+        }
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            if calculate_curve_area(mid).unwrap() <= area {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Supplies in every phase, and either side of both phase boundaries
+    fn supplies() -> Vec<u128> {
+        let mut supplies = vec![0, 50_000_000 * S, 300_000_000 * S, 600_000_000 * S, 999_000_000 * S];
+        for boundary in [PHASE1_END - VIRTUAL_ANCHOR, PHASE2_END - VIRTUAL_ANCHOR] {
+            for offset in [1, 1_000, S] {
+                supplies.extend([boundary - offset, boundary, boundary + offset]);
+            }
+        }
+        supplies
+    }
+
+    const SOL_INS: [u128; 7] = [1, 1_000, 1_000_000, 1_000_000_000, 100_000_000_000, 10_000_000_000_000, 1_000_000_000_000_000];
+
+    /// PiecewiseCurve counting every area and spot price evaluated through it
+    #[derive(Default)]
+    struct CountingCurve {
+        evaluations: core::cell::Cell<u32>,
+    }
+
+    impl CountingCurve {
+        fn count(&self) {
+            self.evaluations.set(self.evaluations.get() + 1);
+        }
+    }
+
+    impl BondingCurve for CountingCurve {
+        fn spot_price(&self, supply: u128) -> Result<u128> {
+            self.count();
+            PiecewiseCurve.spot_price(supply)
+        }
+
+        fn area(&self, supply: u128) -> Result<u128> {
+            self.count();
+            PiecewiseCurve.area(supply)
+        }
+
+        fn supply_at_area(&self, area: u128) -> Result<u128> {
+            supply_for_area(self, area)
+        }
+    }
+
+    #[test]
+    fn shares_from_sol_matches_binary_search() {
+        for supply in supplies() {
+            for sol_in in SOL_INS {
+                let shares = calculate_shares_from_sol(sol_in, supply).unwrap();
+                let (low, _) = binary_search(&PiecewiseCurve, sol_in, supply, 128);
+                assert_eq!(supply + shares, low, "sol {} at {}", sol_in, supply);
+            }
+        }
+    }
+
+    #[test]
+    fn old_30_step_search_brackets_the_exact_answer() {
+        // The old search stopped with the answer somewhere in a bracket up to ~1e9 units
+        // wide and paid out its low end; the analytic inverse pins it
+        for supply in supplies() {
+            for sol_in in SOL_INS {
+                let shares = calculate_shares_from_sol(sol_in, supply).unwrap();
+                let (low, high) = binary_search(&PiecewiseCurve, sol_in, supply, 30);
+                assert!(low <= supply + shares && supply + shares <= high, "sol {} at {}", sol_in, supply);
+            }
+        }
+    }
+
+    #[test]
+    fn supply_for_area_matches_bisection() {
+        for supply in supplies() {
+            let area = calculate_curve_area(supply).unwrap();
+            for target in [area, area + S / 2, area + 1_000 * S, area + 1_000_000_000 * S] {
+                let exact = bisect_area(target);
+                let inverse = supply_for_area(&PiecewiseCurve, target).unwrap();
+                if inverse < TOTAL_SUPPLY {
+                    assert!(target < calculate_curve_area(inverse + 1).unwrap());
+                }
+                assert!(calculate_curve_area(inverse).unwrap() <= target);
+                assert_eq!(inverse, exact, "area {}", target);
+            }
+        }
+    }
+
+    #[test]
+    fn settle_on_area_pins_any_guess() {
+        for supply in supplies() {
+            let target = calculate_curve_area(supply).unwrap() + S / 2;
+            let exact = bisect_area(target);
+            for guess in [0, TOTAL_SUPPLY, exact.saturating_sub(1_000_000 * S), exact + 1, exact] {
+                let settled = crate::curve::settle_on_area(guess, TOTAL_SUPPLY, target, calculate_curve_area).unwrap();
+                assert_eq!(settled, exact, "guess {}", guess);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_costs_a_fraction_of_the_old_search() {
+        // Areas and spot prices evaluated through the curve: the old search spends two
+        // areas per step, the inverse a few Newton steps and a short bisection over the
+        // width they leave
+        for supply in supplies() {
+            for sol_in in SOL_INS {
+                let inverse = CountingCurve::default();
+                let shares = inverse.shares_for(sol_in, supply).unwrap();
+                assert_eq!(shares, calculate_shares_from_sol(sol_in, supply).unwrap());
+                let search = CountingCurve::default();
+                binary_search(&search, sol_in, supply, 30);
+                // Both skip straight to the end when the budget buys out the curve
+                if supply + shares == TOTAL_SUPPLY {
+                    continue;
+                }
+                let (inverse, search) = (inverse.evaluations.get(), search.evaluations.get());
+                assert!(inverse * 2 <= search, "sol {} at {}: {} vs {}", sol_in, supply, inverse, search);
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+// Per-market curves: every CurveKind behind the BondingCurve trait keeps the same
+// guarantees as the global curve (monotone price and area, rounding in the vault's
+// favour).
+// Run with `cargo test -p djinn-curve --test curves`.
+
+use djinn_curve::{BondingCurve, CurveConfig, CurveKind, SHARE_SCALE, TOTAL_SUPPLY};
+
+const S: u128 = SHARE_SCALE;
+
+fn curves() -> [CurveConfig; 4] {
+    [
+        CurveConfig::piecewise(),
+        CurveConfig { kind: CurveKind::Linear, param_a: 1_000, param_b: 1_000_000_000 },
+        CurveConfig { kind: CurveKind::LmsrStyle, param_a: 1_000_000_000, param_b: 1_000_000 * S },
+        CurveConfig { kind: CurveKind::ConstantProduct, param_a: 1_000_000_000_000, param_b: 2 * TOTAL_SUPPLY },
+    ]
+}
+
+#[test]
+fn spot_price_never_falls() {
+    for curve in curves() {
+        curve.validate().unwrap();
+        let mut last = 0;
+        for i in 0..=1_000u128 {
+            let price = curve.spot_price(TOTAL_SUPPLY / 1_000 * i).unwrap();
+            assert!(price >= last, "{:?} at step {}", curve.kind, i);
+            last = price;
+        }
+    }
+}
+
+#[test]
+fn area_never_falls_unit_by_unit() {
+    // Unit steps, where a fixed-point area could dip by a rounding error
+    for curve in curves() {
+        for start in [0, 1_000_000 * S, TOTAL_SUPPLY / 2, TOTAL_SUPPLY - 2_000] {
+            let mut last = curve.area(start).unwrap();
+            for supply in start + 1..=start + 2_000 {
+                let area = curve.area(supply).unwrap();
+                assert!(area >= last, "{:?} at {}", curve.kind, supply);
+                last = area;
+            }
+        }
+    }
+}
+
+#[test]
+fn shares_for_rounds_down() {
+    for curve in curves() {
+        let mut supply = 0;
+        for sol_in in [1_000u128, 1_000_000, 1_000_000_000, 50_000_000_000] {
+            let shares = curve.shares_for(sol_in, supply).unwrap();
+            assert!(curve.cost(supply, supply + shares).unwrap() <= sol_in, "{:?}", curve.kind);
+            if supply + shares < curve.max_supply() {
+                assert!(curve.cost(supply, supply + shares + 1).unwrap() > sol_in, "{:?}", curve.kind);
+            }
+            supply += shares;
+        }
+    }
+}
+
+#[test]
+fn shares_to_release_rounds_up() {
+    for curve in curves() {
+        let supply = 10_000_000 * S;
+        let value = curve.cost(0, supply).unwrap();
+        for refund in [1u128, 1_000, value / 3, value] {
+            let shares = curve.shares_to_release(refund, supply).unwrap();
+            assert!(curve.cost(supply - shares, supply).unwrap() >= refund, "{:?}", curve.kind);
+            if shares > 0 {
+                assert!(curve.cost(supply - shares + 1, supply).unwrap() < refund, "{:?}", curve.kind);
+            }
+        }
+        assert!(curve.shares_to_release(value + 1, supply).is_err(), "{:?}", curve.kind);
+    }
+}
+
+#[test]
+fn degenerate_params_are_rejected() {
+    let invalid = [
+        CurveConfig { kind: CurveKind::Linear, param_a: 0, param_b: 1_000 },
+        CurveConfig { kind: CurveKind::Linear, param_a: 2_000, param_b: 1_000 },
+        CurveConfig { kind: CurveKind::Linear, param_a: 1_000, param_b: 20_000_000_000 },
+        CurveConfig { kind: CurveKind::LmsrStyle, param_a: 1_000, param_b: S - 1 },
+        CurveConfig { kind: CurveKind::LmsrStyle, param_a: 0, param_b: S },
+        CurveConfig { kind: CurveKind::ConstantProduct, param_a: 1_000, param_b: TOTAL_SUPPLY },
+        CurveConfig { kind: CurveKind::ConstantProduct, param_a: 0, param_b: 2 * TOTAL_SUPPLY },
+    ];
+    for curve in invalid {
+        assert!(curve.validate().is_err(), "{:?}", curve);
+    }
+}
@@ -0,0 +1,222 @@
+// Golden vectors: exact outputs the program and the frontend (native / WASM) must agree on.
+// Any change here is a pricing change and needs a deliberate update of these values.
+// Run with `cargo test -p djinn-curve --test golden`.
+
+use djinn_curve::curve::PiecewiseCurve;
+use djinn_curve::lmsr::{
+    lmsr_buy_cost, lmsr_funding, lmsr_probability, lmsr_sell_refund, lmsr_shares_for, lmsr_shares_to_release,
+    lmsr_spot_price,
+};
+use djinn_curve::payout::{
+    lmsr_payout, lmsr_settlement, lmsr_settlement_at, pro_rata_payout, scalar_long_bps, scalar_side_pots,
+    LmsrSettlement,
+};
+use djinn_curve::{
+    calculate_cost, calculate_curve_area, calculate_shares_from_sol, calculate_spot_price, entry_fee,
+    entry_fee_for_net, exit_fee, exit_refund_for_net, fee_for_net, price_impact_bps, refund_for_net, resolution_fee,
+    split_resolution_fee, split_trading_fee, BondingCurve, CurveConfig, CurveKind, FeeSchedule, FeeSplit,
+    SHARE_SCALE, TOTAL_SUPPLY,
+};
+
+const S: u128 = SHARE_SCALE;
+
+#[test]
+fn piecewise_spot_price_and_area() {
+    // (supply, spot price, area)
+    let vectors = [
+        (0, 1_240, 1_120_000_000_000_000_000),
+        (50_000_000 * S, 13_240, 363_120_000_000_000_000_000),
+        (99_000_000 * S, 25_000, 1_300_000_000_000_000_000_000),
+        (150_000_000 * S, 83_522, 3_569_882_500_000_000_000_000),
+        (250_000_000 * S, 660_760, 32_691_820_012_217_953_233_122),
+        (600_000_000 * S, 480_002_497, 31_863_487_247_339_388_666_129_668),
+        (TOTAL_SUPPLY, 949_790_015, 380_975_312_637_559_602_792_807_278),
+    ];
+    for (supply, price, area) in vectors {
+        assert_eq!(calculate_spot_price(supply).unwrap(), price, "spot at {}", supply);
+        assert_eq!(calculate_curve_area(supply).unwrap(), area, "area at {}", supply);
+    }
+}
+
+#[test]
+fn piecewise_cost() {
+    // (supply_old, supply_new, cost) - one per phase plus both boundaries
+    let vectors = [
+        (0, 1_000_000 * S, 1_360_000_000),
+        (90_000_000 * S, 110_000_000 * S, 500_262_500_000),
+        (190_000_000 * S, 210_000_000 * S, 5_117_676_841_162),
+        (500_000_000 * S, 501_000_000 * S, 106_714_248_159_785),
+    ];
+    for (old, new, cost) in vectors {
+        assert_eq!(calculate_cost(old, new).unwrap(), cost, "cost {} -> {}", old, new);
+    }
+}
+
+#[test]
+fn piecewise_shares_from_sol() {
+    // (sol_in, supply_old, shares)
+    let vectors = [
+        (1_000_000_000, 0, 751_760_302_225_583),
+        (10_000_000_000, 100_000_000 * S, 399_477_720_996_507),
+        (1_000_000_000, 300_000_000 * S, 549_726_956_466),
+        (100_000_000_000, 700_000_000 * S, 117_897_858_009),
+    ];
+    for (sol_in, supply, shares) in vectors {
+        assert_eq!(calculate_shares_from_sol(sol_in, supply).unwrap(), shares, "{} at {}", sol_in, supply);
+    }
+}
+
+#[test]
+fn piecewise_shares_to_release() {
+    // (refund, supply_old, shares)
+    let vectors = [
+        (1_000_000_000, 300_000_000 * S, 549_733_162_854),
+        (10_000_000_000, 150_000_000 * S, 119_925_655_901_706),
+    ];
+    for (refund, supply, shares) in vectors {
+        assert_eq!(PiecewiseCurve.shares_to_release(refund, supply).unwrap(), shares, "{} at {}", refund, supply);
+    }
+}
+
+#[test]
+fn alternative_curves() {
+    // (curve, spot at 100M, cost 100M -> 110M, shares for 1 SOL at 100M)
+    let vectors = [
+        (CurveConfig { kind: CurveKind::Linear, param_a: 1_000, param_b: 1_000_000 }, 100_900, 1_058_950_000_000, 9_910_316_579_978),
+        (
+            CurveConfig { kind: CurveKind::LmsrStyle, param_a: 1_000_000, param_b: 100_000_000 * S },
+            731_058,
+            7_407_363_759_721,
+            1_367_876_925_454,
+        ),
+        (
+            CurveConfig { kind: CurveKind::ConstantProduct, param_a: 30_000_000_000, param_b: 1_073_000_000 * S },
+            34,
+            343_543_590,
+            28_547_749_009_451_730,
+        ),
+    ];
+    for (curve, spot, cost, shares) in vectors {
+        curve.validate().unwrap();
+        assert_eq!(curve.spot_price(100_000_000 * S).unwrap(), spot, "{:?}", curve.kind);
+        assert_eq!(curve.cost(100_000_000 * S, 110_000_000 * S).unwrap(), cost, "{:?}", curve.kind);
+        assert_eq!(curve.shares_for(1_000_000_000, 100_000_000 * S).unwrap(), shares, "{:?}", curve.kind);
+    }
+}
+
+#[test]
+fn lmsr() {
+    let b = 1_000 * S;
+    let supplies = [0, 500 * S, 0];
+    assert_eq!(lmsr_funding(2, b).unwrap(), 693_147_180_560);
+    assert_eq!(lmsr_funding(4, b).unwrap(), 1_386_294_361_120);
+    assert_eq!(lmsr_buy_cost(&supplies, b, 0, 100 * S).unwrap(), 28_416_449_309);
+    assert_eq!(lmsr_sell_refund(&supplies, b, 1, 100 * S).unwrap(), 43_952_333_834);
+    assert_eq!(lmsr_shares_for(&supplies, b, 2, 1_000_000_000).unwrap(), 3_643_899_167);
+    assert_eq!(lmsr_shares_to_release(&supplies, b, 1, 10_000_000_000).unwrap(), 22_266_394_493);
+    assert_eq!(lmsr_probability(&supplies, b, 1).unwrap(), 451_862_761_877_606_045);
+    assert_eq!(lmsr_spot_price(&supplies, b, 1).unwrap(), 451_862_761);
+}
+
+#[test]
+fn fees_and_splits() {
+    assert_eq!(entry_fee(1_234_567_891).unwrap(), 12_345_678);
+    assert_eq!(exit_fee(999).unwrap(), 9);
+    assert_eq!(resolution_fee(55_555_555_555).unwrap(), 1_111_111_111);
+    assert_eq!(entry_fee_for_net(99).unwrap(), 1);
+    assert_eq!(entry_fee_for_net(1_000_000_000).unwrap(), 10_101_011);
+    assert_eq!(exit_refund_for_net(1_000_000_000).unwrap(), 1_010_101_011);
+
+    // Treasury absorbs rounding, and the creator cut when the creator is the treasury
+    assert_eq!(
+        split_trading_fee(12_345_679, false).unwrap(),
+        FeeSplit { creator: 4_938_271, treasury: 6_172_841, insurance: 1_234_567 }
+    );
+    assert_eq!(
+        split_trading_fee(12_345_679, true).unwrap(),
+        FeeSplit { creator: 0, treasury: 11_111_112, insurance: 1_234_567 }
+    );
+    assert_eq!(split_resolution_fee(1_111_111_111), (555_555_555, 555_555_556));
+
+    assert_eq!(price_impact_bps(250_000, 275_000).unwrap(), 1_000);
+    assert_eq!(price_impact_bps(1_000, 999).unwrap(), 10);
+    assert_eq!(price_impact_bps(0, 1_240).unwrap(), 0);
+}
+
+#[test]
+fn fee_schedule() {
+    let schedule = FeeSchedule {
+        entry_bps: 100,
+        exit_bps: 100,
+        ramp_window_secs: 3_600,
+        ramp_max_bps: 400,
+        early_volume: 10_000_000_000,
+        early_discount_bps: 50,
+        min_hold_secs: 600,
+        short_hold_bps: 200,
+    };
+    assert_eq!(schedule.validate(), Ok(()));
+    assert_eq!(FeeSchedule::default(), FeeSchedule::FLAT);
+    assert_eq!(FeeSchedule::FLAT.entry_bps_at(0, 0), 100);
+    assert_eq!(FeeSchedule::FLAT.exit_bps_at(0), 100);
+
+    // Early discount, then a linear ramp over the final hour
+    assert_eq!(schedule.entry_bps_at(7_200, 0), 50);
+    assert_eq!(schedule.entry_bps_at(7_200, 10_000_000_000), 100);
+    assert_eq!(schedule.entry_bps_at(3_600, 10_000_000_000), 100);
+    assert_eq!(schedule.entry_bps_at(1_800, 10_000_000_000), 300);
+    assert_eq!(schedule.entry_bps_at(900, 0), 350);
+    assert_eq!(schedule.entry_bps_at(0, 10_000_000_000), 500);
+
+    // Short-hold exit surcharge
+    assert_eq!(schedule.exit_bps_at(599), 300);
+    assert_eq!(schedule.exit_bps_at(600), 100);
+
+    // Gross-ups at arbitrary bps match the flat helpers at 1%
+    assert_eq!(fee_for_net(1_000_000_000, 100).unwrap(), entry_fee_for_net(1_000_000_000).unwrap());
+    assert_eq!(refund_for_net(1_000_000_000, 100).unwrap(), exit_refund_for_net(1_000_000_000).unwrap());
+    assert_eq!(fee_for_net(1_000_000_000, 300).unwrap(), 30_927_836);
+    assert_eq!(refund_for_net(1_000_000_000, 300).unwrap(), 1_030_927_836);
+
+    // No schedule may charge more than 10% or discount below zero
+    assert!(FeeSchedule { ramp_max_bps: 901, ..schedule }.validate().is_err());
+    assert!(FeeSchedule { short_hold_bps: 901, ..schedule }.validate().is_err());
+    assert!(FeeSchedule { early_discount_bps: 101, ..schedule }.validate().is_err());
+}
+
+#[test]
+fn payouts() {
+    assert_eq!(pro_rata_payout(10_000_000_000, 3 * S, 7 * S).unwrap(), 4_285_714_285);
+    assert_eq!(lmsr_payout(5 * S, 10 * S), 5 * S);
+    assert_eq!(lmsr_payout(15 * S, 10 * S), 10 * S);
+    assert_eq!(
+        lmsr_settlement(10_000_000_000, 4_000_000_000).unwrap(),
+        LmsrSettlement { fee: 200_000_000, creator_refund: 5_800_000_000 }
+    );
+    assert_eq!(
+        lmsr_settlement_at(10_000_000_000, 4_000_000_000, 500).unwrap(),
+        LmsrSettlement { fee: 500_000_000, creator_refund: 5_500_000_000 }
+    );
+    // The fee never eats into what winners are owed
+    assert_eq!(
+        lmsr_settlement_at(10_000_000_000, 9_900_000_000, 500).unwrap(),
+        LmsrSettlement { fee: 100_000_000, creator_refund: 0 }
+    );
+}
+
+#[test]
+fn scalar_payouts() {
+    // BTC between $50k and $150k: $80k pays LONG 30%, out-of-range values clamp
+    assert_eq!(scalar_long_bps(80_000, 50_000, 150_000).unwrap(), 3_000);
+    assert_eq!(scalar_long_bps(33_333, 0, 100_000).unwrap(), 3_333);
+    assert_eq!(scalar_long_bps(-5, -10, 10).unwrap(), 2_500);
+    assert_eq!(scalar_long_bps(10_000, 50_000, 150_000).unwrap(), 0);
+    assert_eq!(scalar_long_bps(200_000, 50_000, 150_000).unwrap(), 10_000);
+    assert_eq!(scalar_long_bps(i64::MAX, i64::MIN, i64::MAX).unwrap(), 10_000);
+    assert!(scalar_long_bps(0, 10, 10).is_err());
+
+    // Sides always sum to the pot; an empty side leaves its share to the other
+    assert_eq!(scalar_side_pots(10_000_000_001, 3_000, S, S).unwrap(), (3_000_000_000, 7_000_000_001));
+    assert_eq!(scalar_side_pots(10_000_000_000, 3_000, S, 0).unwrap(), (10_000_000_000, 0));
+    assert_eq!(scalar_side_pots(10_000_000_000, 3_000, 0, S).unwrap(), (0, 10_000_000_000));
+}
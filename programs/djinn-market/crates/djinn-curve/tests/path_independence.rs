@@ -0,0 +1,88 @@
+// Path independence: splitting a trade never changes what it costs.
+// cost(a→c) == cost(a→b) + cost(b→c) for every a <= b <= c on an exhaustive grid that
+// straddles each phase boundary and packs sub-lamport steps inside Phase 3, for the
+// global curve and every alternative curve.
+// Run with `cargo test -p djinn-curve --test path_independence`.
+
+use djinn_curve::{
+    calculate_cost, BondingCurve, CurveConfig, CurveKind, PHASE1_END, PHASE2_END, SHARE_SCALE, TOTAL_SUPPLY,
+    VIRTUAL_ANCHOR,
+};
+
+const S: u128 = SHARE_SCALE;
+
+/// Supplies around each phase boundary (and the ends of the curve), from one unit to
+/// a million shares either side, plus runs of supplies a few hundred units apart inside
+/// Phase 3, where one step costs a fraction of a lamport
+fn grid() -> Vec<u128> {
+    let offsets = [0, 1, 1_000, S, 1_000_000 * S];
+    let anchors = [
+        0,
+        PHASE1_END - VIRTUAL_ANCHOR,
+        PHASE2_END - VIRTUAL_ANCHOR,
+        500_000_000 * S,
+        TOTAL_SUPPLY,
+    ];
+    let mut points = Vec::new();
+    for anchor in anchors {
+        for offset in offsets {
+            points.push(anchor.saturating_sub(offset));
+            points.push((anchor + offset).min(TOTAL_SUPPLY));
+        }
+    }
+    let dense_starts = [PHASE2_END - VIRTUAL_ANCHOR + 1_000 * S, 240_089_548_685_154_880, 600_000_000 * S];
+    for start in dense_starts {
+        points.extend((0..12).map(|i| start + i * 487));
+    }
+    points.sort_unstable();
+    points.dedup();
+    points
+}
+
+fn assert_path_independent(cost: impl Fn(u128, u128) -> u128, label: &str) {
+    let points = grid();
+    for (i, &a) in points.iter().enumerate() {
+        for (j, &b) in points.iter().enumerate().skip(i) {
+            for &c in points.iter().skip(j) {
+                let direct = cost(a, c);
+                let split = cost(a, b) + cost(b, c);
+                assert_eq!(direct, split, "{}: {} -> {} -> {}", label, a, b, c);
+            }
+        }
+    }
+}
+
+#[test]
+fn piecewise_cost_is_path_independent_across_phases() {
+    assert_path_independent(|a, b| calculate_cost(a, b).unwrap(), "piecewise");
+}
+
+#[test]
+fn phase3_splits_between_sub_lamport_steps_are_exact() {
+    // A split that once cost a lamport more than the direct trade
+    let (a, b, c) = (240_089_548_685_154_880, 240_089_548_685_155_858, 240_089_548_685_156_523);
+    assert_eq!(calculate_cost(a, c).unwrap(), calculate_cost(a, b).unwrap() + calculate_cost(b, c).unwrap());
+}
+
+#[test]
+fn alternative_curves_are_path_independent() {
+    let curves = [
+        CurveConfig { kind: CurveKind::Linear, param_a: 1_000, param_b: 1_000_000 },
+        CurveConfig { kind: CurveKind::LmsrStyle, param_a: 1_000_000, param_b: 100_000_000 * S },
+        CurveConfig { kind: CurveKind::ConstantProduct, param_a: 30_000_000_000, param_b: 1_073_000_000 * S },
+    ];
+    for curve in curves {
+        curve.validate().unwrap();
+        assert_path_independent(|a, b| curve.cost(a, b).unwrap(), &format!("{:?}", curve.kind));
+    }
+}
+
+#[test]
+fn many_small_buys_cost_the_same_as_one_large_buy() {
+    // 1000 consecutive buys of 100k shares each, walking through Phase 1 into the bridge
+    let step = 100_000 * S;
+    let start = PHASE1_END - VIRTUAL_ANCHOR - 500 * step;
+    let end = start + 1_000 * step;
+    let stepped: u128 = (0..1_000).map(|i| calculate_cost(start + i * step, start + (i + 1) * step).unwrap()).sum();
+    assert_eq!(stepped, calculate_cost(start, end).unwrap());
+}
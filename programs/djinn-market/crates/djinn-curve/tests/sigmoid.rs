@@ -0,0 +1,148 @@
+// Phase 3 logistic: monotonicity, error bounds and continuity with the bridge.
+// Run with `cargo test -p djinn-curve --test sigmoid`.
+
+use djinn_curve::fixed_point::{ln_fixed, softplus_fixed, FIXED_ONE, FIXED_ONE_I, LN_2, SOFTPLUS_STEP};
+use djinn_curve::{
+    calculate_cost, calculate_curve_area, calculate_shares_from_sol, calculate_spot_price, P_50, P_90,
+    P_MAX, PHASE1_END, PHASE2_END, PHASE3_START, SHARE_SCALE, SIGMOID_MIDPOINT, SIGMOID_STEEPNESS,
+    TOTAL_SUPPLY, VIRTUAL_ANCHOR,
+};
+
+/// f64 reference for the Phase 3 price at `x_rel` past PHASE3_START
+fn reference_sigmoid_price(x_rel: f64) -> f64 {
+    let y = SIGMOID_STEEPNESS as f64 / 1e18;
+    let mid = SIGMOID_MIDPOINT as f64;
+    let sigma = |t: f64| 1.0 / (1.0 + (-t).exp());
+    let floor = sigma(-y);
+    let t = y * (x_rel - mid) / mid;
+    P_90 as f64 + (P_MAX - P_90) as f64 * (sigma(t) - floor) / (1.0 - floor)
+}
+
+/// Supply (without the virtual anchor) at an effective supply
+fn supply_at(effective: u128) -> u128 {
+    effective - VIRTUAL_ANCHOR
+}
+
+#[test]
+fn spot_price_is_monotonic_up_to_total_supply() {
+    let steps = 20_000u128;
+    let mut last = 0;
+    for i in 0..=steps {
+        let supply = TOTAL_SUPPLY / steps * i;
+        let price = calculate_spot_price(supply).unwrap();
+        assert!(price >= last, "price fell at supply {}: {} < {}", supply, price, last);
+        last = price;
+    }
+    assert!(last < P_MAX);
+}
+
+#[test]
+fn spot_price_is_monotonic_around_phase_boundary() {
+    let start = supply_at(PHASE2_END) - 1_000 * SHARE_SCALE;
+    let mut last = calculate_spot_price(start).unwrap();
+    for i in 1..=2_000u128 {
+        let price = calculate_spot_price(start + i * SHARE_SCALE).unwrap();
+        assert!(price >= last);
+        last = price;
+    }
+}
+
+#[test]
+fn sigmoid_price_matches_reference_within_one_lamport() {
+    let span = TOTAL_SUPPLY + VIRTUAL_ANCHOR - PHASE3_START;
+    for i in 0..=5_000u128 {
+        let x_rel = span / 5_000 * i;
+        let price = calculate_spot_price(supply_at(PHASE3_START + x_rel)).unwrap() as f64;
+        let expected = reference_sigmoid_price(x_rel as f64);
+        // Integer truncation costs at most 1 lamport; f64 adds ~1e-15 relative
+        assert!(
+            (price - expected).abs() <= 1.0 + expected * 1e-12,
+            "x_rel {}: {} vs {}",
+            x_rel,
+            price,
+            expected
+        );
+    }
+}
+
+#[test]
+fn sigmoid_is_continuous_in_value_and_slope_at_phase2_end() {
+    let boundary = supply_at(PHASE2_END);
+    assert_eq!(calculate_spot_price(boundary).unwrap(), P_90);
+    assert_eq!(calculate_spot_price(boundary + 1).unwrap(), P_90);
+
+    // Slopes measured over 1M shares either side of the boundary
+    let h = 1_000_000 * SHARE_SCALE;
+    let left = (P_90 - calculate_spot_price(boundary - h).unwrap()) as f64 / h as f64;
+    let right = (calculate_spot_price(boundary + h).unwrap() - P_90) as f64 / h as f64;
+    let bridge_slope = 2.0 * (P_90 - P_50) as f64 / (PHASE2_END - PHASE1_END) as f64;
+    assert!((left - bridge_slope).abs() / bridge_slope < 0.02, "left {} vs {}", left, bridge_slope);
+    assert!((right - bridge_slope).abs() / bridge_slope < 0.02, "right {} vs {}", right, bridge_slope);
+}
+
+#[test]
+fn softplus_grid_values_increase_across_every_fixed_point_seam() {
+    // exp_fixed switches its power of two at x = (k + 1/2)·ln2 and ln_fixed at
+    // 1 + e^x = 2^k; softplus_monotone relies on consecutive grid values increasing
+    let mut seams: Vec<i128> = (-15..=15).map(|k| k * LN_2 + LN_2 / 2).collect();
+    seams.extend((1..=14).map(|k| ln_fixed((1u128 << k) * FIXED_ONE - FIXED_ONE).unwrap()));
+    seams.extend([-10 * FIXED_ONE_I, -(SIGMOID_STEEPNESS as i128), 40 * FIXED_ONE_I]);
+    for seam in seams {
+        let first = seam.div_euclid(SOFTPLUS_STEP) * SOFTPLUS_STEP - 500 * SOFTPLUS_STEP;
+        let mut last = softplus_fixed(first).unwrap();
+        for i in 1..1_000 {
+            let x = first + i * SOFTPLUS_STEP;
+            let value = softplus_fixed(x).unwrap();
+            assert!(value > last, "softplus grid falls at {}", x);
+            last = value;
+        }
+    }
+}
+
+#[test]
+fn phase3_area_never_falls_unit_by_unit() {
+    // Includes the stretch where the fixed-point area used to dip by a lamport
+    let end = TOTAL_SUPPLY - 3_000;
+    for start in [supply_at(PHASE3_START), 240_089_548_685_154_000, supply_at(PHASE3_START + SIGMOID_MIDPOINT) - 1_500, end] {
+        let mut last = calculate_curve_area(start).unwrap();
+        for supply in start + 1..=start + 3_000 {
+            let area = calculate_curve_area(supply).unwrap();
+            assert!(area >= last, "area fell at {}", supply);
+            last = area;
+        }
+    }
+}
+
+#[test]
+fn sigmoid_area_matches_numeric_integral() {
+    let span = TOTAL_SUPPLY + VIRTUAL_ANCHOR - PHASE3_START;
+    let base = calculate_curve_area(supply_at(PHASE3_START)).unwrap();
+    for k in 1..=8u128 {
+        let x_rel = span / 8 * k;
+        let exact = (calculate_curve_area(supply_at(PHASE3_START + x_rel)).unwrap() - base) as f64;
+
+        // Simpson's rule on the f64 reference
+        let n = 2_000;
+        let h = x_rel as f64 / n as f64;
+        let mut sum = reference_sigmoid_price(0.0) + reference_sigmoid_price(x_rel as f64);
+        for j in 1..n {
+            let w = if j % 2 == 1 { 4.0 } else { 2.0 };
+            sum += w * reference_sigmoid_price(j as f64 * h);
+        }
+        let numeric = sum * h / 3.0;
+        assert!((exact - numeric).abs() / numeric < 1e-9, "x_rel {}: {} vs {}", x_rel, exact, numeric);
+    }
+}
+
+#[test]
+fn shares_from_sol_inverts_cost_in_phase3() {
+    for &supply in &[supply_at(PHASE3_START), 300_000_000 * SHARE_SCALE, 700_000_000 * SHARE_SCALE] {
+        for &sol_in in &[1_000_000u128, 1_000_000_000, 100_000_000_000] {
+            let shares = calculate_shares_from_sol(sol_in, supply).unwrap();
+            assert!(calculate_cost(supply, supply + shares).unwrap() <= sol_in);
+            if supply + shares < TOTAL_SUPPLY {
+                assert!(calculate_cost(supply, supply + shares + 1).unwrap() > sol_in);
+            }
+        }
+    }
+}
@@ -0,0 +1,248 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// BATCH AUCTION MODULE - Frequent-batch buys cleared at one uniform price
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Every continuous buy moves the curve immediately, so a bot can sandwich a large
+// buy between its own buy and sell. A creator can opt a market into batch mode
+// (Market.batch_window_slots > 0), which turns continuous buys off:
+//
+// 1. submit_batch_order escrows a buy (SOL in, entry fee included) into the
+//    market's open Batch. The first order opens the batch's slot window.
+// 2. Once the window has closed, anyone calls settle_batch. For each outcome the
+//    batch buys with the summed net SOL of its orders along the exact curve
+//    integral, so every order on that outcome pays the same average price.
+//    Outcomes clear one after another in outcome order (this only matters for LMSR).
+//    Settling also opens the next batch id for new orders.
+// 3. claim_batch_fill credits each order its pro-rata share of the cleared shares,
+//    rounded down. The last order claimed on an outcome takes whatever is left, so
+//    every cleared share ends up owned. Claims are permissionless, so the position's
+//    last_trade_ts is stamped with the batch's settled_at, never the claim time.
+//
+// A batch whose market stopped trading before settlement is cancelled instead and
+// every order is refunded in full, fee included. Sells stay continuous.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::{DjinnError, Market};
+
+// --- BATCH STATUS ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum BatchStatus {
+    Open = 0,      // Collecting orders (or waiting for settle_batch)
+    Settled = 1,   // Cleared; orders claim shares
+    Cancelled = 2, // Market stopped trading; orders claim refunds
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BATCH ACCOUNTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[account]
+pub struct Batch {
+    pub market: Pubkey,
+    pub batch_id: u64,
+    pub opener: Pubkey,              // Funded the escrow's rent, gets it back
+    pub start_slot: u64,
+    pub end_slot: u64,               // Orders accepted while slot < end_slot
+    pub status: BatchStatus,
+    pub total_net_sol: [u64; 6],     // Net SOL committed per outcome
+    pub total_fee: u64,              // Entry fees held in escrow until settlement
+    pub cleared_shares: [u128; 6],   // Shares bought per outcome at settlement
+    pub open_orders: u32,            // Orders not yet claimed
+    pub bump: u8,
+    pub escrow_bump: u8,
+    pub claimed_net_sol: [u64; 6],   // Net SOL of the orders claimed so far, per outcome
+    pub claimed_shares: [u128; 6],   // Shares credited so far, per outcome
+    pub settled_at: i64,             // Unix time of settlement; claims stamp positions with it
+}
+
+impl Batch {
+    pub const LEN: usize = 8     // Discriminator
+        + 32                     // market
+        + 8                      // batch_id
+        + 32                     // opener
+        + 8                      // start_slot
+        + 8                      // end_slot
+        + 1                      // status
+        + (6 * 8)                // total_net_sol
+        + 8                      // total_fee
+        + (6 * 16)               // cleared_shares
+        + 4                      // open_orders
+        + 1                      // bump
+        + 1                      // escrow_bump
+        + (6 * 8)                // claimed_net_sol
+        + (6 * 16)               // claimed_shares
+        + 8;                     // settled_at
+
+    /// Clear every outcome at its uniform price: buy with the outcome's summed net SOL
+    /// along the exact curve integral at `now`. Returns the net SOL moving into the vault.
+    pub fn clear(&mut self, market: &mut Market, now: i64) -> Result<u64> {
+        let mut total_net_sol: u64 = 0;
+        for outcome_index in 0..market.num_outcomes {
+            let outcome = outcome_index as usize;
+            let net_sol = self.total_net_sol[outcome] as u128;
+            if net_sol == 0 {
+                continue;
+            }
+            let shares = market.shares_for_sol(outcome_index, net_sol)?;
+            market.outcome_supplies[outcome] =
+                market.outcome_supplies[outcome].checked_add(shares).ok_or(DjinnError::MathError)?;
+            market.vault_balance = market.vault_balance.checked_add(net_sol).ok_or(DjinnError::MathError)?;
+            market.add_volume(net_sol)?;
+            market.add_cost_basis(net_sol)?;
+            self.cleared_shares[outcome] = shares;
+            total_net_sol = total_net_sol.checked_add(net_sol as u64).ok_or(DjinnError::MathError)?;
+        }
+        market.add_volume(self.total_fee as u128)?;
+        self.status = BatchStatus::Settled;
+        self.settled_at = now;
+        Ok(total_net_sol)
+    }
+
+    /// Shares owed to a settled order of `net_sol` on `outcome`: its pro-rata cut of
+    /// the cleared shares, or everything left for the outcome's last claim
+    pub fn take_fill(&mut self, outcome: u8, net_sol: u64) -> Result<u128> {
+        let outcome = outcome as usize;
+        let claimed_net_sol = self.claimed_net_sol[outcome].checked_add(net_sol).ok_or(DjinnError::MathError)?;
+        require!(claimed_net_sol <= self.total_net_sol[outcome], DjinnError::MathError);
+
+        let shares = if claimed_net_sol == self.total_net_sol[outcome] {
+            self.cleared_shares[outcome] - self.claimed_shares[outcome]
+        } else {
+            self.cleared_shares[outcome].checked_mul(net_sol as u128).ok_or(DjinnError::MathError)?
+                / self.total_net_sol[outcome] as u128
+        };
+        self.claimed_net_sol[outcome] = claimed_net_sol;
+        self.claimed_shares[outcome] = self.claimed_shares[outcome].checked_add(shares).ok_or(DjinnError::MathError)?;
+        Ok(shares)
+    }
+}
+
+#[account]
+pub struct BatchOrder {
+    pub owner: Pubkey,
+    pub batch: Pubkey,
+    pub outcome: u8,
+    pub sol_in: u64,             // Gross lamports escrowed (refunded on cancel)
+    pub net_sol: u64,            // sol_in minus the entry fee; weight in the fill
+    pub bump: u8,
+}
+
+impl BatchOrder {
+    // 8 (discriminator) + 32 (owner) + 32 (batch) + 1 (outcome) + 8 (sol_in) + 8 (net_sol) + 1 (bump)
+    pub const LEN: usize = 8 + 32 + 32 + 1 + 8 + 8 + 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, lmsr_market, market, NOW, SOL};
+
+    fn batch() -> Batch {
+        Batch {
+            market: Pubkey::new_unique(),
+            batch_id: 0,
+            opener: Pubkey::new_unique(),
+            start_slot: 0,
+            end_slot: 10,
+            status: BatchStatus::Open,
+            total_net_sol: [0; 6],
+            total_fee: 0,
+            cleared_shares: [0; 6],
+            open_orders: 0,
+            bump: 255,
+            escrow_bump: 255,
+            claimed_net_sol: [0; 6],
+            claimed_shares: [0; 6],
+            settled_at: 0,
+        }
+    }
+
+    /// A batch holding `orders` (outcome, net lamports)
+    fn batch_with(orders: &[(u8, u64)]) -> Batch {
+        let mut batch = batch();
+        for &(outcome, net_sol) in orders {
+            batch.total_net_sol[outcome as usize] += net_sol;
+            batch.open_orders += 1;
+        }
+        batch
+    }
+
+    #[test]
+    fn clearing_buys_each_outcome_once_at_its_summed_net_sol() {
+        for mut market in [market(), lmsr_market(3, 1_000 * SOL)] {
+            let mut batch = batch_with(&[(0, SOL as u64), (0, 2 * SOL as u64), (1, SOL as u64 / 2)]);
+            batch.total_fee = 35_000_000;
+            let expected = [market.shares_for_sol(0, 3 * SOL).unwrap(), 0];
+
+            let net_sol = batch.clear(&mut market, NOW).unwrap();
+            assert_eq!(net_sol, 3 * SOL as u64 + SOL as u64 / 2);
+            assert_eq!(batch.cleared_shares[0], expected[0]);
+            assert_eq!(market.outcome_supplies[0], batch.cleared_shares[0]);
+            assert_eq!(market.outcome_supplies[1], batch.cleared_shares[1]);
+            assert_eq!(market.vault_balance, net_sol as u128);
+            assert_eq!(market.total_volume, net_sol as u128 + 35_000_000);
+            assert!(batch.status == BatchStatus::Settled);
+        }
+    }
+
+    #[test]
+    fn every_order_on_an_outcome_pays_the_same_average_price() {
+        let mut market = market();
+        let mut batch = batch_with(&[(0, SOL as u64), (0, 2 * SOL as u64)]);
+        batch.clear(&mut market, NOW).unwrap();
+
+        let small = batch.take_fill(0, SOL as u64).unwrap();
+        let large = batch.take_fill(0, 2 * SOL as u64).unwrap();
+        assert_eq!(small + large, batch.cleared_shares[0]);
+        assert!(large.abs_diff(2 * small) <= 1);
+
+        // Submitting first buys no more than submitting last, unlike two continuous buys
+        let mut continuous = crate::test_fixtures::market();
+        let first = continuous.quote_buy(&config(), 0, SOL, NOW).unwrap();
+        continuous.apply_buy(0, &first).unwrap();
+        let second = continuous.quote_buy(&config(), 0, SOL, NOW).unwrap();
+        assert!(first.shares > second.shares);
+    }
+
+    #[test]
+    fn the_last_claim_takes_the_rounding_remainder() {
+        let mut market = market();
+        let orders = [(0, 333_333_333u64), (0, 333_333_333), (0, 333_333_334), (1, 7)];
+        let mut batch = batch_with(&orders);
+        batch.clear(&mut market, NOW).unwrap();
+
+        let mut credited = [0u128; 2];
+        for &(outcome, net_sol) in &orders {
+            credited[outcome as usize] += batch.take_fill(outcome, net_sol).unwrap();
+        }
+        // No dust left unowned in the outcome supplies
+        assert_eq!(credited, [market.outcome_supplies[0], market.outcome_supplies[1]]);
+        assert_eq!(batch.claimed_shares[..2], batch.cleared_shares[..2]);
+    }
+
+    #[test]
+    fn claims_stamp_the_settlement_time_whenever_they_land() {
+        let mut market = market();
+        let mut batch = batch_with(&[(0, SOL as u64)]);
+        batch.clear(&mut market, NOW).unwrap();
+        assert_eq!(batch.settled_at, NOW);
+
+        // A claim cranked a day later still dates the buy to settlement
+        let mut position = crate::test_fixtures::position(0);
+        let shares = batch.take_fill(0, SOL as u64).unwrap();
+        position.credit_buy(shares, SOL, batch.settled_at).unwrap();
+        assert_eq!(position.last_trade_ts, NOW);
+    }
+
+    #[test]
+    fn claims_cannot_exceed_the_outcome_total() {
+        let mut market = market();
+        let mut batch = batch_with(&[(0, SOL as u64)]);
+        batch.clear(&mut market, NOW).unwrap();
+        batch.take_fill(0, SOL as u64).unwrap();
+        assert!(batch.take_fill(0, 1).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// BONDING CURVE MODULE - Pluggable per-market pricing curves
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// The BondingCurve trait and the curve implementations live in djinn_curve::curve;
+// this module holds the on-chain curve selection stored on each market.
+//
+// - Piecewise:       the global "Aggressive V4" 3-phase curve (default)
+// - Linear:          straight line from a start price to an end price at TOTAL_SUPPLY
+// - LmsrStyle:       p_max · σ(s / b), a binary LMSR price against a fixed counter-side
+// - ConstantProduct: x·y = k against virtual SOL / share reserves
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use djinn_curve::BondingCurve;
+
+use crate::curve_result;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CurveKind {
+    Piecewise = 0,       // Global 3-phase curve (params ignored)
+    Linear = 1,          // param_a = start price, param_b = end price (lamports / share)
+    LmsrStyle = 2,       // param_a = max price (lamports / share), param_b = liquidity b (shares * 1e9)
+    ConstantProduct = 3, // param_a = virtual SOL reserve (lamports), param_b = virtual share reserve
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct CurveConfig {
+    pub kind: CurveKind,
+    pub param_a: u128,
+    pub param_b: u128,
+}
+
+impl CurveConfig {
+    pub const LEN: usize = 1 + 16 + 16;
+
+    pub fn piecewise() -> Self {
+        CurveConfig { kind: CurveKind::Piecewise, param_a: 0, param_b: 0 }
+    }
+
+    /// The same curve as the shared djinn-curve type that carries the math
+    fn spec(&self) -> djinn_curve::CurveConfig {
+        let kind = match self.kind {
+            CurveKind::Piecewise => djinn_curve::CurveKind::Piecewise,
+            CurveKind::Linear => djinn_curve::CurveKind::Linear,
+            CurveKind::LmsrStyle => djinn_curve::CurveKind::LmsrStyle,
+            CurveKind::ConstantProduct => djinn_curve::CurveKind::ConstantProduct,
+        };
+        djinn_curve::CurveConfig { kind, param_a: self.param_a, param_b: self.param_b }
+    }
+
+    /// Reject parameters that would make the curve degenerate or overflow
+    pub fn validate(&self) -> Result<()> {
+        curve_result(self.spec().validate())
+    }
+
+    /// Spot price at `supply`, in lamports per whole share
+    pub fn spot_price(&self, supply: u128) -> Result<u128> {
+        curve_result(self.spec().spot_price(supply))
+    }
+
+    /// Cost in lamports to move from supply_old to supply_new
+    pub fn cost(&self, supply_old: u128, supply_new: u128) -> Result<u128> {
+        curve_result(self.spec().cost(supply_old, supply_new))
+    }
+
+    /// Shares bought with `sol_in` lamports at `supply_old`, rounded down
+    pub fn shares_for(&self, sol_in: u128, supply_old: u128) -> Result<u128> {
+        curve_result(self.spec().shares_for(sol_in, supply_old))
+    }
+
+    /// Fewest shares to sell at `supply_old` for a refund of at least `refund` lamports
+    pub fn shares_to_release(&self, refund: u128, supply_old: u128) -> Result<u128> {
+        curve_result(self.spec().shares_to_release(refund, supply_old))
+    }
+}
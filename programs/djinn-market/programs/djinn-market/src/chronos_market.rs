@@ -12,6 +12,9 @@
 
 use anchor_lang::prelude::*;
 
+use crate::bonding_curve::CurveConfig;
+use crate::trading_limits::TradingLimits;
+
 // --- PYTH PRICE FEED IDS (Mainnet) ---
 // These are the official Pyth price feed IDs for mainnet-beta
 pub const PYTH_BTC_USD: &str = "e62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
@@ -109,6 +112,7 @@ pub struct ChronosMarket {
     pub outcome_supplies: [u128; 2],   // Supply for YES and NO
     pub vault_balance: u128,           // Total SOL in vault (Lamports)
     pub total_pot_at_resolution: u64,  // Snapshot for fair claiming
+    pub total_volume: u128,            // Lamports bought in (fees included); locks the limits once > 0
     
     // --- PDA Bumps ---
     pub bump: u8,
@@ -116,6 +120,10 @@ pub struct ChronosMarket {
     
     // --- Keeper Info ---
     pub keeper: Pubkey,                // Keeper bot that created this market
+    
+    // --- Pricing ---
+    pub curve: CurveConfig,            // Bonding curve chosen by the keeper
+    pub limits: TradingLimits,         // Keeper-set caps on buys
 }
 
 impl ChronosMarket {
@@ -135,9 +143,12 @@ impl ChronosMarket {
         + (2 * 16)                 // outcome_supplies ([u128; 2])
         + 16                       // vault_balance
         + 8                        // total_pot_at_resolution
+        + 16                       // total_volume
         + 1                        // bump
         + 1                        // vault_bump
-        + 32;                      // keeper
+        + 32                       // keeper
+        + CurveConfig::LEN         // curve
+        + TradingLimits::LEN;      // limits
     
     // Check if market is currently in trading phase
     pub fn is_trading_active(&self, now: i64) -> bool {
@@ -175,6 +186,7 @@ pub struct ChronosPosition {
     pub outcome: u8,             // 0 = YES, 1 = NO
     pub shares: u128,            // Number of shares held
     pub claimed: bool,           // Whether winnings have been claimed
+    pub last_trade_ts: i64,      // Last buy, for the market's cooldown
 }
 
 impl ChronosPosition {
@@ -183,7 +195,8 @@ impl ChronosPosition {
         + 32                     // market
         + 1                      // outcome
         + 16                     // shares
-        + 1;                     // claimed
+        + 1                      // claimed
+        + 8;                     // last_trade_ts
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -0,0 +1,237 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// COMPLETE SET MODULE - One share of every outcome, minted or redeemed at par
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// In an LMSR market exactly one outcome pays 1 lamport per share unit, so a bundle
+// of one unit of every outcome is always worth exactly 1 lamport (1 SOL per whole
+// share set). mint_complete_set sells that bundle at par and redeem_complete_set
+// buys it back at par, fee-free. Buying the same bundle through the LMSR cost
+// function also costs exactly par and leaves prices untouched, so arbitrage bots
+// can use the pair to pull outcome prices back to summing to 1.
+//
+// Bonding-curve markets take the same 1 lamport per set against the vault, but their
+// independent curves give a bundle no fixed value: minting adds a unit to every curve
+// and redeeming removes one, moving what those curves would refund by the set's curve
+// value. Each side is only allowed when par covers that move, so the vault keeps
+// backing every curve refund:
+// - mint: the set must cost at most par on the curves
+// - redeem: the set must release at least par on the curves
+//
+// The caller passes its num_outcomes UserPosition PDAs as remaining accounts, in
+// outcome order. Positions that do not exist yet are created on mint.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{
+    allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+};
+
+use crate::lmsr::PricingModel;
+use crate::position_exit::ExitThresholds;
+use crate::{DjinnError, Market, UserPosition, TOTAL_SUPPLY};
+
+impl Market {
+    /// Book `amount` complete sets minted at par: every outcome supply and the vault up
+    /// by `amount`. Curve markets refuse sets their curves price above par.
+    pub fn mint_sets(&mut self, amount: u128) -> Result<()> {
+        if self.pricing == PricingModel::Curve {
+            let mut set_cost = 0u128;
+            for outcome in 0..self.num_outcomes {
+                set_cost = set_cost.checked_add(self.sol_to_buy(outcome, amount)?).ok_or(DjinnError::MathError)?;
+            }
+            require!(set_cost <= amount, DjinnError::CompleteSetOffPar);
+        }
+        for supply in self.outcome_supplies[..self.num_outcomes as usize].iter_mut() {
+            *supply = supply.checked_add(amount).ok_or(DjinnError::MathError)?;
+            require!(*supply <= TOTAL_SUPPLY, DjinnError::MathError);
+        }
+        self.vault_balance = self.vault_balance.checked_add(amount).ok_or(DjinnError::MathError)?;
+        Ok(())
+    }
+
+    /// Book `amount` complete sets redeemed at par: every outcome supply and the vault
+    /// down by `amount`. Curve markets refuse sets their curves price below par.
+    pub fn redeem_sets(&mut self, amount: u128) -> Result<()> {
+        if self.pricing == PricingModel::Curve {
+            let mut set_value = 0u128;
+            for outcome in 0..self.num_outcomes {
+                set_value = set_value.checked_add(self.sol_for_shares(outcome, amount)?).ok_or(DjinnError::MathError)?;
+            }
+            require!(set_value >= amount, DjinnError::CompleteSetOffPar);
+        }
+        for supply in self.outcome_supplies[..self.num_outcomes as usize].iter_mut() {
+            *supply = supply.checked_sub(amount).ok_or(DjinnError::InsufficientShares)?;
+        }
+        self.vault_balance = self.vault_balance.checked_sub(amount).ok_or(DjinnError::InsufficientVaultBalance)?;
+        Ok(())
+    }
+}
+
+/// Load the caller's UserPosition PDAs for outcomes 0..num_outcomes from `remaining`.
+/// With `create_missing`, uninitialized PDAs are created and paid for by `user`.
+pub fn load_positions<'info>(
+    remaining: &'info [AccountInfo<'info>],
+    market: Pubkey,
+    num_outcomes: u8,
+    user: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    create_missing: bool,
+) -> Result<Vec<Account<'info, UserPosition>>> {
+    require!(remaining.len() >= num_outcomes as usize, DjinnError::InvalidPositionAccount);
+
+    let mut positions = Vec::with_capacity(num_outcomes as usize);
+    for (outcome, info) in (0..num_outcomes).zip(remaining) {
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"user_pos", market.as_ref(), user.key.as_ref(), &[outcome]],
+            &crate::ID,
+        );
+        require_keys_eq!(info.key(), expected, DjinnError::InvalidPositionAccount);
+
+        if info.owner == &crate::ID {
+            positions.push(Account::<UserPosition>::try_from(info)?);
+            continue;
+        }
+        require!(create_missing, DjinnError::NoShares);
+
+        let seeds: &[&[u8]] = &[b"user_pos", market.as_ref(), user.key.as_ref(), &[outcome], &[bump]];
+        create_position_account(info, user, system_program, &[seeds])?;
+
+        let mut position = Account::<UserPosition>::try_from_unchecked(info)?;
+        position.market = market;
+        position.outcome = outcome;
+        position.shares = 0;
+        position.claimed = false;
+        position.exit_thresholds = ExitThresholds::default();
+        position.last_trade_ts = 0; // Never traded; the mint's record_buy stamps it
+        position.cost_basis = 0;
+        position.open_sell_orders = 0;
+        positions.push(position);
+    }
+    Ok(positions)
+}
+
+/// Allocate a UserPosition PDA owned by this program. Mirrors Anchor's `init`, which
+/// tops up instead of failing when someone has already sent lamports to the address.
+fn create_position_account<'info>(
+    info: &AccountInfo<'info>,
+    user: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(UserPosition::LEN);
+
+    if info.lamports() == 0 {
+        return create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: user.clone(), to: info.clone() },
+                signer,
+            ),
+            rent,
+            UserPosition::LEN as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(info.lamports());
+    if top_up > 0 {
+        transfer(
+            CpiContext::new(system_program.clone(), Transfer { from: user.clone(), to: info.clone() }),
+            top_up,
+        )?;
+    }
+    allocate(
+        CpiContext::new_with_signer(system_program.clone(), Allocate { account_to_allocate: info.clone() }, signer),
+        UserPosition::LEN as u64,
+    )?;
+    assign(
+        CpiContext::new_with_signer(system_program.clone(), Assign { account_to_assign: info.clone() }, signer),
+        &crate::ID,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonding_curve::{CurveConfig, CurveKind};
+    use crate::protocol_config::ProtocolConfig;
+    use crate::test_fixtures::{config, lmsr_market, market, NOW, SOL};
+
+    /// What the vault owes if every share sold back down its curve right now
+    fn curve_value(market: &Market) -> u128 {
+        (0..market.num_outcomes)
+            .map(|outcome| market.sol_for_shares(outcome, market.outcome_supplies[outcome as usize]).unwrap())
+            .sum()
+    }
+
+    fn buy(market: &mut Market, config: &ProtocolConfig, outcome: u8, sol_in: u128) {
+        let quote = market.quote_buy(config, outcome, sol_in, NOW).unwrap();
+        market.apply_buy(outcome, &quote).unwrap();
+    }
+
+    #[test]
+    fn lmsr_sets_mint_and_redeem_at_par() {
+        let mut market = lmsr_market(3, 1_000 * SOL);
+        let prices = [market.spot_price(0).unwrap(), market.spot_price(2).unwrap()];
+        market.mint_sets(5 * SOL).unwrap();
+        assert_eq!(market.supplies(), [5 * SOL; 3]);
+        assert_eq!(market.vault_balance, 5 * SOL);
+        assert_eq!(prices, [market.spot_price(0).unwrap(), market.spot_price(2).unwrap()]);
+
+        market.redeem_sets(2 * SOL).unwrap();
+        assert_eq!(market.supplies(), [3 * SOL; 3]);
+        assert_eq!(market.vault_balance, 3 * SOL);
+    }
+
+    #[test]
+    fn curve_sets_mint_at_par_while_the_curves_price_them_below_it() {
+        let config = config();
+        let mut market = market();
+        buy(&mut market, &config, 0, 3 * SOL);
+        buy(&mut market, &config, 1, SOL);
+
+        let amount = 1_000 * SOL;
+        let vault = market.vault_balance;
+        market.mint_sets(amount).unwrap();
+        assert_eq!(market.vault_balance, vault + amount);
+        assert!(market.vault_balance >= curve_value(&market));
+    }
+
+    #[test]
+    fn curve_sets_are_refused_on_the_side_of_par_that_drains_the_vault() {
+        let config = config();
+        let mut market = market();
+        buy(&mut market, &config, 0, 2 * SOL);
+        buy(&mut market, &config, 1, 2 * SOL);
+
+        // Far below a lamport per unit on both curves: redeeming at par would overpay
+        let held = market.outcome_supplies[0].min(market.outcome_supplies[1]);
+        assert!(market.redeem_sets(held).is_err());
+
+        // Priced over par on the curves: minting at par would let the legs sell for more
+        let mut expensive = market.clone();
+        expensive.curve = CurveConfig {
+            kind: CurveKind::Linear,
+            param_a: 2 * SOL,
+            param_b: 2 * SOL,
+        };
+        assert!(expensive.mint_sets(SOL).is_err());
+    }
+
+    #[test]
+    fn curve_sets_minted_above_their_value_redeem_only_when_the_curves_cover_par() {
+        let mut market = market();
+        market.curve = CurveConfig {
+            kind: CurveKind::Linear,
+            param_a: SOL / 4,
+            param_b: SOL,
+        };
+        market.mint_sets(1_000 * SOL).unwrap();
+        let vault = market.vault_balance;
+        assert!(vault >= curve_value(&market));
+        // 2 x 1/4 SOL a share is half of par: the sets can't come back out at par
+        assert!(market.redeem_sets(SOL).is_err());
+        assert_eq!(market.vault_balance, vault);
+    }
+}
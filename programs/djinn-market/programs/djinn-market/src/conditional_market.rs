@@ -0,0 +1,164 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// CONDITIONAL MARKET MODULE - Markets that live or die on another market's outcome
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// "If candidate A wins (market X), will policy Y pass?": the creator passes the parent
+// market and a parent outcome to initialize_market, which checks the parent is another
+// open categorical market with that outcome and attaches the MarketCondition for the
+// market's lifetime. Trading runs as usual while the parent is open.
+//
+// Once the parent settles, settle_market_condition (permissionless) applies it:
+// - parent resolved to parent_outcome: the condition is met and the market stays live
+// - parent resolved to anything else, or voided: the market is voided and positions
+//   take their cost-basis refunds through claim_refund
+//
+// A conditional market can only resolve once its condition is met. resolve_market and
+// resolve_scalar_market check the parent passed alongside them; every other
+// resolution path (optimistic proposal, oracle vote) needs settle_market_condition to
+// have run first.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::{DjinnError, Market, MarketStatus};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct MarketCondition {
+    pub parent: Pubkey,     // Market this one depends on
+    pub parent_outcome: u8, // Parent outcome that keeps this market live
+    pub met: bool,          // Parent resolved to parent_outcome
+}
+
+impl MarketCondition {
+    pub const LEN: usize = 32 + 1 + 1; // parent + parent_outcome + met
+
+    /// Condition on `parent_outcome` of `parent` (at `parent_key`): an open categorical
+    /// market with that outcome. Scalar parents have no outcome index to condition on.
+    pub fn new(parent_key: Pubkey, parent: &Market, parent_outcome: u8) -> Result<Self> {
+        require!(
+            parent.awaiting_resolution() && !parent.is_scalar() && parent_outcome < parent.num_outcomes,
+            DjinnError::InvalidParentMarket
+        );
+        Ok(MarketCondition { parent: parent_key, parent_outcome, met: false })
+    }
+
+    /// Whether `parent` satisfied the condition; None while it is unresolved
+    pub fn evaluate(&self, parent: &Market) -> Option<bool> {
+        match parent.status {
+            MarketStatus::Resolved => Some(parent.winning_outcome == Some(self.parent_outcome)),
+            MarketStatus::Voided => Some(false),
+            MarketStatus::Active | MarketStatus::PendingVerification => None,
+        }
+    }
+}
+
+impl Market {
+    /// Unconditional, or the parent already resolved to the condition
+    pub fn condition_met(&self) -> bool {
+        match self.condition {
+            Some(condition) => condition.met,
+            None => true,
+        }
+    }
+
+    /// Apply a settled parent: marks the condition met and returns true, or returns
+    /// false when it failed and the market has to be voided
+    pub fn settle_condition(&mut self, parent: &Market) -> Result<bool> {
+        let condition = self.condition.as_mut().ok_or(DjinnError::NotConditionalMarket)?;
+        let met = condition.evaluate(parent).ok_or(DjinnError::ParentNotResolved)?;
+        condition.met = met;
+        Ok(met)
+    }
+
+    /// Check a conditional market against its parent before resolving it, marking the
+    /// condition met. A failed condition is an error: the market has to be voided
+    /// through settle_market_condition instead.
+    pub fn check_condition(&mut self, parent: Option<&Account<Market>>) -> Result<()> {
+        let Some(condition) = self.condition.as_mut() else {
+            return Ok(());
+        };
+        if condition.met {
+            return Ok(());
+        }
+        let parent = parent.ok_or(DjinnError::InvalidParentMarket)?;
+        require_keys_eq!(parent.key(), condition.parent, DjinnError::InvalidParentMarket);
+        match condition.evaluate(parent) {
+            Some(true) => {
+                condition.met = true;
+                Ok(())
+            }
+            Some(false) => err!(DjinnError::ConditionFailed),
+            None => err!(DjinnError::ParentNotResolved),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar_market::MarketKind;
+    use crate::test_fixtures::{market, NOW};
+
+    fn conditional_on(parent: &Market, parent_outcome: u8) -> Market {
+        let condition = MarketCondition::new(Pubkey::new_unique(), parent, parent_outcome).unwrap();
+        Market { condition: Some(condition), ..market() }
+    }
+
+    fn resolved(outcome: Option<u8>) -> Market {
+        let mut parent = market();
+        match outcome {
+            Some(outcome) => {
+                parent.status = MarketStatus::Resolved;
+                parent.winning_outcome = Some(outcome);
+            }
+            None => {
+                parent.void().unwrap();
+            }
+        }
+        parent
+    }
+
+    #[test]
+    fn conditions_need_an_open_categorical_parent_with_that_outcome() {
+        let parent = market();
+        assert!(MarketCondition::new(Pubkey::new_unique(), &parent, 2).is_err());
+
+        let scalar = Market { kind: MarketKind::Scalar { lower_bound: 0, upper_bound: 10 }, ..market() };
+        assert!(MarketCondition::new(Pubkey::new_unique(), &scalar, 0).is_err());
+        assert!(MarketCondition::new(Pubkey::new_unique(), &resolved(Some(0)), 0).is_err());
+
+        let mut verifying = market();
+        verifying.enter_verification(None, NOW + 7 * 86_400).unwrap();
+        let condition = MarketCondition::new(Pubkey::new_unique(), &verifying, 1).unwrap();
+        assert!(!condition.met && condition.parent_outcome == 1);
+    }
+
+    #[test]
+    fn a_parent_resolved_to_the_condition_keeps_the_market_live() {
+        let mut child = conditional_on(&market(), 1);
+        assert!(!child.condition_met());
+        assert!(child.settle_condition(&resolved(Some(1))).unwrap());
+        assert!(child.condition_met() && child.status == MarketStatus::Active);
+    }
+
+    #[test]
+    fn any_other_parent_outcome_fails_the_condition() {
+        for parent in [resolved(Some(0)), resolved(None)] {
+            let mut child = conditional_on(&market(), 1);
+            assert!(!child.settle_condition(&parent).unwrap());
+            assert!(!child.condition_met());
+            // The handler then voids it
+            child.void().unwrap();
+            assert!(child.status == MarketStatus::Voided);
+        }
+    }
+
+    #[test]
+    fn an_open_parent_settles_nothing() {
+        let mut child = conditional_on(&market(), 0);
+        assert!(child.settle_condition(&market()).is_err());
+        assert!(market().settle_condition(&resolved(Some(0))).is_err());
+        assert!(!child.condition_met());
+    }
+}
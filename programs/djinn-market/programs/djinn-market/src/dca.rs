@@ -0,0 +1,205 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// DCA MODULE - Scheduled buys executed by permissionless keepers
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// A DcaPlan PDA accumulates one outcome of a Market gradually, e.g. 0.1 SOL into
+// YES every hour for a day. The owner deposits the whole budget into a system-owned
+// "dca_escrow" PDA up front (plus the escrow's own rent).
+//
+// execute_dca runs the buy_shares path for amount_per_interval at most once per
+// interval, pays the keeper its tip from the escrow, and refuses to run once the
+// plan's end_time or the market's resolution_time has passed. Intervals a keeper
+// missed are skipped rather than bunched together. An interval whose buy would
+// leave the spot price above the plan's max_price is skipped too, as is every
+// interval while the market is in batch mode: nothing is bought, the schedule moves
+// on and the keeper still earns its tip. close_dca_plan refunds
+// whatever is left in the escrow to the owner.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::protocol_config::ProtocolConfig;
+use crate::quote::TradeQuote;
+use crate::{DjinnError, Market};
+
+// --- PLAN PARAMETERS ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DcaParams {
+    pub deposit: u64,             // Total lamports escrowed for buys and keeper tips
+    pub amount_per_interval: u64, // Lamports spent per execution (fee included)
+    pub interval_secs: i64,
+    pub end_time: i64,            // No executions at or after this timestamp
+    pub keeper_tip: u64,          // Lamports paid per execution
+    pub max_price: u128,          // Skip-guard: post-trade spot price cap (0 = none)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DCA PLAN ACCOUNT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[account]
+pub struct DcaPlan {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub plan_id: u64,             // Owner-chosen id (PDA seed)
+    pub outcome: u8,
+    pub amount_per_interval: u64,
+    pub interval_secs: i64,
+    pub end_time: i64,
+    pub keeper_tip: u64,
+    pub max_price: u128,
+    pub remaining: u64,           // Escrowed lamports not yet spent or tipped
+    pub next_execution: i64,      // Earliest timestamp for the next buy
+    pub executions: u32,          // Intervals that bought (skipped ones not counted)
+    pub total_spent: u64,
+    pub total_shares: u128,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+impl DcaPlan {
+    pub const LEN: usize = 8     // Discriminator
+        + 32                     // owner
+        + 32                     // market
+        + 8                      // plan_id
+        + 1                      // outcome
+        + 8                      // amount_per_interval
+        + 8                      // interval_secs
+        + 8                      // end_time
+        + 8                      // keeper_tip
+        + 16                     // max_price
+        + 8                      // remaining
+        + 8                      // next_execution
+        + 4                      // executions
+        + 8                      // total_spent
+        + 16                     // total_shares
+        + 1                      // bump
+        + 1;                     // escrow_bump
+
+    /// First interval boundary strictly after `now`, skipping any the keeper missed
+    pub fn next_slot_after(&self, now: i64) -> i64 {
+        let missed = now.saturating_sub(self.next_execution) / self.interval_secs;
+        self.next_execution.saturating_add((missed + 1).saturating_mul(self.interval_secs))
+    }
+
+    /// The buy due at `now` (same path as buy_shares), or None when it would leave the
+    /// spot price above max_price and the interval is skipped
+    pub fn due_buy(&self, market: &Market, config: &ProtocolConfig, now: i64) -> Result<Option<TradeQuote>> {
+        // Batch mode turns continuous buys off; the interval is skipped until it ends
+        if market.batch_window_slots > 0 {
+            return Ok(None);
+        }
+        let quote = market.quote_buy(config, self.outcome, self.amount_per_interval as u128, now)?;
+        if self.max_price > 0 && quote.spot_price_after > self.max_price {
+            return Ok(None);
+        }
+        Ok(Some(quote))
+    }
+
+    /// Book the interval due at `now`: the keeper tip, plus the buy unless it was skipped
+    pub fn record_interval(&mut self, now: i64, buy: Option<&TradeQuote>) -> Result<()> {
+        let spent = if buy.is_some() { self.amount_per_interval } else { 0 };
+        let cost = spent.checked_add(self.keeper_tip).ok_or(DjinnError::MathError)?;
+        self.remaining = self.remaining.checked_sub(cost).ok_or(DjinnError::DcaEscrowDepleted)?;
+        self.next_execution = self.next_slot_after(now);
+        if let Some(quote) = buy {
+            self.executions = self.executions.saturating_add(1);
+            self.total_spent = self.total_spent.checked_add(spent).ok_or(DjinnError::MathError)?;
+            self.total_shares = self.total_shares.checked_add(quote.shares).ok_or(DjinnError::MathError)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, market, NOW, SOL};
+
+    fn plan(max_price: u128) -> DcaPlan {
+        DcaPlan {
+            owner: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+            plan_id: 0,
+            outcome: 0,
+            amount_per_interval: SOL as u64 / 10,
+            interval_secs: 3_600,
+            end_time: NOW + 86_400,
+            keeper_tip: 10_000,
+            max_price,
+            remaining: SOL as u64,
+            next_execution: NOW,
+            executions: 0,
+            total_spent: 0,
+            total_shares: 0,
+            bump: 255,
+            escrow_bump: 255,
+        }
+    }
+
+    #[test]
+    fn each_interval_buys_through_the_buy_shares_quote() {
+        let config = config();
+        let mut market = market();
+        let mut plan = plan(0);
+        let mut now = NOW;
+        for _ in 0..5 {
+            let quote = plan.due_buy(&market, &config, now).unwrap().unwrap();
+            let direct = market.quote_buy(&config, 0, plan.amount_per_interval as u128, now).unwrap();
+            assert_eq!((quote.shares, quote.net_sol), (direct.shares, direct.net_sol));
+            market.apply_buy(0, &quote).unwrap();
+            plan.record_interval(now, Some(&quote)).unwrap();
+            now = plan.next_execution;
+        }
+        assert_eq!(plan.executions, 5);
+        assert_eq!(plan.total_spent, 5 * plan.amount_per_interval);
+        assert_eq!(plan.total_shares, market.outcome_supplies[0]);
+        assert_eq!(plan.remaining, SOL as u64 - 5 * (plan.amount_per_interval + plan.keeper_tip));
+        assert_eq!(plan.next_execution, NOW + 5 * 3_600);
+    }
+
+    #[test]
+    fn an_interval_above_max_price_is_skipped_not_failed() {
+        let config = config();
+        let market = market();
+        let after = market.quote_buy(&config, 0, SOL / 10, NOW).unwrap().spot_price_after;
+
+        assert!(plan(after).due_buy(&market, &config, NOW).unwrap().is_some());
+        let mut capped = plan(after - 1);
+        assert!(capped.due_buy(&market, &config, NOW).unwrap().is_none());
+
+        capped.record_interval(NOW, None).unwrap();
+        assert_eq!(capped.next_execution, NOW + 3_600);
+        assert_eq!(capped.remaining, SOL as u64 - capped.keeper_tip);
+        assert_eq!((capped.executions, capped.total_spent, capped.total_shares), (0, 0, 0));
+    }
+
+    #[test]
+    fn intervals_are_skipped_while_the_market_is_in_batch_mode() {
+        let config = config();
+        let mut market = market();
+        market.batch_window_slots = 10;
+        let mut plan = plan(0);
+        assert!(plan.due_buy(&market, &config, NOW).unwrap().is_none());
+        plan.record_interval(NOW, None).unwrap();
+        assert_eq!(plan.remaining, SOL as u64 - plan.keeper_tip);
+
+        market.batch_window_slots = 0;
+        assert!(plan.due_buy(&market, &config, plan.next_execution).unwrap().is_some());
+    }
+
+    #[test]
+    fn missed_intervals_are_skipped_rather_than_bunched() {
+        let mut plan = plan(0);
+        plan.record_interval(NOW + 3 * 3_600 + 10, None).unwrap();
+        assert_eq!(plan.next_execution, NOW + 4 * 3_600);
+    }
+
+    #[test]
+    fn an_interval_the_escrow_cannot_cover_is_refused() {
+        let mut plan = plan(0);
+        plan.remaining = plan.keeper_tip - 1;
+        assert!(plan.record_interval(NOW, None).is_err());
+    }
+}
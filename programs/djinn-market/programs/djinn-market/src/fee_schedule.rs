@@ -0,0 +1,149 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// FEE SCHEDULE MODULE - Per-market, time- and volume-dependent trading fees
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// The schedule math lives in djinn_curve::fees::FeeSchedule; this module holds the
+// compact on-chain copy stored on each Market and evaluates it against the Clock:
+// - entry fee: base bps, minus a discount while market volume is below early_volume,
+//   plus a linear ramp up to ramp_max_bps over the last ramp_window_secs before
+//   resolution_time (discourages last-second informed sniping)
+// - exit fee: base bps, plus short_hold_bps when the seller's last buy of the outcome
+//   was less than min_hold_secs ago. Every buy-side credit stamps that time
+//   (UserPosition::credit_buy): direct buys, swaps into the outcome, complete-set
+//   mints, DCA and limit-order fills and batch claims.
+//
+// A new market's schedule is a flat snapshot of the ProtocolConfig entry / exit fees
+// at creation; later config updates do not reach it. A creator may change it only
+// before the market's first trade, so nobody buys under one schedule and sells
+// under another.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::protocol_config::ProtocolParams;
+use crate::{curve_result, Market};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FeeSchedule {
+    pub entry_bps: u16,
+    pub exit_bps: u16,
+    pub ramp_window_secs: u64,   // 0 = no late ramp
+    pub ramp_max_bps: u16,       // Extra entry bps reached at resolution_time
+    pub early_volume: u64,       // Lamports of volume that get the discount
+    pub early_discount_bps: u16,
+    pub min_hold_secs: u64,      // 0 = no short-hold surcharge
+    pub short_hold_bps: u16,
+}
+
+impl FeeSchedule {
+    // 2 (entry_bps) + 2 (exit_bps) + 8 (ramp_window_secs) + 2 (ramp_max_bps)
+    // + 8 (early_volume) + 2 (early_discount_bps) + 8 (min_hold_secs) + 2 (short_hold_bps)
+    pub const LEN: usize = 2 + 2 + 8 + 2 + 8 + 2 + 8 + 2;
+
+    /// Flat schedule at the protocol's default entry / exit fees
+    pub fn flat(params: &ProtocolParams) -> Self {
+        FeeSchedule {
+            entry_bps: params.entry_fee_bps,
+            exit_bps: params.exit_fee_bps,
+            ramp_window_secs: 0,
+            ramp_max_bps: 0,
+            early_volume: 0,
+            early_discount_bps: 0,
+            min_hold_secs: 0,
+            short_hold_bps: 0,
+        }
+    }
+
+    /// The same schedule as the shared djinn-curve type that carries the math
+    fn spec(&self) -> djinn_curve::fees::FeeSchedule {
+        djinn_curve::fees::FeeSchedule {
+            entry_bps: self.entry_bps as u128,
+            exit_bps: self.exit_bps as u128,
+            ramp_window_secs: self.ramp_window_secs,
+            ramp_max_bps: self.ramp_max_bps as u128,
+            early_volume: self.early_volume as u128,
+            early_discount_bps: self.early_discount_bps as u128,
+            min_hold_secs: self.min_hold_secs,
+            short_hold_bps: self.short_hold_bps as u128,
+        }
+    }
+
+    /// Reject schedules above the 10% fee ceiling or with a discount below zero
+    pub fn validate(&self) -> Result<()> {
+        curve_result(self.spec().validate())
+    }
+}
+
+impl Market {
+    /// Entry fee bps for a buy at `now`
+    pub fn entry_fee_bps(&self, now: i64) -> u128 {
+        let secs_to_resolution = self.resolution_time.saturating_sub(now).max(0) as u64;
+        self.fee_schedule.spec().entry_bps_at(secs_to_resolution, self.total_volume)
+    }
+
+    /// Exit fee bps for a sell at `now` by a holder whose last buy was at `last_buy_ts`
+    /// (0 = unknown, treated as held long enough)
+    pub fn exit_fee_bps(&self, now: i64, last_buy_ts: i64) -> u128 {
+        let held_secs = if last_buy_ts == 0 {
+            u64::MAX
+        } else {
+            now.saturating_sub(last_buy_ts).max(0) as u64
+        };
+        self.fee_schedule.spec().exit_bps_at(held_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::{market, position, NOW, SOL};
+    use crate::trading_limits::TradingLimits;
+
+    #[test]
+    fn short_hold_surcharge_applies_until_min_hold_secs_pass() {
+        let mut market = market();
+        market.fee_schedule.min_hold_secs = 3_600;
+        market.fee_schedule.short_hold_bps = 400;
+        let base = market.fee_schedule.exit_bps as u128;
+        assert_eq!(market.exit_fee_bps(NOW + 3_599, NOW), base + 400);
+        assert_eq!(market.exit_fee_bps(NOW + 3_600, NOW), base);
+        // 0 = never bought: held long enough
+        assert_eq!(market.exit_fee_bps(NOW, 0), base);
+    }
+
+    #[test]
+    fn every_buy_side_credit_starts_the_short_hold_clock() {
+        let mut market = market();
+        market.fee_schedule.min_hold_secs = 3_600;
+        market.fee_schedule.short_hold_bps = 400;
+        let base = market.fee_schedule.exit_bps as u128;
+
+        // Direct, swap, complete-set, DCA and limit-order buys (checked against limits)
+        let mut checked = position(0);
+        checked.record_buy(&TradingLimits::default(), SOL, SOL, SOL, NOW).unwrap();
+        // Batch claims (limits were checked at submission)
+        let mut claimed = position(0);
+        claimed.credit_buy(SOL, SOL, NOW).unwrap();
+
+        for position in [checked, claimed] {
+            assert_eq!(position.last_trade_ts, NOW);
+            assert_eq!(market.exit_fee_bps(NOW + 60, position.last_trade_ts), base + 400);
+        }
+    }
+
+    #[test]
+    fn entry_fee_ramps_up_before_resolution_and_discounts_early_volume() {
+        let mut market = market();
+        let base = market.fee_schedule.entry_bps as u128;
+        market.fee_schedule.ramp_window_secs = 3_600;
+        market.fee_schedule.ramp_max_bps = 400;
+        market.fee_schedule.early_volume = 10 * SOL as u64;
+        market.fee_schedule.early_discount_bps = 50;
+
+        assert_eq!(market.entry_fee_bps(NOW), base - 50);
+        market.total_volume = 10 * SOL;
+        assert_eq!(market.entry_fee_bps(NOW), base);
+        assert_eq!(market.entry_fee_bps(market.resolution_time - 1_800), base + 200);
+        assert_eq!(market.entry_fee_bps(market.resolution_time), base + 400);
+    }
+}
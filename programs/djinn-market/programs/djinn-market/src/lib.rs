@@ -4,47 +4,92 @@ use anchor_lang::solana_program::hash;
 // Chronos Market Module - Automated Time-Based Crypto Markets
 pub mod chronos_market;
 
-declare_id!("A8pVMgP6vwjGqcbYh1WGWDjXq9uwQRoF9Lz1siLmD7nm");
+// Bonding Curve Module - Pluggable per-market pricing curves
+pub mod bonding_curve;
 
-// ═══════════════════════════════════════════════════════════════════════════════
-// DJINN CURVE V4 AGGRESSIVE: "EARLY BIRD REWARDS"
-// 3-Phase Piecewise Bonding Curve with Progressive Gains
-// Phase 1: Linear (0-50M → 6x) | Phase 2: Quadratic (50M-90M → 15x) | Phase 3: Sigmoid (90M+)
-// Progressive: 10M=2x, 20M=3x, 30M=4x, 40M=5x, 50M=6x
-// ═══════════════════════════════════════════════════════════════════════════════
+// LMSR Module - Coupled pricing for N-outcome markets
+pub mod lmsr;
+
+// Quote Module - Read-only trade previews for clients
+pub mod quote;
+
+// Complete Set Module - Mint / redeem one share of every outcome at par
+pub mod complete_set;
+
+// Limit Order Module - Price-triggered orders filled by permissionless cranks
+pub mod limit_order;
+
+// Position Exit Module - Stop-loss / take-profit executed by keepers
+pub mod position_exit;
+
+// DCA Module - Scheduled buys executed by keepers
+pub mod dca;
+
+// Batch Auction Module - Opt-in uniform-price batches against sandwiching
+pub mod batch_auction;
+
+// Trading Limits Module - Per-market caps on trade size, wallet size and pace
+pub mod trading_limits;
+
+// Fee Schedule Module - Per-market time- and volume-dependent trading fees
+pub mod fee_schedule;
+
+// Protocol Config Module - Governance-editable fees and splits (market fees snapshotted at creation)
+pub mod protocol_config;
+
+// Referral Module - Trading-fee sharing with referring frontends
+pub mod referral;
+
+// Void Market Module - Cancelled markets unwound by cost-basis refunds
+pub mod void_market;
 
-// --- GLOBAL CONSTANTS ---
-pub const TOTAL_SUPPLY: u128 = 1_000_000_000_000_000_000; // 1B Shares * 1e9 (9 decimals)
-pub const ANCHOR_THRESHOLD: u128 = 100_000_000_000_000_000; // 100M * 1e9
+// Optimistic Resolution Module - Bonded proposals with a challenge window
+pub mod optimistic_resolution;
 
-// PHASE BOUNDARIES (Scaled by 1e9) - AGGRESSIVE CURVE
-pub const PHASE1_END: u128 = 100_000_000_000_000_000;  // 100M
-pub const PHASE2_END: u128 = 200_000_000_000_000_000;  // 200M
-pub const PHASE3_START: u128 = 200_000_000_000_000_000;
+// Verification Module - Oracle-network votes that resolve markets
+pub mod verification;
 
-// PRICE CONSTANTS (in Lamports, 1 SOL = 1e9 Lamports)
-pub const P_START: u128 = 1_000;       // 1000 lamports (0.000001 SOL) - Higher base for depth
-pub const P_50: u128 = 25_000;         // 25k - Steep slope for fast gains
-pub const P_90: u128 = 250_000;        // 250k - Quadratic acceleration
-pub const P_MAX: u128 = 950_000_000;   // 0.95 SOL Max
+// Scalar Market Module - Range markets with a linear payout between bounds
+pub mod scalar_market;
 
-// 🔥 OPTIMAL ANCHOR: 1M Shares (Aggressive Pump Mode)
-// 1 SOL Buy => ~2x Price
-pub const VIRTUAL_ANCHOR: u128 = 1_000_000_000_000_000; // 1M * 1e9 
+// Conditional Market Module - Markets that live or die on another market's outcome
+pub mod conditional_market;
 
+// Test Fixtures - Account state for the pure-logic unit tests
+#[cfg(test)]
+mod test_fixtures;
 
-// SIGMOID K (Scaled: k * 1e18 for fixed-point)
-// ⚠️ LINEAR APPROXIMATION: norm_sig = k * x (not exp-based sigmoid!)
-// Philosophy: GRADUAL GROWTH for democratization
-// This gives ~19x at 120M shares, allowing longer accumulation phase
-pub const K_SIGMOID_SCALED: u128 = 1_250_000_000;
-pub const K_SCALE_FACTOR: u128 = 1_000_000_000_000_000_000; // 1e18
+use batch_auction::{Batch, BatchOrder, BatchStatus};
+use bonding_curve::CurveConfig;
+use conditional_market::MarketCondition;
+use limit_order::{LimitOrder, LimitOrderParams, OrderSide};
+use dca::{DcaParams, DcaPlan};
+use fee_schedule::FeeSchedule;
+use lmsr::PricingModel;
+use optimistic_resolution::{ProposalStatus, ResolutionProposal};
+use position_exit::ExitThresholds;
+use protocol_config::{ProtocolConfig, ProtocolParams};
+use quote::TradeQuote;
+use referral::Referrer;
+use scalar_market::MarketKind;
+use trading_limits::TradingLimits;
 
-// FEE CONSTANTS
-pub const ENTRY_FEE_BPS: u128 = 100;    // 1%
-pub const EXIT_FEE_BPS: u128 = 100;     // 1%
-pub const RESOLUTION_FEE_BPS: u128 = 200; // 2%
-pub const BPS_DENOMINATOR: u128 = 10_000;
+declare_id!("A8pVMgP6vwjGqcbYh1WGWDjXq9uwQRoF9Lz1siLmD7nm");
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DJINN CURVE V4 AGGRESSIVE: "EARLY BIRD REWARDS"
+// 3-Phase Piecewise Bonding Curve with Progressive Gains
+// The curve, fee split and payout math live in the shared `djinn-curve` crate so the
+// program, the frontend (WASM) and the scripts all price trades identically.
+// ═══════════════════════════════════════════════════════════════════════════════
+
+pub use djinn_curve::{
+    ANCHOR_THRESHOLD, BPS_DENOMINATOR, ENTRY_FEE_BPS, EXIT_FEE_BPS, K_SCALE_FACTOR, PHASE1_END, PHASE2_END,
+    PHASE3_START, P_50, P_90, P_MAX, P_START, RESOLUTION_FEE_BPS, SHARE_SCALE, SIGMOID_MIDPOINT,
+    SIGMOID_STEEPNESS, TOTAL_SUPPLY, VIRTUAL_ANCHOR,
+};
+use djinn_curve::fees::fee_from_bps;
+use djinn_curve::payout::{lmsr_payout, lmsr_settlement_at, pro_rata_payout};
 
 // TREASURY
 pub const G1_TREASURY: Pubkey = anchor_lang::solana_program::pubkey!("G1NaEsx5Pg7dSmyYy6Jfraa74b7nTbmN9A9NuiK171Ma");
@@ -53,113 +98,53 @@ pub const G1_TREASURY: Pubkey = anchor_lang::solana_program::pubkey!("G1NaEsx5Pg
 // CURVE MATH (V4 AGGRESSIVE: 3-PHASE PIECEWISE)
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Linear slope for Phase 1: m = (P_50 - P_START) / PHASE1_END
-fn get_linear_slope() -> u128 {
-    // (6000 - 1) / 50e15 → steeper slope for faster gains
-    ((P_50 - P_START) * K_SCALE_FACTOR) / PHASE1_END
+/// Lift a djinn-curve result into the program's error type
+pub(crate) fn curve_result<T>(result: djinn_curve::Result<T>) -> Result<T> {
+    result.map_err(|e| DjinnError::from(e).into())
 }
 
 /// Calculate spot price at given supply (in nanoSOL/Lamports)
 pub fn calculate_spot_price(supply: u128) -> Result<u128> {
-    // VIRTUAL ANCHOR: We add this to make the curve start at a stable point (50/50 odds)
-    let effective_supply = supply.checked_add(VIRTUAL_ANCHOR).unwrap();
-
-    if effective_supply <= PHASE1_END {
-        // PHASE 1: LINEAR RAMP
-        let slope = get_linear_slope();
-        let price_delta = (slope * effective_supply) / K_SCALE_FACTOR;
-        return Ok(P_START + price_delta);
-    } else if effective_supply <= PHASE2_END {
-        // PHASE 2: QUADRATIC BRIDGE
-        let price = calculate_bridge_price(effective_supply)?;
-        return Ok(price);
-    } else {
-        // PHASE 3: SIGMOID
-        let price = calculate_sigmoid_price(effective_supply)?;
-        return Ok(price);
-    }
-}
-
-
-/// Quadratic Bridge: P = P_50 + (P_90 - P_50) * t²
-fn calculate_bridge_price(supply: u128) -> Result<u128> {
-    let progress = supply.checked_sub(PHASE1_END).unwrap();
-    let range = PHASE2_END - PHASE1_END; // 40M
-    
-    // Quadratic acceleration: P = P_50 + (P_90 - P_50) * (progress/range)²
-    let ratio = (progress * 1_000_000) / range; // Scaled by 1e6
-    let ratio_sq = (ratio * ratio) / 1_000_000; // (progress/range)² * 1e6
-    
-    let price_delta = ((P_90 - P_50) * ratio_sq) / 1_000_000;
-    Ok(P_50 + price_delta)
+    curve_result(djinn_curve::calculate_spot_price(supply))
 }
 
-/// Sigmoid Phase: P = P_90 + (P_MAX - P_90) * normalized_sigmoid(x - 90M)
-fn calculate_sigmoid_price(supply: u128) -> Result<u128> {
-    let x_rel = supply.checked_sub(PHASE3_START).unwrap();
-    
-    // Linear approximation: norm_sig = k * x
-    let kz = (K_SIGMOID_SCALED * x_rel) / K_SCALE_FACTOR;
-    
-    // Clamp to [0, 1e9]
-    let norm_sig = if kz > 1_000_000_000 { 1_000_000_000 } else { kz };
-    
-    // P = P_90 + (P_MAX - P_90) * norm_sig / 1e9
-    let price_delta = ((P_MAX - P_90) * norm_sig) / 1_000_000_000;
-    Ok(P_90 + price_delta)
+/// Cumulative curve area (lamports * SHARE_SCALE) up to `supply`, including the virtual anchor
+pub fn calculate_curve_area(supply: u128) -> Result<u128> {
+    curve_result(djinn_curve::calculate_curve_area(supply))
 }
 
-/// Calculate cost to buy from supply_old to supply_new
+/// Calculate cost to buy from supply_old to supply_new (path-independent closed form)
 pub fn calculate_cost(supply_old: u128, supply_new: u128) -> Result<u128> {
-    if supply_new <= supply_old {
-        return Ok(0);
-    }
-    
-    // Trapezoidal approximation: Cost = (P_old + P_new) / 2 * delta
-    let p_old = calculate_spot_price(supply_old)?;
-    let p_new = calculate_spot_price(supply_new)?;
-    let delta = supply_new - supply_old;
-    
-    // Cost = (p_old + p_new) * delta / (2 * 1e9) [adjust for share scaling]
-    let avg_price = (p_old + p_new) / 2;
-    let cost = (avg_price * delta) / 1_000_000_000; // Divide by share scale
-    
-    Ok(cost)
+    curve_result(djinn_curve::calculate_cost(supply_old, supply_new))
 }
 
-/// Solve for shares received given SOL input (Binary Search)
+/// Solve for shares received given SOL input, rounded down
 pub fn calculate_shares_from_sol(sol_in: u128, supply_old: u128) -> Result<u128> {
-    if sol_in == 0 {
-        return Ok(0);
-    }
-    
-    // Binary search for supply_new such that cost(old, new) = sol_in
-    let mut low = supply_old;
-    let mut high = TOTAL_SUPPLY;
-    
-    // OPTIMIZATION (Point 7): Reduced from 50 to 30 to save gas
-    for _ in 0..30 {
-        let mid = (low + high) / 2;
-        let cost = calculate_cost(supply_old, mid)?;
-        
-        if cost < sol_in {
-            low = mid;
-        } else {
-            high = mid;
-        }
-        
-        if high - low < 1_000 { // Precision threshold
-            break;
-        }
-    }
-    
-    Ok(low - supply_old)
+    curve_result(djinn_curve::calculate_shares_from_sol(sol_in, supply_old))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // MARKET STATE & ACCOUNTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// How a new market prices and pays out; fixed for its lifetime at initialize_market
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MarketParams {
+    pub curve: CurveConfig,    // Bonding curve shared by every outcome
+    pub pricing: PricingModel, // Independent curves or coupled LMSR
+    pub kind: MarketKind,      // Categorical outcomes or a scalar range
+    pub parent_outcome: Option<u8>, // Conditional markets: parent_market outcome that keeps it live
+}
+
+impl MarketParams {
+    pub fn validate(&self, num_outcomes: u8) -> Result<()> {
+        require!((2..=6).contains(&num_outcomes), DjinnError::InvalidOutcomeCount);
+        self.curve.validate()?;
+        self.pricing.validate()?;
+        self.kind.validate(num_outcomes, &self.pricing)
+    }
+}
+
 #[account]
 pub struct Market {
     pub creator: Pubkey,
@@ -174,12 +159,144 @@ pub struct Market {
     pub winning_outcome: Option<u8>,
     pub bump: u8,
     pub vault_bump: u8,
+    pub curve: CurveConfig,      // Bonding curve shared by every outcome of this market
+    pub pricing: PricingModel,   // Independent curves or coupled LMSR
+    pub batch_window_slots: u64, // 0 = continuous trading, else batch auction window
+    pub open_batch_id: u64,      // Batch currently accepting orders in batch mode
+    pub limits: TradingLimits,   // Creator-set caps on direct buys
+    pub fee_schedule: FeeSchedule, // Entry / exit fee bps over time and volume
+    pub total_volume: u128,      // Lamports traded (buys in, sells out, fees included)
+    pub total_cost_basis: u128,  // Sum of position cost bases (void refund weights)
+    pub kind: MarketKind,        // Categorical outcomes or a scalar range
+    pub resolved_value: Option<i64>, // Scalar markets: the value they resolved at
+    pub condition: Option<MarketCondition>, // Parent market outcome this market depends on
+}
+
+impl Market {
+    // 8 (discriminator) + 32 (creator) + (4 + 64) (title) + 8 (nonce) + 1 (num_outcomes)
+    // + (6 * 16) (outcome_supplies array) + 16 (vault_balance) + 8 (total_pot) + 1 (status)
+    // + 8 (resolution_time) + 2 (winning_outcome) + 1 (bump) + 1 (vault_bump) + curve
+    // + pricing + 8 (batch_window_slots) + 8 (open_batch_id) + limits + fee_schedule
+    // + 16 (total_volume) + 16 (total_cost_basis) + kind + 9 (resolved_value)
+    // + (1 + condition)
+    pub const LEN: usize = 8 + 32 + (4 + 64) + 8 + 1 + (6 * 16) + 16 + 8 + 1 + 8 + 2 + 1 + 1
+        + CurveConfig::LEN + PricingModel::LEN + 8 + 8 + TradingLimits::LEN + FeeSchedule::LEN + 16 + 16
+        + MarketKind::LEN + 9 + (1 + MarketCondition::LEN);
+
+    /// Shares of `outcome_index` bought with `sol_in` lamports (after fees)
+    pub fn shares_for_sol(&self, outcome_index: u8, sol_in: u128) -> Result<u128> {
+        let outcome = outcome_index as usize;
+        match self.pricing {
+            PricingModel::Curve => self.curve.shares_for(sol_in, self.outcome_supplies[outcome]),
+            PricingModel::Lmsr { liquidity_b } => {
+                curve_result(djinn_curve::lmsr::lmsr_shares_for(self.supplies(), liquidity_b, outcome, sol_in))
+            }
+        }
+    }
+
+    /// Lamports (before fees) to buy exactly `shares` of `outcome_index`
+    pub fn sol_to_buy(&self, outcome_index: u8, shares: u128) -> Result<u128> {
+        let outcome = outcome_index as usize;
+        let current_supply = self.outcome_supplies[outcome];
+        let new_supply = current_supply.checked_add(shares).ok_or(DjinnError::MathError)?;
+        match self.pricing {
+            PricingModel::Curve => self.curve.cost(current_supply, new_supply),
+            PricingModel::Lmsr { liquidity_b } => {
+                curve_result(djinn_curve::lmsr::lmsr_buy_cost(self.supplies(), liquidity_b, outcome, shares))
+            }
+        }
+    }
+
+    /// Gross lamports released by selling `shares` of `outcome_index` (before fees)
+    pub fn sol_for_shares(&self, outcome_index: u8, shares: u128) -> Result<u128> {
+        let outcome = outcome_index as usize;
+        let current_supply = self.outcome_supplies[outcome];
+        let new_supply = current_supply.checked_sub(shares).ok_or(DjinnError::InsufficientShares)?;
+        match self.pricing {
+            PricingModel::Curve => self.curve.cost(new_supply, current_supply),
+            PricingModel::Lmsr { liquidity_b } => {
+                curve_result(djinn_curve::lmsr::lmsr_sell_refund(self.supplies(), liquidity_b, outcome, shares))
+            }
+        }
+    }
+
+    /// Fewest shares of `outcome_index` whose sale releases at least `refund` lamports (before fees)
+    pub fn shares_to_release(&self, outcome_index: u8, refund: u128) -> Result<u128> {
+        let outcome = outcome_index as usize;
+        match self.pricing {
+            PricingModel::Curve => self.curve.shares_to_release(refund, self.outcome_supplies[outcome]),
+            PricingModel::Lmsr { liquidity_b } => {
+                curve_result(djinn_curve::lmsr::lmsr_shares_to_release(self.supplies(), liquidity_b, outcome, refund))
+            }
+        }
+    }
+
+    /// Book a priced buy: outcome supply up by the shares, vault up by the net SOL.
+    /// Every continuous buy path goes through here, so batch-mode markets refuse it.
+    pub fn apply_buy(&mut self, outcome_index: u8, quote: &TradeQuote) -> Result<()> {
+        require!(self.batch_window_slots == 0, DjinnError::BatchModeActive);
+        let outcome = outcome_index as usize;
+        self.outcome_supplies[outcome] =
+            self.outcome_supplies[outcome].checked_add(quote.shares).ok_or(DjinnError::MathError)?;
+        self.vault_balance = self.vault_balance.checked_add(quote.net_sol).ok_or(DjinnError::MathError)?;
+        self.add_volume(quote.net_sol.checked_add(quote.fee).ok_or(DjinnError::MathError)?)
+    }
+
+    /// Book a priced sell: outcome supply down by the shares, vault down by the refund
+    /// (net SOL to the seller plus the fee)
+    pub fn apply_sell(&mut self, outcome_index: u8, quote: &TradeQuote) -> Result<()> {
+        let outcome = outcome_index as usize;
+        let actual_refund = quote.net_sol.checked_add(quote.fee).ok_or(DjinnError::MathError)?;
+        self.outcome_supplies[outcome] =
+            self.outcome_supplies[outcome].checked_sub(quote.shares).ok_or(DjinnError::MathError)?;
+        self.vault_balance = self.vault_balance.checked_sub(actual_refund).ok_or(DjinnError::MathError)?;
+        self.add_volume(actual_refund)
+    }
+
+    /// Book a priced swap: `shares_in` of `from_index` out, the quoted shares of
+    /// `to_index` in. The sell leg's net SOL stays in the vault; only the fee leaves.
+    pub fn apply_swap(&mut self, from_index: u8, to_index: u8, shares_in: u128, quote: &TradeQuote) -> Result<()> {
+        let (from, to) = (from_index as usize, to_index as usize);
+        self.outcome_supplies[from] = self.outcome_supplies[from].checked_sub(shares_in).ok_or(DjinnError::MathError)?;
+        self.outcome_supplies[to] = self.outcome_supplies[to].checked_add(quote.shares).ok_or(DjinnError::MathError)?;
+        self.vault_balance = self.vault_balance.checked_sub(quote.fee).ok_or(DjinnError::MathError)?;
+        self.add_volume(quote.net_sol.checked_add(quote.fee).ok_or(DjinnError::MathError)?)
+    }
+
+    /// Count `lamports` towards the market's volume (drives the early-volume fee discount)
+    pub fn add_volume(&mut self, lamports: u128) -> Result<()> {
+        self.total_volume = self.total_volume.checked_add(lamports).ok_or(DjinnError::MathError)?;
+        Ok(())
+    }
+
+    /// Supplies of the live outcomes only
+    pub fn supplies(&self) -> &[u128] {
+        &self.outcome_supplies[..self.num_outcomes as usize]
+    }
+
+    /// Spot price of `outcome_index` in lamports per whole share
+    pub fn spot_price(&self, outcome_index: u8) -> Result<u128> {
+        self.spot_price_at(self.supplies(), outcome_index)
+    }
+
+    /// Spot price of `outcome_index` if the live outcome supplies were `supplies`
+    pub fn spot_price_at(&self, supplies: &[u128], outcome_index: u8) -> Result<u128> {
+        let outcome = outcome_index as usize;
+        match self.pricing {
+            PricingModel::Curve => self.curve.spot_price(supplies[outcome]),
+            PricingModel::Lmsr { liquidity_b } => {
+                curve_result(djinn_curve::lmsr::lmsr_spot_price(supplies, liquidity_b, outcome))
+            }
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
 pub enum MarketStatus {
     Active,
     Resolved,
+    Voided, // Cancelled: positions reclaim their cost-basis share through claim_refund
+    PendingVerification, // Past resolution_time, outcome being voted on by the oracle network
 }
 
 #[account]
@@ -188,6 +305,16 @@ pub struct UserPosition {
     pub outcome: u8, // 0 = YES, 1 = NO
     pub shares: u128,
     pub claimed: bool,
+    pub exit_thresholds: ExitThresholds, // Stop-loss / take-profit (see position_exit)
+    pub last_trade_ts: i64,              // Last buy of any kind: cooldown and short-hold surcharge
+    pub cost_basis: u128,                // Net lamports paid in for `shares` (see void_market)
+    pub open_sell_orders: u32,           // Sell orders holding this position's shares (see limit_order)
+}
+
+impl UserPosition {
+    // 8 (discriminator) + 32 (market) + 1 (outcome) + 16 (shares) + 1 (claimed) + exit thresholds
+    // + 8 (last_trade_ts) + 16 (cost_basis) + 4 (open_sell_orders)
+    pub const LEN: usize = 8 + 32 + 1 + 16 + 1 + ExitThresholds::LEN + 8 + 16 + 4;
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -198,18 +325,57 @@ pub struct UserPosition {
 pub mod djinn_market {
     use super::*;
 
-    /// Create a new prediction market with multiple outcomes (2-6)
+    // ═══════════════════════════════════════════════════════════════════════════
+    // PROTOCOL CONFIG
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Create the global ProtocolConfig with the launch economics (G1 treasury, once).
+    /// The treasury is the first governance authority.
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        let config = &mut ctx.accounts.protocol_config;
+        config.authority = ctx.accounts.admin.key();
+        config.params = ProtocolParams::launch();
+        config.bump = ctx.bumps.protocol_config;
+        Ok(())
+    }
+
+    /// Governance replaces every fee and split; applies from the next instruction on.
+    /// Market entry / exit fees are the exception: they were snapshotted into each
+    /// market's FeeSchedule at creation, so only markets created afterwards pick them up.
+    pub fn update_protocol_config(ctx: Context<UpdateProtocolConfig>, params: ProtocolParams) -> Result<()> {
+        params.validate()?;
+        ctx.accounts.protocol_config.params = params;
+        Ok(())
+    }
+
+    /// Governance hands its authority over (e.g. to a multisig or DAO PDA)
+    pub fn set_protocol_authority(ctx: Context<UpdateProtocolConfig>, new_authority: Pubkey) -> Result<()> {
+        ctx.accounts.protocol_config.authority = new_authority;
+        Ok(())
+    }
+
+    /// Create a new prediction market with multiple outcomes (2-6). `params` fix its
+    /// curve, pricing model, kind (categorical or scalar) and, with parent_market,
+    /// the condition it depends on, for good.
     pub fn initialize_market(
         ctx: Context<InitializeMarket>,
         title: String,
         resolution_time: i64,
         nonce: i64,
         num_outcomes: u8, // 2-6
+        params: MarketParams,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
-
-        // Validate outcome count
-        require!(num_outcomes >= 2 && num_outcomes <= 6, DjinnError::InvalidOutcomeCount);
+        params.validate(num_outcomes)?;
+        let MarketParams { curve, pricing, kind, parent_outcome } = params;
+        let condition = match parent_outcome {
+            Some(parent_outcome) => {
+                let parent = ctx.accounts.parent_market.as_ref().ok_or(DjinnError::InvalidParentMarket)?;
+                require_keys_neq!(parent.key(), market.key(), DjinnError::InvalidParentMarket);
+                Some(MarketCondition::new(parent.key(), parent, parent_outcome)?)
+            }
+            None => None,
+        };
 
         market.creator = ctx.accounts.creator.key();
         market.title = title;
@@ -222,6 +388,17 @@ pub mod djinn_market {
         market.resolution_time = resolution_time;
         market.winning_outcome = None;
         market.bump = ctx.bumps.market;
+        market.curve = curve;
+        market.pricing = pricing;
+        market.batch_window_slots = 0;
+        market.open_batch_id = 0;
+        market.limits = TradingLimits::default();
+        market.fee_schedule = FeeSchedule::flat(&ctx.accounts.protocol_config.params);
+        market.total_volume = 0;
+        market.total_cost_basis = 0;
+        market.kind = kind;
+        market.resolved_value = None;
+        market.condition = condition;
         
         // Calculate vault bump
         let (_, vault_bump) = Pubkey::find_program_address(
@@ -244,6 +421,22 @@ pub mod djinn_market {
             ),
             creation_fee,
         )?;
+
+        // LMSR SUBSIDY: creator funds b·ln(N), the market maker's worst-case loss
+        if let PricingModel::Lmsr { liquidity_b } = pricing {
+            let funding = curve_result(djinn_curve::lmsr::lmsr_funding(num_outcomes, liquidity_b))?;
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.creator.to_account_info(),
+                        to: ctx.accounts.market_vault.to_account_info(),
+                    },
+                ),
+                funding as u64,
+            )?;
+            market.vault_balance = funding;
+        }
         
         Ok(())
     }
@@ -262,113 +455,34 @@ pub mod djinn_market {
         let now = Clock::get()?.unix_timestamp;
         require!(now < market.resolution_time, DjinnError::MarketExpired);
 
-        let sol_in_u128 = sol_in as u128;
+        // 1-3. Scheduled entry fee, fee split and shares (same path as quote_buy)
+        let quote = market.quote_buy(&ctx.accounts.protocol_config, outcome_index, sol_in as u128, now)?;
+        require!(quote.shares >= min_shares_out as u128, DjinnError::SlippageExceeded);
 
-        // 1. Calculate Entry Fee (1%)
-        let fee = (sol_in_u128 * ENTRY_FEE_BPS) / BPS_DENOMINATOR;
-        let net_sol = sol_in_u128 - fee;
+        settle_buy(ctx.accounts, outcome_index, &quote, sol_in as u128, now)
+    }
 
-        // 2. Get current supply for outcome
-        let current_supply = market.outcome_supplies[outcome_index as usize];
+    /// Buy exactly `shares_out` shares: curve cost plus the entry fee, capped by `max_sol_in`
+    pub fn buy_exact_shares(
+        ctx: Context<BuyShares>,
+        outcome_index: u8,
+        shares_out: u64,
+        max_sol_in: u64,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(shares_out > 0, DjinnError::InvalidAmount);
 
-        // 3. Calculate shares
-        let shares = calculate_shares_from_sol(net_sol, current_supply)?;
-        require!(shares >= min_shares_out as u128, DjinnError::SlippageExceeded);
+        // (Point 5) Check Expiry
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
 
-        // 4. Update state
-        market.outcome_supplies[outcome_index as usize] = current_supply.checked_add(shares).unwrap();
-        market.vault_balance = market.vault_balance.checked_add(net_sol).unwrap();
-        
-        // 5. Update user position
-        let position = &mut ctx.accounts.user_position;
-        position.market = market.key();
-        position.outcome = outcome_index;
-        position.shares = position.shares.checked_add(shares).unwrap();
-        
-        // 6. Transfer SOL
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.user.to_account_info(),
-                    to: ctx.accounts.market_vault.to_account_info(),
-                },
-            ),
-            net_sol as u64,
-        )?;
-        
-        // 7. Transfer fee (SPLIT: 40% Creator / 50% Treasury / 10% Insurance)
-        if fee > 0 {
-            let creator = market.creator;
-            let treasury = ctx.accounts.protocol_treasury.key();
-            
-            // Insurance always gets 10%
-            let insurance_cut = fee / 10;
-            
-            // Check Rate Limits based on Bot Tier
-            // Note: We need to pass bot_profile to enforce this, but currently buy_shares doesn't take it.
-            // If user IS a bot owner, we should ideally check the Bot PDA.
-            // For now, we proceed with the Fee Split Logic.
-            
-            if creator == treasury {
-                // G1 created market: 90% treasury + 10% insurance
-                let treasury_cut = fee - insurance_cut;
-                anchor_lang::system_program::transfer(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.user.to_account_info(),
-                            to: ctx.accounts.protocol_treasury.to_account_info(),
-                        },
-                    ),
-                    treasury_cut as u64,
-                )?;
-            } else {
-                // User/Bot created market: 40% Creator / 50% Treasury / 10% Insurance
-                let creator_cut = (fee * 40) / 100;
-                let treasury_cut = fee - creator_cut - insurance_cut;
-                
-                // To Treasury (50%)
-                anchor_lang::system_program::transfer(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.user.to_account_info(),
-                            to: ctx.accounts.protocol_treasury.to_account_info(),
-                        },
-                    ),
-                    treasury_cut as u64,
-                )?;
-                
-                // To Creator (40%)
-                anchor_lang::system_program::transfer(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.user.to_account_info(),
-                            to: ctx.accounts.market_creator.to_account_info(),
-                        },
-                    ),
-                    creator_cut as u64,
-                )?;
-            }
-            
-            // Insurance Pool (10% always)
-            if insurance_cut > 0 {
-                anchor_lang::system_program::transfer(
-                    CpiContext::new(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.user.to_account_info(),
-                            to: ctx.accounts.insurance_vault.to_account_info(),
-                        },
-                    ),
-                    insurance_cut as u64,
-                )?;
-            }
-        }
-        
-        Ok(())
+        let quote = market.quote_buy_exact(&ctx.accounts.protocol_config, outcome_index, shares_out as u128, now)?;
+        let sol_in = quote.net_sol.checked_add(quote.fee).ok_or(DjinnError::MathError)?;
+        require!(sol_in <= max_sol_in as u128, DjinnError::SlippageExceeded);
+
+        settle_buy(ctx.accounts, outcome_index, &quote, sol_in, now)
     }
 
     pub fn sell_shares(
@@ -377,8 +491,8 @@ pub mod djinn_market {
         shares_to_sell: u64,
         min_sol_out: u64, // (Point 3) Slippage argument
     ) -> Result<()> {
-        let market = &mut ctx.accounts.market;
-        let position = &mut ctx.accounts.user_position;
+        let market = &ctx.accounts.market;
+        let position = &ctx.accounts.user_position;
 
         require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
         require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
@@ -388,33 +502,80 @@ pub mod djinn_market {
         let now = Clock::get()?.unix_timestamp;
         require!(now < market.resolution_time, DjinnError::MarketExpired);
 
-        let shares_u128 = shares_to_sell as u128;
-        let current_supply = market.outcome_supplies[outcome_index as usize];
+        // 1-3. Curve refund, vault clamp, scheduled exit fee and fee split (same path as quote_sell)
+        let quote = market.quote_sell(&ctx.accounts.protocol_config, outcome_index, shares_to_sell as u128, now, position.last_trade_ts)?;
 
-        // 1. Calculate SOL value of shares (Bonding Curve Value)
-        let new_supply = current_supply.checked_sub(shares_u128).unwrap();
-        let refund_gross = calculate_cost(new_supply, current_supply)?;
+        // (Point 3) Slippage Check
+        require!(quote.net_sol >= min_sol_out as u128, DjinnError::SlippageExceeded);
 
-        // 2. SAFETY CLAMP: Ensure we don't try to refund more than what's in the vault
-        let actual_refund = if refund_gross > market.vault_balance {
-            market.vault_balance
-        } else {
-            refund_gross
-        };
+        settle_sell(ctx.accounts, outcome_index, &quote)
+    }
 
-        // 3. Exit fee (1%)
-        let fee = (actual_refund * EXIT_FEE_BPS) / BPS_DENOMINATOR;
-        let net_refund = actual_refund - fee;
+    /// Sell just enough shares to net `sol_out` after the exit fee, burning at most
+    /// `max_shares_in`. Returns the shares burned.
+    pub fn sell_for_sol(
+        ctx: Context<SellShares>,
+        outcome_index: u8,
+        sol_out: u64,
+        max_shares_in: u64,
+    ) -> Result<u64> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(sol_out > 0, DjinnError::InvalidAmount);
 
-        // (Point 3) Slippage Check
-        require!(net_refund >= min_sol_out as u128, DjinnError::SlippageExceeded);
+        // (Point 5) Check Expiry
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
 
-        // 4. Update state
-        market.outcome_supplies[outcome_index as usize] = new_supply;
-        market.vault_balance = market.vault_balance.checked_sub(actual_refund).unwrap();
-        position.shares = position.shares.checked_sub(shares_u128).unwrap();
-        
-        // 5. Transfer SOL to User
+        let last_buy_ts = ctx.accounts.user_position.last_trade_ts;
+        let quote = market.quote_sell_for_sol(&ctx.accounts.protocol_config, outcome_index, sol_out as u128, now, last_buy_ts)?;
+        require!(quote.shares <= max_shares_in as u128, DjinnError::SlippageExceeded);
+        require!(ctx.accounts.user_position.shares >= quote.shares, DjinnError::InsufficientShares);
+
+        settle_sell(ctx.accounts, outcome_index, &quote)?;
+        Ok(quote.shares as u64)
+    }
+
+    /// Move `shares_in` of `from_index` into `to_index` inside the market vault.
+    /// One exit fee on the value moved; both UserPositions are updated atomically.
+    pub fn swap_outcome(
+        ctx: Context<SwapOutcome>,
+        from_index: u8,
+        to_index: u8,
+        shares_in: u64,
+        min_shares_out: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(from_index < market.num_outcomes && to_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(from_index != to_index, DjinnError::InvalidOutcome);
+        require!(shares_in > 0, DjinnError::InvalidAmount);
+        require!(market.batch_window_slots == 0, DjinnError::BatchModeActive);
+        require!(ctx.accounts.from_position.shares >= shares_in as u128, DjinnError::InsufficientShares);
+
+        // (Point 5) Check Expiry
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        let last_buy_ts = ctx.accounts.from_position.last_trade_ts;
+        let quote = market.quote_swap(&ctx.accounts.protocol_config, from_index, to_index, shares_in as u128, now, last_buy_ts)?;
+        require!(quote.shares >= min_shares_out as u128, DjinnError::SlippageExceeded);
+
+        // 1. Update state: only the fee leaves the vault
+        market.apply_swap(from_index, to_index, shares_in as u128, &quote)?;
+
+        // 2. Update both user positions; the cost basis moves with the value
+        let from_position = &mut ctx.accounts.from_position;
+        let basis = from_position.debit(shares_in as u128)?;
+
+        let to_position = &mut ctx.accounts.to_position;
+        to_position.market = market.key();
+        to_position.outcome = to_index;
+        to_position.record_buy(&market.limits, quote.net_sol, quote.shares, basis, now)?;
+
+        // 3. Transfer fee out of the vault (split per ProtocolConfig, referrer's cut out
+        //    of the treasury share)
         let market_key = market.key();
         let seeds = &[
             b"market_vault",
@@ -422,211 +583,408 @@ pub mod djinn_market {
             &[market.vault_bump],
         ];
         let signer = &[&seeds[..]];
-        
-        if net_refund > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.market_vault.to_account_info(),
-                        to: ctx.accounts.user.to_account_info(),
-                    },
-                    signer,
-                ),
-                net_refund as u64,
-            )?;
+        let user = ctx.accounts.user.key();
+        let (quote, referral) = referral::take_referral(
+            &mut ctx.accounts.referrer,
+            &ctx.accounts.referral_vault,
+            &ctx.accounts.protocol_config,
+            &user,
+            &quote,
+        )?;
+        let system_program = ctx.accounts.system_program.to_account_info();
+        route_trading_fee(
+            &quote,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.protocol_treasury,
+            &ctx.accounts.market_creator,
+            &ctx.accounts.insurance_vault,
+            &system_program,
+            signer,
+        )?;
+        if let Some((referral_vault, cut)) = referral {
+            transfer_lamports(&ctx.accounts.market_vault, &referral_vault, &system_program, signer, cut)?;
         }
+        Ok(())
+    }
 
-        // 6. Transfer Fee (SPLIT: 40% Creator / 50% Treasury / 10% Insurance)
-        if fee > 0 {
-            let creator = market.creator;
-            let treasury = ctx.accounts.protocol_treasury.key();
-            
-            // Insurance always gets 10%
-            let insurance_cut = fee / 10;
-            
-            if creator == treasury {
-                // G1 created market: 90% treasury + 10% insurance
-                let treasury_cut = fee - insurance_cut;
-                anchor_lang::system_program::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.market_vault.to_account_info(),
-                            to: ctx.accounts.protocol_treasury.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    treasury_cut as u64,
-                )?;
-            } else {
-                // User/Bot created market: 40% Creator / 50% Treasury / 10% Insurance
-                let creator_cut = (fee * 40) / 100;
-                let treasury_cut = fee - creator_cut - insurance_cut;
-                
-                // To Treasury (50%)
-                anchor_lang::system_program::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.market_vault.to_account_info(),
-                            to: ctx.accounts.protocol_treasury.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    treasury_cut as u64,
-                )?;
-                
-                // To Creator (40%)
-                anchor_lang::system_program::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.market_vault.to_account_info(),
-                            to: ctx.accounts.market_creator.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    creator_cut as u64,
-                )?;
-            }
-            
-            // Insurance Pool (10% always)
-            if insurance_cut > 0 {
-                anchor_lang::system_program::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.market_vault.to_account_info(),
-                            to: ctx.accounts.insurance_vault.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    insurance_cut as u64,
-                )?;
-            }
+    /// Mint `amount` share units of every outcome for `amount` lamports.
+    /// Remaining accounts: the caller's UserPosition PDAs for outcomes 0..num_outcomes.
+    pub fn mint_complete_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompleteSet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(amount > 0, DjinnError::InvalidAmount);
+        require!(market.batch_window_slots == 0, DjinnError::BatchModeActive);
+
+        // (Point 5) Check Expiry
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        let user = ctx.accounts.user.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mut positions = complete_set::load_positions(
+            ctx.remaining_accounts,
+            market.key(),
+            market.num_outcomes,
+            &user,
+            &system_program,
+            true,
+        )?;
+
+        // 1. One unit of every outcome per lamport; the basis is spread evenly (dust on outcome 0)
+        let amount_u128 = amount as u128;
+        market.mint_sets(amount_u128)?;
+        let basis_each = amount_u128 / market.num_outcomes as u128;
+        let dust = amount_u128 - basis_each * market.num_outcomes as u128;
+        let mut basis = basis_each + dust;
+        for position in positions.iter_mut() {
+            position.record_buy(&market.limits, amount_u128, amount_u128, basis, now)?;
+            position.exit(&crate::ID)?;
+            basis = basis_each;
         }
+        market.add_cost_basis(amount_u128)?;
 
-        
-        Ok(())
+        // 2. Pay par into the vault
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program,
+                anchor_lang::system_program::Transfer {
+                    from: user,
+                    to: ctx.accounts.market_vault.to_account_info(),
+                },
+            ),
+            amount,
+        )
     }
 
-    /// Resolve market - declares the winning outcome
-    pub fn resolve_market(
-        ctx: Context<ResolveMarket>,
-        winning_outcome: u8, // 0 = YES, 1 = NO
+    /// Burn `amount` share units of every outcome for `amount` lamports.
+    /// Remaining accounts: the caller's UserPosition PDAs for outcomes 0..num_outcomes.
+    pub fn redeem_complete_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CompleteSet<'info>>,
+        amount: u64,
     ) -> Result<()> {
         let market = &mut ctx.accounts.market;
-        
         require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
-        
-        // (Point 4) Fixed Outcome Check
-        require!(winning_outcome < market.num_outcomes, DjinnError::InvalidOutcome);
-        
-        // Check resolution time
+        require!(amount > 0, DjinnError::InvalidAmount);
+
+        // (Point 5) Check Expiry
         let now = Clock::get()?.unix_timestamp;
-        require!(now >= market.resolution_time, DjinnError::MarketNotExpired);
-        
-        // Extract resolution fee (2%)
-        let resolution_fee = (market.vault_balance * RESOLUTION_FEE_BPS) / BPS_DENOMINATOR;
-        
-        if resolution_fee > 0 {
-            // Split 50/50: 1% to Treasury, 1% to Bounty Pool
-            let treasury_cut = resolution_fee / 2;
-            let bounty_cut = resolution_fee - treasury_cut;
-            
-            let market_key = market.key();
-            let seeds = &[
-                b"market_vault",
-                market_key.as_ref(),
-                &[market.vault_bump],
-            ];
-            let signer = &[&seeds[..]];
-            
-            // 1. To Treasury
-            if treasury_cut > 0 {
-                 anchor_lang::system_program::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.market_vault.to_account_info(),
-                            to: ctx.accounts.protocol_treasury.to_account_info(),
-                        },
-                        signer,
-                    ),
-                    treasury_cut as u64,
-                )?;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        let user = ctx.accounts.user.to_account_info();
+        let system_program = ctx.accounts.system_program.to_account_info();
+        let mut positions = complete_set::load_positions(
+            ctx.remaining_accounts,
+            market.key(),
+            market.num_outcomes,
+            &user,
+            &system_program,
+            false,
+        )?;
+
+        // 1. Burn one unit of every outcome per lamport
+        let amount_u128 = amount as u128;
+        market.redeem_sets(amount_u128)?;
+        let mut released_basis = 0u128;
+        for position in positions.iter_mut() {
+            released_basis = released_basis.checked_add(position.debit(amount_u128)?).ok_or(DjinnError::MathError)?;
+            position.exit(&crate::ID)?;
+        }
+        market.remove_cost_basis(released_basis)?;
+
+        // 2. Pay par out of the vault
+        let market_key = market.key();
+        let seeds = &[
+            b"market_vault",
+            market_key.as_ref(),
+            &[market.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program,
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.market_vault.to_account_info(),
+                    to: user,
+                },
+                signer,
+            ),
+            amount,
+        )
+    }
+
+    /// Preview buy_shares: shares out, fee breakdown, post-trade spot price and impact.
+    /// Read-only; the TradeQuote is returned through set_return_data.
+    pub fn quote_buy(ctx: Context<QuoteTrade>, outcome_index: u8, sol_in: u64) -> Result<TradeQuote> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        market.quote_buy(&ctx.accounts.protocol_config, outcome_index, sol_in as u128, now)
+    }
+
+    /// Preview buy_exact_shares: total SOL in is `net_sol + fee`
+    pub fn quote_buy_exact(ctx: Context<QuoteTrade>, outcome_index: u8, shares_out: u64) -> Result<TradeQuote> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        market.quote_buy_exact(&ctx.accounts.protocol_config, outcome_index, shares_out as u128, now)
+    }
+
+    /// Preview sell_shares: SOL out after the vault clamp and exit fee, plus the same breakdown.
+    /// Pass the seller's UserPosition to include the short-hold exit surcharge.
+    /// Read-only; the TradeQuote is returned through set_return_data.
+    pub fn quote_sell(ctx: Context<QuoteTrade>, outcome_index: u8, shares: u64) -> Result<TradeQuote> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        let last_buy_ts = ctx.accounts.last_buy_ts(outcome_index)?;
+        market.quote_sell(&ctx.accounts.protocol_config, outcome_index, shares as u128, now, last_buy_ts)
+    }
+
+    /// Preview sell_for_sol: `shares` is what would be burned for `sol_out`
+    pub fn quote_sell_for_sol(ctx: Context<QuoteTrade>, outcome_index: u8, sol_out: u64) -> Result<TradeQuote> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        let last_buy_ts = ctx.accounts.last_buy_ts(outcome_index)?;
+        market.quote_sell_for_sol(&ctx.accounts.protocol_config, outcome_index, sol_out as u128, now, last_buy_ts)
+    }
+
+    /// Preview swap_outcome: `shares` is the amount of `to_index` received
+    pub fn quote_swap(ctx: Context<QuoteTrade>, from_index: u8, to_index: u8, shares_in: u64) -> Result<TradeQuote> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(from_index < market.num_outcomes && to_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(from_index != to_index, DjinnError::InvalidOutcome);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
+        let last_buy_ts = ctx.accounts.last_buy_ts(from_index)?;
+        market.quote_swap(&ctx.accounts.protocol_config, from_index, to_index, shares_in as u128, now, last_buy_ts)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // REFERRALS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Register the signer as a referrer. Its referral vault is funded with its own rent
+    /// so it can receive cuts of any size.
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        let referrer = &mut ctx.accounts.referrer;
+        referrer.owner = ctx.accounts.owner.key();
+        referrer.total_volume = 0;
+        referrer.trade_count = 0;
+        referrer.total_earned = 0;
+        referrer.unclaimed = 0;
+        referrer.created_at = Clock::get()?.unix_timestamp;
+        referrer.bump = ctx.bumps.referrer;
+        referrer.vault_bump = ctx.bumps.referral_vault;
+
+        transfer_lamports(
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.referral_vault,
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+            Rent::get()?.minimum_balance(0),
+        )
+    }
+
+    /// Referrer withdraws every referral fee accrued since the last claim. Returns the amount.
+    pub fn claim_referral_fees(ctx: Context<ClaimReferralFees>) -> Result<u64> {
+        let referrer = &mut ctx.accounts.referrer;
+        let amount = referrer.unclaimed;
+        require!(amount > 0, DjinnError::NoReferralFees);
+        referrer.unclaimed = 0;
+
+        let referrer_key = referrer.key();
+        let seeds: &[&[u8]] = &[b"referral_vault", referrer_key.as_ref(), &[referrer.vault_bump]];
+        transfer_lamports(
+            &ctx.accounts.referral_vault,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[seeds],
+            amount,
+        )?;
+        Ok(amount)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // LIMIT ORDERS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Rest a price-triggered order on one outcome. Buy orders escrow `amount` lamports,
+    /// sell orders take `amount` shares out of the owner's position; both escrow the tip.
+    pub fn place_limit_order(
+        ctx: Context<PlaceLimitOrder>,
+        outcome_index: u8,
+        order_id: u64,
+        params: LimitOrderParams,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(params.amount > 0, DjinnError::InvalidAmount);
+        require!(params.trigger_price > 0, DjinnError::InvalidTriggerPrice);
+        require!(params.tip >= limit_order::MIN_CRANK_TIP, DjinnError::CrankTipTooLow);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+        require!(params.expiry > now, DjinnError::OrderExpired);
+
+        let order = &mut ctx.accounts.limit_order;
+        order.owner = ctx.accounts.owner.key();
+        order.market = market.key();
+        order.order_id = order_id;
+        order.outcome = outcome_index;
+        order.side = params.side;
+        order.amount = params.amount;
+        order.trigger_price = params.trigger_price;
+        order.min_out = params.min_out;
+        order.tip = params.tip;
+        order.expiry = params.expiry;
+        order.bump = ctx.bumps.limit_order;
+        order.escrow_bump = ctx.bumps.order_escrow;
+        order.cost_basis = 0;
+
+        let position = &mut ctx.accounts.user_position;
+        position.market = market.key();
+        position.outcome = outcome_index;
+
+        // The escrow holds its own rent so it can hold any tip, however small
+        let mut deposit = Rent::get()?.minimum_balance(0)
+            .checked_add(params.tip)
+            .ok_or(DjinnError::MathError)?;
+        match params.side {
+            OrderSide::Buy => {
+                deposit = deposit.checked_add(params.amount).ok_or(DjinnError::MathError)?;
             }
-           
-            // 2. To Bounty Pool Vault (Optional: if bounty pool account is passed)
-            // Note: Currently resolve_market doesn't take bounty_vault as account.
-            // We need to either pass it or just send to treasury for manual distribution.
-            // Given the complexity of changing the instruction signature extensively, 
-            // and that BountyVault accounts are per-bounty-pool (not global),
-            // we will send the Bounty Share to a designated "Global Bounty Treasury" or keep it in Treasury for now?
-            
-            // ACTUALLY: The correct way is to send to the Bounty Pool specific to this market?
-            // But bounty pools are created later or separately.
-            
-            // PLAN B: Send EVERYTHING to Treasury, but mark it as "Restricted"?
-            // OR: Send to a separate "Insurance/Operating" wallet that funds bounties.
-            
-            // For now, to keep simulation accurate, we will send the `bounty_cut` to the `insurance_vault`
-            // which can act as a holding tank for operational expenses (bounties).
-            
-            if bounty_cut > 0 {
-                 anchor_lang::system_program::transfer(
-                    CpiContext::new_with_signer(
-                        ctx.accounts.system_program.to_account_info(),
-                        anchor_lang::system_program::Transfer {
-                            from: ctx.accounts.market_vault.to_account_info(),
-                            to: ctx.accounts.insurance_vault.to_account_info(), 
-                        },
-                        signer,
-                    ),
-                    bounty_cut as u64,
-                )?;
+            OrderSide::Sell => {
+                // The escrowed shares take their cost basis with them
+                order.cost_basis = position.escrow_sell(params.amount as u128)?;
             }
+        }
+
+        transfer_lamports(
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.order_escrow,
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+            deposit,
+        )
+    }
 
-            market.vault_balance = market.vault_balance.checked_sub(resolution_fee).unwrap();
+    /// Owner pulls a resting order: escrowed SOL and shares go back, the order closes
+    pub fn cancel_limit_order(ctx: Context<CancelLimitOrder>) -> Result<()> {
+        let order = &ctx.accounts.limit_order;
+        if order.side == OrderSide::Sell {
+            ctx.accounts.user_position.refund_sell(order.amount as u128, order.cost_basis)?;
         }
-        
-        // (Point 2) Snapshot the Pot Balance for fair Claiming
-        market.total_pot_at_resolution = market.vault_balance as u64;
 
-        market.status = MarketStatus::Resolved;
-        market.winning_outcome = Some(winning_outcome);
-        
-        Ok(())
+        let order_key = order.key();
+        let seeds: &[&[u8]] = &[b"order_escrow", order_key.as_ref(), &[order.escrow_bump]];
+        limit_order::drain_escrow(
+            &ctx.accounts.order_escrow,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            seeds,
+        )
     }
 
-    /// Claim winnings after market resolution
-    pub fn claim_winnings(
-        ctx: Context<ClaimWinnings>,
-        outcome_index: u8,
+    /// Permissionless: fill every triggered order passed in, refund expired ones and
+    /// pay the cranker each order's tip. Orders that are not triggered, or whose fill
+    /// would miss min_out or break the market's trading limits, are skipped. Remaining accounts come in groups of
+    /// [limit_order, order_escrow, owner's UserPosition, owner]. Returns orders closed.
+    pub fn crank_orders<'info>(ctx: Context<'_, '_, 'info, 'info, CrankOrders<'info>>) -> Result<u32> {
+        let groups = ctx.remaining_accounts.chunks_exact(4);
+        require!(groups.len() > 0 && groups.remainder().is_empty(), DjinnError::InvalidOrderAccount);
+
+        let accounts = limit_order::CrankAccounts {
+            market_key: ctx.accounts.market.key(),
+            market_vault: ctx.accounts.market_vault.to_account_info(),
+            protocol_treasury: ctx.accounts.protocol_treasury.to_account_info(),
+            market_creator: ctx.accounts.market_creator.to_account_info(),
+            insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+            cranker: ctx.accounts.cranker.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let now = Clock::get()?.unix_timestamp;
+        let market = &mut ctx.accounts.market;
+
+        let mut closed = 0u32;
+        for group in groups {
+            if limit_order::crank_order(market, &ctx.accounts.protocol_config, &accounts, group, now)? {
+                closed += 1;
+            }
+        }
+        Ok(closed)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // STOP-LOSS / TAKE-PROFIT
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Arm (or with both thresholds 0, disarm) the caller's stop-loss / take-profit
+    pub fn set_exit_thresholds(
+        ctx: Context<SetExitThresholds>,
+        _outcome_index: u8,
+        thresholds: ExitThresholds,
     ) -> Result<()> {
+        require!(
+            thresholds.stop_loss == 0 || thresholds.take_profit == 0 || thresholds.stop_loss < thresholds.take_profit,
+            DjinnError::InvalidExitThresholds
+        );
+        ctx.accounts.user_position.exit_thresholds = thresholds;
+        Ok(())
+    }
+
+    /// Permissionless: sell the owner's whole position once the exit would realise a
+    /// crossed threshold. The keeper keeps up to keeper_tip of the proceeds (capped at
+    /// MAX_KEEPER_TIP_BPS); the owner nets at least min_sol_out or the exit fails.
+    pub fn execute_exit(ctx: Context<ExecuteExit>, outcome_index: u8) -> Result<()> {
         let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+
+        // (Point 5) Check Expiry
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+
         let position = &mut ctx.accounts.user_position;
-        
-        require!(market.status == MarketStatus::Resolved, DjinnError::MarketNotResolved);
-        require!(!position.claimed, DjinnError::AlreadyClaimed);
+        let thresholds = position.exit_thresholds;
+        require!(thresholds.is_set(), DjinnError::NoExitThresholds);
         require!(position.shares > 0, DjinnError::NoShares);
-        
-        let winning_outcome = market.winning_outcome.unwrap();
-        require!(outcome_index == winning_outcome, DjinnError::NotWinner);
-        
-        // (Point 2) Use total_pot_at_resolution instead of shrinking vault_balance
-        // Calculate payout: user_shares / total_winning_shares * SNAPSHOT_BALANCE
-        let total_winning_shares = market.outcome_supplies[winning_outcome as usize];
-        
-        if total_winning_shares == 0 {
-            return Ok(());
-        }
-        
-        let snapshot_pot = market.total_pot_at_resolution as u128;
-        let payout = (snapshot_pot * position.shares) / total_winning_shares;
-        
-        // Transfer payout
+
+        // 1. Price the full exit through the sell_shares path, then test the threshold
+        //    against what it realises (not the pre-trade spot a prior trade can move)
+        let quote = market.quote_sell(&ctx.accounts.protocol_config, outcome_index, position.shares, now, position.last_trade_ts)?;
+        require!(thresholds.is_crossed(thresholds.measure(&quote)?), DjinnError::ExitThresholdNotCrossed);
+
+        let tip = thresholds.tip_for(quote.net_sol)?;
+        let owner_out = quote.net_sol - tip;
+        require!(owner_out >= thresholds.min_sol_out as u128, DjinnError::SlippageExceeded);
+
+        // 2. Update state; thresholds are one-shot
+        market.apply_sell(outcome_index, &quote)?;
+        let basis = position.debit(quote.shares)?;
+        market.remove_cost_basis(basis)?;
+        position.exit_thresholds = ExitThresholds::default();
+
+        // 3. Proceeds to the owner and keeper, fee split out of the vault
         let market_key = market.key();
         let seeds = &[
             b"market_vault",
@@ -634,43 +992,673 @@ pub mod djinn_market {
             &[market.vault_bump],
         ];
         let signer = &[&seeds[..]];
-        
-        if payout > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.market_vault.to_account_info(),
-                        to: ctx.accounts.user.to_account_info(),
-                    },
-                    signer,
-                ),
-                payout as u64,
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        transfer_lamports(&ctx.accounts.market_vault, &ctx.accounts.owner, &system_program, signer, owner_out as u64)?;
+        transfer_lamports(
+            &ctx.accounts.market_vault,
+            &ctx.accounts.keeper.to_account_info(),
+            &system_program,
+            signer,
+            tip as u64,
+        )?;
+        route_trading_fee(
+            &quote,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.protocol_treasury,
+            &ctx.accounts.market_creator,
+            &ctx.accounts.insurance_vault,
+            &system_program,
+            signer,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // DCA PLANS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Open a DCA plan: escrow `deposit` lamports to buy `outcome_index` in
+    /// amount_per_interval chunks. The first buy is due immediately.
+    pub fn create_dca_plan(
+        ctx: Context<CreateDcaPlan>,
+        outcome_index: u8,
+        plan_id: u64,
+        params: DcaParams,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(params.amount_per_interval > 0 && params.interval_secs > 0, DjinnError::InvalidDcaSchedule);
+        require!(params.keeper_tip >= limit_order::MIN_CRANK_TIP, DjinnError::CrankTipTooLow);
+        let per_execution = params.amount_per_interval.checked_add(params.keeper_tip).ok_or(DjinnError::MathError)?;
+        require!(params.deposit >= per_execution, DjinnError::InvalidAmount);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+        require!(params.end_time > now, DjinnError::InvalidDcaSchedule);
+
+        let plan = &mut ctx.accounts.dca_plan;
+        plan.owner = ctx.accounts.owner.key();
+        plan.market = market.key();
+        plan.plan_id = plan_id;
+        plan.outcome = outcome_index;
+        plan.amount_per_interval = params.amount_per_interval;
+        plan.interval_secs = params.interval_secs;
+        plan.end_time = params.end_time;
+        plan.keeper_tip = params.keeper_tip;
+        plan.max_price = params.max_price;
+        plan.remaining = params.deposit;
+        plan.next_execution = now;
+        plan.executions = 0;
+        plan.total_spent = 0;
+        plan.total_shares = 0;
+        plan.bump = ctx.bumps.dca_plan;
+        plan.escrow_bump = ctx.bumps.dca_escrow;
+
+        let position = &mut ctx.accounts.user_position;
+        position.market = market.key();
+        position.outcome = outcome_index;
+
+        // The escrow holds its own rent on top of the budget
+        let deposit = Rent::get()?.minimum_balance(0)
+            .checked_add(params.deposit)
+            .ok_or(DjinnError::MathError)?;
+        transfer_lamports(
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.dca_escrow,
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+            deposit,
+        )
+    }
+
+    /// Permissionless: run one due interval of a DCA plan through the buy_shares path
+    /// and pay the keeper its tip. Returns the shares bought (0 when the interval was
+    /// skipped for max_price).
+    pub fn execute_dca(ctx: Context<ExecuteDca>) -> Result<u64> {
+        let market = &mut ctx.accounts.market;
+        let plan = &mut ctx.accounts.dca_plan;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < market.resolution_time, DjinnError::MarketExpired);
+        require!(now < plan.end_time, DjinnError::DcaPlanEnded);
+        require!(now >= plan.next_execution, DjinnError::DcaIntervalNotElapsed);
+
+        let per_execution = plan.amount_per_interval.checked_add(plan.keeper_tip).ok_or(DjinnError::MathError)?;
+        require!(plan.remaining >= per_execution, DjinnError::DcaEscrowDepleted);
+
+        // 1. Scheduled entry fee, fee split and shares (same path as buy_shares);
+        //    an interval priced above max_price is skipped
+        let quote = plan.due_buy(market, &ctx.accounts.protocol_config, now)?;
+
+        // 2. Update state
+        if let Some(quote) = &quote {
+            market.apply_buy(plan.outcome, quote)?;
+            market.add_cost_basis(quote.net_sol)?;
+            let sol_in = quote.net_sol.checked_add(quote.fee).ok_or(DjinnError::MathError)?;
+            ctx.accounts.user_position.record_buy(&market.limits, sol_in, quote.shares, quote.net_sol, now)?;
+        }
+        plan.record_interval(now, quote.as_ref())?;
+
+        // 3. Escrow pays the vault, the fee split and the keeper
+        let plan_key = plan.key();
+        let seeds: &[&[u8]] = &[b"dca_escrow", plan_key.as_ref(), &[plan.escrow_bump]];
+        let escrow = &ctx.accounts.dca_escrow;
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        if let Some(quote) = &quote {
+            transfer_lamports(escrow, &ctx.accounts.market_vault, &system_program, &[seeds], quote.net_sol as u64)?;
+            route_trading_fee(
+                quote,
+                escrow,
+                &ctx.accounts.protocol_treasury,
+                &ctx.accounts.market_creator,
+                &ctx.accounts.insurance_vault,
+                &system_program,
+                &[seeds],
             )?;
-            
-            // We still decrease vault balance to track remaining funds, but it doesn't affect payout calculation
-            market.vault_balance = market.vault_balance.checked_sub(payout).unwrap();
         }
-        
-        position.claimed = true;
-        
-        Ok(())
+        transfer_lamports(
+            escrow,
+            &ctx.accounts.keeper.to_account_info(),
+            &system_program,
+            &[seeds],
+            plan.keeper_tip,
+        )?;
+        Ok(quote.map_or(0, |quote| quote.shares as u64))
     }
 
     // ═══════════════════════════════════════════════════════════════════════════
-    // CHRONOS MARKET INSTRUCTIONS (Automated Crypto Majors)
+    // BATCH AUCTIONS
     // ═══════════════════════════════════════════════════════════════════════════
 
-    /// Initialize a new Chronos market (called by keeper bot)
+    /// Creator switches the market between continuous trading (0) and batch auctions
+    /// collecting orders for `window_slots` slots. Only before the first trade, so
+    /// resting orders, DCA plans and open batches never see the mode change under them.
+    pub fn set_batch_mode(ctx: Context<UpdateMarketConfig>, window_slots: u64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(market.total_volume == 0, DjinnError::BatchModeLocked);
+        market.batch_window_slots = window_slots;
+        Ok(())
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // TRADING LIMITS
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Creator sets the market's per-wallet, per-trade and cooldown limits (0 = off);
+    /// only before the first trade
+    pub fn set_trading_limits(ctx: Context<UpdateMarketConfig>, limits: TradingLimits) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.total_volume == 0, DjinnError::TradingLimitsLocked);
+        limits.validate()?;
+        market.limits = limits;
+        Ok(())
+    }
+
+    /// Creator replaces the market's fee schedule; only before the first trade
+    pub fn set_fee_schedule(ctx: Context<UpdateMarketConfig>, schedule: FeeSchedule) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.total_volume == 0, DjinnError::FeeScheduleLocked);
+        schedule.validate()?;
+        market.fee_schedule = schedule;
+        Ok(())
+    }
+
+    /// Permissionless: once the parent settles, keep a conditional market live
+    /// (condition met) or void it so positions can claim refunds (condition failed)
+    pub fn settle_market_condition(ctx: Context<SettleMarketCondition>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        if market.settle_condition(&ctx.accounts.parent_market)? {
+            return Ok(());
+        }
+        settle_void(
+            market,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.market_creator,
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Keeper sets a Chronos market's trading limits (0 = off), before the first trade
+    pub fn set_chronos_trading_limits(ctx: Context<SetChronosTradingLimits>, limits: TradingLimits) -> Result<()> {
+        require!(ctx.accounts.chronos_market.total_volume == 0, DjinnError::TradingLimitsLocked);
+        limits.validate()?;
+        ctx.accounts.chronos_market.limits = limits;
+        Ok(())
+    }
+
+    /// Escrow a buy of `outcome_index` for `sol_in` lamports (fee included) into the
+    /// market's open batch. The first order opens the batch window.
+    pub fn submit_batch_order(
+        ctx: Context<SubmitBatchOrder>,
+        batch_id: u64,
+        outcome_index: u8,
+        sol_in: u64,
+    ) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(market.batch_window_slots > 0, DjinnError::BatchModeDisabled);
+        require!(batch_id == market.open_batch_id, DjinnError::InvalidBatch);
+        require!(outcome_index < market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(sol_in > 0, DjinnError::InvalidAmount);
+
+        // (Point 5) Check Expiry
+        let clock = Clock::get()?;
+        require!(clock.unix_timestamp < market.resolution_time, DjinnError::MarketExpired);
+
+        let batch = &mut ctx.accounts.batch;
+        let mut deposit = sol_in;
+        if batch.market == Pubkey::default() {
+            // First order opens the window and funds the escrow's rent
+            batch.market = market.key();
+            batch.batch_id = batch_id;
+            batch.opener = ctx.accounts.user.key();
+            batch.start_slot = clock.slot;
+            batch.end_slot = clock.slot.checked_add(market.batch_window_slots).ok_or(DjinnError::MathError)?;
+            batch.status = BatchStatus::Open;
+            batch.bump = ctx.bumps.batch;
+            batch.escrow_bump = ctx.bumps.batch_escrow;
+            deposit = deposit.checked_add(Rent::get()?.minimum_balance(0)).ok_or(DjinnError::MathError)?;
+        }
+        require!(batch.status == BatchStatus::Open, DjinnError::BatchNotOpen);
+        require!(clock.slot < batch.end_slot, DjinnError::BatchWindowClosed);
+
+        // Scheduled entry fee is held in escrow with the order until settlement
+        let fee = curve_result(fee_from_bps(sol_in as u128, market.entry_fee_bps(clock.unix_timestamp)))? as u64;
+        let net_sol = sol_in - fee;
+        let outcome = outcome_index as usize;
+        batch.total_net_sol[outcome] = batch.total_net_sol[outcome].checked_add(net_sol).ok_or(DjinnError::MathError)?;
+        batch.total_fee = batch.total_fee.checked_add(fee).ok_or(DjinnError::MathError)?;
+        batch.open_orders = batch.open_orders.checked_add(1).ok_or(DjinnError::MathError)?;
+
+        let order = &mut ctx.accounts.batch_order;
+        order.owner = ctx.accounts.user.key();
+        order.batch = batch.key();
+        order.outcome = outcome_index;
+        order.sol_in = sol_in;
+        order.net_sol = net_sol;
+        order.bump = ctx.bumps.batch_order;
+
+        // Creator-set trading limits, against what the order would buy alone right now
+        let position = &mut ctx.accounts.user_position;
+        position.market = market.key();
+        position.outcome = outcome_index;
+        let max_fill = market.shares_for_sol(outcome_index, net_sol as u128)?;
+        position.check_buy(&market.limits, sol_in as u128, max_fill, clock.unix_timestamp)?;
+
+        transfer_lamports(
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.batch_escrow,
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+            deposit,
+        )
+    }
+
+    /// Permissionless: clear a closed batch. Each outcome buys with the batch's summed
+    /// net SOL along the exact curve integral, so its orders share one average price.
+    /// Cancels the batch instead if the market stopped trading.
+    pub fn settle_batch(ctx: Context<SettleBatch>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let batch = &mut ctx.accounts.batch;
+        require!(batch.status == BatchStatus::Open, DjinnError::BatchNotOpen);
+
+        let clock = Clock::get()?;
+        require!(clock.slot >= batch.end_slot, DjinnError::BatchWindowOpen);
+
+        if market.open_batch_id == batch.batch_id {
+            market.open_batch_id = market.open_batch_id.checked_add(1).ok_or(DjinnError::MathError)?;
+        }
+
+        if market.status != MarketStatus::Active || clock.unix_timestamp >= market.resolution_time {
+            batch.status = BatchStatus::Cancelled;
+            return Ok(());
+        }
+
+        // 1. Clear every outcome at its uniform price
+        let total_net_sol = batch.clear(market, clock.unix_timestamp)?;
+
+        // 2. Escrow pays the vault and the fee split; its rent goes back to the opener
+        let batch_key = batch.key();
+        let seeds: &[&[u8]] = &[b"batch_escrow", batch_key.as_ref(), &[batch.escrow_bump]];
+        let escrow = &ctx.accounts.batch_escrow;
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        transfer_lamports(escrow, &ctx.accounts.market_vault, &system_program, &[seeds], total_net_sol)?;
+        route_trading_fee(
+            &market.quote_fee(&ctx.accounts.protocol_config, batch.total_fee as u128)?,
+            escrow,
+            &ctx.accounts.protocol_treasury,
+            &ctx.accounts.market_creator,
+            &ctx.accounts.insurance_vault,
+            &system_program,
+            &[seeds],
+        )?;
+        limit_order::drain_escrow(escrow, &ctx.accounts.opener, &system_program, seeds)
+    }
+
+    /// Permissionless: credit a settled order its pro-rata shares (the outcome's last
+    /// claim also takes the rounding remainder), or refund a cancelled order in full.
+    /// Closes the order to its owner. Returns the shares credited.
+    pub fn claim_batch_fill(ctx: Context<ClaimBatchFill>) -> Result<u64> {
+        let batch = &mut ctx.accounts.batch;
+        let order = &ctx.accounts.batch_order;
+
+        let mut shares = 0u128;
+        match batch.status {
+            BatchStatus::Open => return err!(DjinnError::BatchNotSettled),
+            BatchStatus::Settled => {
+                shares = batch.take_fill(order.outcome, order.net_sol)?;
+                // The market's total basis already counted this at settlement; the limits
+                // were checked at submission. Stamped at settlement, not whenever the
+                // (permissionless) claim happens to land.
+                ctx.accounts.user_position.credit_buy(shares, order.net_sol as u128, batch.settled_at)?;
+            }
+            BatchStatus::Cancelled => {
+                let batch_key = batch.key();
+                let seeds: &[&[u8]] = &[b"batch_escrow", batch_key.as_ref(), &[batch.escrow_bump]];
+                let system_program = ctx.accounts.system_program.to_account_info();
+                transfer_lamports(&ctx.accounts.batch_escrow, &ctx.accounts.owner, &system_program, &[seeds], order.sol_in)?;
+                if batch.open_orders == 1 {
+                    limit_order::drain_escrow(&ctx.accounts.batch_escrow, &ctx.accounts.opener, &system_program, seeds)?;
+                }
+            }
+        }
+        batch.open_orders = batch.open_orders.saturating_sub(1);
+        Ok(shares as u64)
+    }
+
+    /// Owner closes a DCA plan at any time; whatever is left in the escrow is refunded
+    pub fn close_dca_plan(ctx: Context<CloseDcaPlan>) -> Result<()> {
+        let plan_key = ctx.accounts.dca_plan.key();
+        let seeds: &[&[u8]] = &[b"dca_escrow", plan_key.as_ref(), &[ctx.accounts.dca_plan.escrow_bump]];
+        limit_order::drain_escrow(
+            &ctx.accounts.dca_escrow,
+            &ctx.accounts.owner.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            seeds,
+        )
+    }
+
+    /// Resolve market - Cerberus declares the winning outcome of a market under
+    /// verification, ruling on its BountyPool and finalizing it (bots are scored against
+    /// the ruling). Active markets need initialize_bounty_pool first.
+    pub fn resolve_market(
+        ctx: Context<ResolveMarket>,
+        winning_outcome: u8, // 0 = YES, 1 = NO
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::PendingVerification, DjinnError::VerificationRequired);
+        let pool = ctx.accounts.bounty_pool.as_mut().ok_or(DjinnError::VerificationRequired)?;
+
+        // (Point 4) Fixed Outcome Check
+        pool.rule(winning_outcome, market.num_outcomes)?;
+        pool.settle(Some(winning_outcome));
+
+        // Conditional markets: the parent must have resolved to the condition
+        market.check_condition(ctx.accounts.parent_market.as_deref())?;
+        
+        let accounts = ResolutionAccounts {
+            market_vault: ctx.accounts.market_vault.to_account_info(),
+            protocol_treasury: ctx.accounts.protocol_treasury.to_account_info(),
+            insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+            market_creator: ctx.accounts.market_creator.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        settle_resolution(market, &ctx.accounts.protocol_config, &accounts, winning_outcome)
+    }
+
+    /// Cerberus resolves a scalar market under verification at `resolved_value`,
+    /// finalizing its BountyPool; LONG and SHORT split the pot by where the value falls
+    /// between the bounds. Active markets need initialize_bounty_pool first.
+    pub fn resolve_scalar_market(ctx: Context<ResolveMarket>, resolved_value: i64) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        require!(market.status == MarketStatus::PendingVerification, DjinnError::VerificationRequired);
+        let pool = ctx.accounts.bounty_pool.as_mut().ok_or(DjinnError::VerificationRequired)?;
+        pool.rule_scalar(market)?;
+
+        market.check_condition(ctx.accounts.parent_market.as_deref())?;
+
+        let accounts = ResolutionAccounts {
+            market_vault: ctx.accounts.market_vault.to_account_info(),
+            protocol_treasury: ctx.accounts.protocol_treasury.to_account_info(),
+            insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+            market_creator: ctx.accounts.market_creator.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        settle_scalar_resolution(market, &ctx.accounts.protocol_config, &accounts, resolved_value)
+    }
+
+    /// Claim winnings after market resolution
+    pub fn claim_winnings(
+        ctx: Context<ClaimWinnings>,
+        outcome_index: u8,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.user_position;
+        
+        require!(market.status == MarketStatus::Resolved, DjinnError::MarketNotResolved);
+        require!(!position.claimed, DjinnError::AlreadyClaimed);
+        require!(position.open_sell_orders == 0, DjinnError::OpenSellOrders);
+        require!(position.shares > 0, DjinnError::NoShares);
+        
+        // Scalar: both sides hold a slice of the pot
+        let payout = if market.is_scalar() {
+            market.scalar_payout(outcome_index, position.shares)?
+        } else {
+            let winning_outcome = market.winning_outcome.unwrap();
+            require!(outcome_index == winning_outcome, DjinnError::NotWinner);
+            
+            // (Point 2) Use total_pot_at_resolution instead of shrinking vault_balance
+            // Calculate payout: user_shares / total_winning_shares * SNAPSHOT_BALANCE
+            let total_winning_shares = market.outcome_supplies[winning_outcome as usize];
+            
+            if total_winning_shares == 0 {
+                return Ok(());
+            }
+            
+            match market.pricing {
+                // LMSR: fixed 1 lamport per winning share unit
+                PricingModel::Lmsr { .. } => lmsr_payout(position.shares, market.vault_balance),
+                PricingModel::Curve => {
+                    let snapshot_pot = market.total_pot_at_resolution as u128;
+                    curve_result(pro_rata_payout(snapshot_pot, position.shares, total_winning_shares))?
+                }
+            }
+        };
+        
+        // Transfer payout
+        let market_key = market.key();
+        let seeds = &[
+            b"market_vault",
+            market_key.as_ref(),
+            &[market.vault_bump],
+        ];
+        let signer = &[&seeds[..]];
+        
+        if payout > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.market_vault.to_account_info(),
+                        to: ctx.accounts.user.to_account_info(),
+                    },
+                    signer,
+                ),
+                payout as u64,
+            )?;
+            
+            // We still decrease vault balance to track remaining funds, but it doesn't affect payout calculation
+            market.vault_balance = market.vault_balance.checked_sub(payout).ok_or(DjinnError::MathError)?;
+        }
+        
+        position.claimed = true;
+        
+        Ok(())
+    }
+
+    /// Void a cancelled or ambiguous market instead of resolving it. No resolution fee;
+    /// positions reclaim their cost-basis share of the pot through claim_refund.
+    pub fn void_market(ctx: Context<VoidMarket>) -> Result<()> {
+        settle_void(
+            &mut ctx.accounts.market,
+            &ctx.accounts.market_vault,
+            &ctx.accounts.market_creator,
+            &ctx.accounts.system_program.to_account_info(),
+        )
+    }
+
+    /// Reclaim a position's share of a voided market's pot, weighted by cost basis
+    pub fn claim_refund(ctx: Context<ClaimRefund>) -> Result<()> {
+        let market = &mut ctx.accounts.market;
+        let position = &mut ctx.accounts.user_position;
+        require!(!position.claimed, DjinnError::AlreadyClaimed);
+        require!(position.open_sell_orders == 0, DjinnError::OpenSellOrders);
+
+        let refund = market.take_refund(position.cost_basis)?;
+        position.claimed = true;
+
+        let market_key = market.key();
+        let seeds = &[
+            b"market_vault",
+            market_key.as_ref(),
+            &[market.vault_bump],
+        ];
+        transfer_lamports(
+            &ctx.accounts.market_vault,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[&seeds[..]],
+            refund as u64,
+        )
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // OPTIMISTIC RESOLUTION
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Anyone proposes the winning outcome of an expired market, posting `bond`
+    /// lamports. Opens the protocol's challenge window.
+    pub fn propose_resolution(ctx: Context<ProposeResolution>, proposed_outcome: u8, bond: u64) -> Result<()> {
+        let market = &ctx.accounts.market;
+        require!(market.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(!market.is_scalar(), DjinnError::ScalarMarket);
+        require!(proposed_outcome < market.num_outcomes, DjinnError::InvalidOutcome);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= market.resolution_time, DjinnError::MarketNotExpired);
+
+        let params = &ctx.accounts.protocol_config.params;
+        require!(bond >= params.min_resolution_bond, DjinnError::ResolutionBondTooLow);
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.market = market.key();
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.proposed_outcome = proposed_outcome;
+        proposal.bond = bond;
+        proposal.proposed_at = now;
+        proposal.challenge_deadline = now.checked_add(params.challenge_period_secs).ok_or(DjinnError::MathError)?;
+        proposal.status = ProposalStatus::Proposed;
+        proposal.challenger = Pubkey::default();
+        proposal.bump = ctx.bumps.proposal;
+        proposal.bond_bump = ctx.bumps.bond_escrow;
+
+        // The escrow holds its own rent on top of the bond
+        let deposit = Rent::get()?.minimum_balance(0)
+            .checked_add(bond)
+            .ok_or(DjinnError::MathError)?;
+        transfer_lamports(
+            &ctx.accounts.proposer.to_account_info(),
+            &ctx.accounts.bond_escrow,
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+            deposit,
+        )
+    }
+
+    /// Dispute a proposal inside its window by matching its bond. Escalates the market
+    /// to the oracle network: opens its BountyPool (BountyPool verification / Cerberus)
+    /// unless one is already open.
+    pub fn challenge_resolution(ctx: Context<ChallengeResolution>) -> Result<()> {
+        require!(ctx.accounts.market.awaiting_resolution(), DjinnError::MarketNotActive);
+
+        let proposal = &mut ctx.accounts.proposal;
+        require!(proposal.status == ProposalStatus::Proposed, DjinnError::ProposalAlreadyChallenged);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < proposal.challenge_deadline, DjinnError::ChallengeWindowClosed);
+        require_keys_neq!(ctx.accounts.challenger.key(), proposal.proposer, DjinnError::SelfChallenge);
+
+        proposal.status = ProposalStatus::Challenged;
+        proposal.challenger = ctx.accounts.challenger.key();
+
+        let system_program = ctx.accounts.system_program.to_account_info();
+        transfer_lamports(
+            &ctx.accounts.challenger.to_account_info(),
+            &ctx.accounts.bond_escrow,
+            &system_program,
+            &[],
+            proposal.bond,
+        )?;
+
+        // Escalate: the pool's vote decides, unless it is already running
+        if ctx.accounts.market.status == MarketStatus::Active {
+            let accounts = BountyAccounts {
+                bounty_vault: ctx.accounts.bounty_vault.to_account_info(),
+                insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+                insurance_bump: ctx.bumps.insurance_vault,
+                system_program,
+            };
+            open_bounty_pool(
+                &mut ctx.accounts.market,
+                Some(&ctx.accounts.proposal),
+                &mut ctx.accounts.bounty_pool,
+                ctx.bumps.bounty_pool,
+                &ctx.accounts.protocol_config,
+                &accounts,
+                now,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Permissionless: settle both bonds against the market's outcome, resolving an
+    /// Active market from an unchallenged proposal once its window closes. While the
+    /// market is PendingVerification the proposal waits for finalize_bounty_pool to
+    /// resolve it. Closes the proposal to the proposer.
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let config = &ctx.accounts.protocol_config;
+        let market = &mut ctx.accounts.market;
+        let challenged = proposal.status == ProposalStatus::Challenged;
+
+        // 1. The outcome the bonds settle against; resolve the market if nobody else did
+        let outcome = match market.status {
+            MarketStatus::Active => {
+                require!(!challenged, DjinnError::EscalationPending);
+                let now = Clock::get()?.unix_timestamp;
+                require!(now >= proposal.challenge_deadline, DjinnError::ChallengeWindowOpen);
+                let outcome = proposal.proposed_outcome;
+                let accounts = ResolutionAccounts {
+                    market_vault: ctx.accounts.market_vault.to_account_info(),
+                    protocol_treasury: ctx.accounts.protocol_treasury.to_account_info(),
+                    insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+                    market_creator: ctx.accounts.market_creator.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                };
+                settle_resolution(market, config, &accounts, outcome)?;
+                Some(outcome)
+            }
+            MarketStatus::PendingVerification => return err!(DjinnError::EscalationPending),
+            MarketStatus::Resolved => market.winning_outcome,
+            MarketStatus::Voided => None,
+        };
+
+        // 2. Bonds: refunded unless disputed, otherwise the loser's goes to the winner
+        //    and insurance
+        let (proposer_out, challenger_out, insurance_out) = proposal.bond_payouts(outcome, config)?;
+
+        let proposal_key = proposal.key();
+        let seeds: &[&[u8]] = &[b"resolution_bond", proposal_key.as_ref(), &[proposal.bond_bump]];
+        let escrow = &ctx.accounts.bond_escrow;
+        let proposer = &ctx.accounts.proposer;
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        if challenged {
+            let challenger = ctx.accounts.challenger.as_ref().ok_or(DjinnError::InvalidChallenger)?;
+            require_keys_eq!(challenger.key(), proposal.challenger, DjinnError::InvalidChallenger);
+            transfer_lamports(escrow, &challenger.to_account_info(), &system_program, &[seeds], challenger_out as u64)?;
+        }
+        route_fee(
+            escrow,
+            &system_program,
+            &[seeds],
+            &[(proposer, proposer_out), (&ctx.accounts.insurance_vault, insurance_out)],
+        )?;
+
+        // The escrow's rent goes back to the proposer who funded it
+        limit_order::drain_escrow(escrow, proposer, &system_program, seeds)
+    }
+
+    // ═══════════════════════════════════════════════════════════════════════════
+    // CHRONOS MARKET INSTRUCTIONS (Automated Crypto Majors)
+    // ═══════════════════════════════════════════════════════════════════════════
+
+    /// Initialize a new Chronos market (called by keeper bot)
     pub fn initialize_chronos_market(
         ctx: Context<InitializeChronosMarket>,
         asset: u8,           // 0=BTC, 1=ETH, 2=SOL
         interval: u8,        // 0=15min, 1=1hour
         round_number: u64,
         target_price: u64,   // Strike price in USD cents
+        curve: CurveConfig,
     ) -> Result<()> {
         use chronos_market::*;
         
+        curve.validate()?;
         let market = &mut ctx.accounts.chronos_market;
         let clock = Clock::get()?;
         
@@ -706,8 +1694,11 @@ pub mod djinn_market {
         market.outcome_supplies = [0; 2];
         market.vault_balance = 0;
         market.total_pot_at_resolution = 0;
+        market.total_volume = 0;
         market.bump = ctx.bumps.chronos_market;
         market.keeper = ctx.accounts.keeper.key();
+        market.curve = curve;
+        market.limits = TradingLimits::default();
         
         // Calculate vault bump
         let (_, vault_bump) = Pubkey::find_program_address(
@@ -737,27 +1728,35 @@ pub mod djinn_market {
         
         let sol_in_u128 = sol_in as u128;
         
-        // Calculate entry fee (1%)
-        let fee = (sol_in_u128 * ENTRY_FEE_BPS) / BPS_DENOMINATOR;
+        // Calculate entry fee (ProtocolConfig entry bps)
+        let entry_bps = ctx.accounts.protocol_config.params.entry_fee_bps as u128;
+        let fee = curve_result(fee_from_bps(sol_in_u128, entry_bps))?;
         let net_sol = sol_in_u128 - fee;
         
         // Get current supply for outcome
         let current_supply = market.outcome_supplies[outcome_index as usize];
         
-        // Calculate shares using existing bonding curve
-        let shares = calculate_shares_from_sol(net_sol, current_supply)?;
+        // Calculate shares using the market's bonding curve
+        let shares = market.curve.shares_for(net_sol, current_supply)?;
         require!(shares >= min_shares_out as u128, ChronosError::SlippageExceeded);
+
+        // Creator (keeper) set trading limits
+        let position = &ctx.accounts.user_position;
+        let shares_after = position.shares.checked_add(shares).ok_or(DjinnError::MathError)?;
+        market.limits.check_buy(sol_in_u128, shares_after, position.last_trade_ts, clock.unix_timestamp)?;
         
         // Update market state
-        market.outcome_supplies[outcome_index as usize] = current_supply.checked_add(shares).unwrap();
-        market.vault_balance = market.vault_balance.checked_add(net_sol).unwrap();
+        market.outcome_supplies[outcome_index as usize] = current_supply.checked_add(shares).ok_or(DjinnError::MathError)?;
+        market.vault_balance = market.vault_balance.checked_add(net_sol).ok_or(DjinnError::MathError)?;
+        market.total_volume = market.total_volume.checked_add(sol_in_u128).ok_or(DjinnError::MathError)?;
         
         // Update user position
         let position = &mut ctx.accounts.user_position;
         position.owner = ctx.accounts.user.key();
         position.market = market.key();
         position.outcome = outcome_index;
-        position.shares = position.shares.checked_add(shares).unwrap();
+        position.shares = position.shares.checked_add(shares).ok_or(DjinnError::MathError)?;
+        position.last_trade_ts = clock.unix_timestamp;
         
         // Transfer SOL to vault
         anchor_lang::system_program::transfer(
@@ -772,20 +1771,12 @@ pub mod djinn_market {
         )?;
         
         // Transfer fee to treasury
-        if fee > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.user.to_account_info(),
-                        to: ctx.accounts.protocol_treasury.to_account_info(),
-                    },
-                ),
-                fee as u64,
-            )?;
-        }
-        
-        Ok(())
+        route_fee(
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &[],
+            &[(&ctx.accounts.protocol_treasury, fee)],
+        )
     }
 
     /// Resolve a Chronos market using Pyth price
@@ -820,8 +1811,9 @@ pub mod djinn_market {
         
         let winning_outcome = if final_price_val >= market.target_price { 0 } else { 1 };
         
-        // Extract resolution fee (2%)
-        let resolution_fee = (market.vault_balance as u128 * RESOLUTION_FEE_BPS as u128) / BPS_DENOMINATOR as u128;
+        // Extract resolution fee (ProtocolConfig bps of the pot)
+        let fee_bps = ctx.accounts.protocol_config.params.resolution_fee_bps as u128;
+        let resolution_fee = curve_result(fee_from_bps(market.vault_balance, fee_bps))?;
         
         // PDA Signer
         let market_key = market.key();
@@ -833,19 +1825,14 @@ pub mod djinn_market {
         let signer = &[&seeds[..]];
 
         if resolution_fee > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.chronos_vault.to_account_info(),
-                        to: ctx.accounts.protocol_treasury.to_account_info(),
-                    },
-                    signer,
-                ),
-                resolution_fee as u64,
+            route_fee(
+                &ctx.accounts.chronos_vault,
+                &ctx.accounts.system_program.to_account_info(),
+                signer,
+                &[(&ctx.accounts.protocol_treasury, resolution_fee)],
             )?;
             
-            market.vault_balance = market.vault_balance.checked_sub(resolution_fee).unwrap();
+            market.vault_balance = market.vault_balance.checked_sub(resolution_fee).ok_or(DjinnError::MathError)?;
         }
         
         // HOUSE WIN CHECK: If no shares exist for the winning outcome, TREASURY takes all
@@ -905,7 +1892,7 @@ pub mod djinn_market {
         }
         
         let snapshot_pot = market.total_pot_at_resolution as u128;
-        let payout = (snapshot_pot * position.shares) / total_winning_shares;
+        let payout = curve_result(pro_rata_payout(snapshot_pot, position.shares, total_winning_shares))?;
         
         // Transfer payout
         if payout > 0 {
@@ -930,7 +1917,7 @@ pub mod djinn_market {
             )?;
             
             // Decrease vault balance tracking
-            market.vault_balance = market.vault_balance.checked_sub(payout).unwrap();
+            market.vault_balance = market.vault_balance.checked_sub(payout).ok_or(DjinnError::MathError)?;
         }
         
         position.claimed = true;
@@ -1057,51 +2044,33 @@ pub mod djinn_market {
     // AI BOT INTEGRATION (Module 2: Oracle Network — Verification & Disputes)
     // ═══════════════════════════════════════════════════════════════════════════
 
-    /// Initialize a Bounty Pool for a market (called at resolve)
-    pub fn initialize_bounty_pool(
-        ctx: Context<InitializeBountyPool>,
-        bounty_amount: u64,
-    ) -> Result<()> {
-        let pool = &mut ctx.accounts.bounty_pool;
-        let clock = Clock::get()?;
-
-        pool.market = ctx.accounts.market.key();
-        pool.total_bounty = bounty_amount;
-        pool.total_weight = 0;
-        pool.winning_outcome = None;
-        pool.cerberus_override = false;
-        pool.cerberus_outcome = None;
-        pool.num_submissions = 0;
-        pool.is_finalized = false;
-        pool.created_at = clock.unix_timestamp;
-        pool.expires_at = clock.unix_timestamp + 172800; // 48 hours
-        pool.bump = ctx.bumps.bounty_pool;
-
-        // Transfer bounty from market vault to bounty pool vault
-        let market = &ctx.accounts.market;
-        let market_key = market.key();
-        let seeds = &[
-            b"market_vault",
-            market_key.as_ref(),
-            &[market.vault_bump],
-        ];
-        let signer = &[&seeds[..]];
-
-        if bounty_amount > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.market_vault.to_account_info(),
-                        to: ctx.accounts.bounty_vault.to_account_info(),
-                    },
-                    signer,
-                ),
-                bounty_amount,
-            )?;
-        }
-
-        Ok(())
+    /// Permissionless: open a market's Bounty Pool once it reaches resolution_time,
+    /// funded by the insurance vault with ProtocolConfig.verification_bounty. The market
+    /// moves to PendingVerification and is resolved by the bots' vote (see verification).
+    pub fn initialize_bounty_pool(ctx: Context<InitializeBountyPool>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        // A live optimistic proposal escalates only through challenge_resolution
+        let info = ctx.accounts.proposal.to_account_info();
+        let proposal = if info.owner == &crate::ID {
+            Some(ResolutionProposal::try_deserialize(&mut &info.try_borrow_data()?[..])?)
+        } else {
+            None
+        };
+        let accounts = BountyAccounts {
+            bounty_vault: ctx.accounts.bounty_vault.to_account_info(),
+            insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+            insurance_bump: ctx.bumps.insurance_vault,
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        open_bounty_pool(
+            &mut ctx.accounts.market,
+            proposal.as_ref(),
+            &mut ctx.accounts.bounty_pool,
+            ctx.bumps.bounty_pool,
+            &ctx.accounts.protocol_config,
+            &accounts,
+            now,
+        )
     }
 
     /// Bot submits a verification with confidence level
@@ -1123,6 +2092,8 @@ pub mod djinn_market {
         require!(evidence_uri.len() <= 200, DjinnError::UriTooLong);
         require!(!bot.is_frozen, DjinnError::BotFrozen);
         require!(bot.is_active, DjinnError::BotPaused);
+        require!(proposed_outcome < ctx.accounts.market.num_outcomes, DjinnError::InvalidOutcome);
+        require!(!ctx.accounts.market.is_scalar(), DjinnError::ScalarMarket);
 
         // Calculate skin-weighted vote weight: stake × (community_score / 100)
         // community_score = upvotes / (upvotes + downvotes + 1) * 500 (scale 0-500)
@@ -1150,6 +2121,8 @@ pub mod djinn_market {
 
         // Update pool
         pool.total_weight = pool.total_weight.checked_add(weight).unwrap();
+        let tally = &mut pool.outcome_weights[proposed_outcome as usize];
+        *tally = tally.checked_add(weight).unwrap();
         pool.num_submissions = pool.num_submissions.checked_add(1).unwrap();
 
         // Update bot stats
@@ -1163,40 +2136,44 @@ pub mod djinn_market {
         ctx: Context<CerberusOverride>,
         final_outcome: u8,
     ) -> Result<()> {
-        let pool = &mut ctx.accounts.bounty_pool;
-        require!(!pool.is_finalized, DjinnError::BountyAlreadyFinalized);
-
-        pool.cerberus_override = true;
-        pool.cerberus_outcome = Some(final_outcome);
-
-        Ok(())
+        let market = &ctx.accounts.market;
+        require!(!market.is_scalar(), DjinnError::ScalarMarket);
+        ctx.accounts.bounty_pool.rule(final_outcome, market.num_outcomes)
     }
 
-    /// Finalize a bounty pool — determine the winning outcome and mark correct submissions
+    /// Finalize a bounty pool — determine the winning outcome and resolve the market
+    /// with it, or void the market if the vote never reached quorum
     pub fn finalize_bounty_pool(
         ctx: Context<FinalizeBountyPool>,
     ) -> Result<()> {
         let pool = &mut ctx.accounts.bounty_pool;
-        let clock = Clock::get()?;
-
-        require!(!pool.is_finalized, DjinnError::BountyAlreadyFinalized);
-        // Can finalize after 24h or after cerberus override
-        require!(
-            clock.unix_timestamp >= pool.created_at + 86400 || pool.cerberus_override,
-            DjinnError::BountyNotReady
-        );
-
-        // If Cerberus overrode, use that. Otherwise use the market winning_outcome.
-        let winning = if pool.cerberus_override {
-            pool.cerberus_outcome.unwrap()
-        } else {
-            let market = &ctx.accounts.market;
-            market.winning_outcome.unwrap()
-        };
-
-        pool.winning_outcome = Some(winning);
-        pool.is_finalized = true;
+        let market = &mut ctx.accounts.market;
+        let now = Clock::get()?.unix_timestamp;
+        let winning = pool.verdict(market, &ctx.accounts.protocol_config.params, now)?;
+
+        if market.awaiting_resolution() {
+            let system_program = ctx.accounts.system_program.to_account_info();
+            match winning {
+                Some(outcome) => {
+                    let accounts = ResolutionAccounts {
+                        market_vault: ctx.accounts.market_vault.to_account_info(),
+                        protocol_treasury: ctx.accounts.protocol_treasury.to_account_info(),
+                        insurance_vault: ctx.accounts.insurance_vault.to_account_info(),
+                        market_creator: ctx.accounts.market_creator.to_account_info(),
+                        system_program,
+                    };
+                    settle_resolution(market, &ctx.accounts.protocol_config, &accounts, outcome)?;
+                }
+                None => settle_void(
+                    market,
+                    &ctx.accounts.market_vault,
+                    &ctx.accounts.market_creator,
+                    &system_program,
+                )?,
+            }
+        }
 
+        pool.settle(winning);
         Ok(())
     }
 
@@ -1211,7 +2188,11 @@ pub mod djinn_market {
         require!(pool.is_finalized, DjinnError::BountyNotFinalized);
         require!(!submission.claimed, DjinnError::AlreadyClaimed);
 
-        let winning_outcome = pool.winning_outcome.unwrap();
+        // Voided for low participation: nobody is scored
+        let Some(winning_outcome) = pool.winning_outcome else {
+            submission.claimed = true;
+            return Ok(());
+        };
 
         if submission.proposed_outcome == winning_outcome {
             // CORRECT — gets proportional bounty based on weight
@@ -1231,7 +2212,7 @@ pub mod djinn_market {
                 let seeds = &[
                     b"bounty_vault",
                     pool_key.as_ref(),
-                    &[pool.bump],
+                    &[ctx.bumps.bounty_vault],
                 ];
                 let signer = &[&seeds[..]];
 
@@ -1267,43 +2248,38 @@ pub mod djinn_market {
         Ok(())
     }
 
-    /// Expire unclaimed bounty — after 48h, remaining funds go to treasury
+    /// Expire unclaimed bounty — after 48h, what the bots left goes back: into the refund
+    /// pot on a voided market, otherwise to the insurance vault that funded it
     pub fn expire_bounty(
         ctx: Context<ExpireBounty>,
     ) -> Result<()> {
         let pool = &ctx.accounts.bounty_pool;
         let clock = Clock::get()?;
 
+        require!(pool.is_finalized, DjinnError::BountyNotFinalized);
         require!(clock.unix_timestamp >= pool.expires_at, DjinnError::BountyNotExpired);
 
-        // Transfer remaining bounty vault balance to treasury
         let vault_balance = ctx.accounts.bounty_vault.lamports();
         let rent = Rent::get()?.minimum_balance(0);
         let transferable = vault_balance.saturating_sub(rent);
 
-        if transferable > 0 {
-            let pool_key = pool.key();
-            let seeds = &[
-                b"bounty_vault",
-                pool_key.as_ref(),
-                &[pool.bump],
-            ];
-            let signer = &[&seeds[..]];
-
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.bounty_vault.to_account_info(),
-                        to: ctx.accounts.protocol_treasury.to_account_info(),
-                    },
-                    signer,
-                ),
-                transferable,
-            )?;
-        }
+        let market = &mut ctx.accounts.market;
+        let to = if market.status == MarketStatus::Voided {
+            market.add_to_refund_pot(transferable)?;
+            &ctx.accounts.market_vault
+        } else {
+            &ctx.accounts.insurance_vault
+        };
 
-        Ok(())
+        let pool_key = pool.key();
+        let seeds: &[&[u8]] = &[b"bounty_vault", pool_key.as_ref(), &[ctx.bumps.bounty_vault]];
+        transfer_lamports(
+            &ctx.accounts.bounty_vault,
+            to,
+            &ctx.accounts.system_program.to_account_info(),
+            &[seeds],
+            transferable,
+        )
     }
 
     /// Propose slashing a bot (Reporter initiates)
@@ -1370,46 +2346,26 @@ pub mod djinn_market {
                 proposal.status = SlashStatus::Guilty;
                 let slash_amount = bot.stake; // Full stake slashed
 
-                // Split: 80% to insurance, 20% to reporter
-                let insurance_share = (slash_amount * 80) / 100;
-                let reporter_share = slash_amount - insurance_share;
+                // Split per ProtocolConfig: insurance, reporter keeps the rest
+                let (insurance_share, reporter_share) =
+                    ctx.accounts.protocol_config.split_slash(slash_amount as u128)?;
 
-                // Transfer from bot escrow to insurance vault
+                // Transfer from bot escrow to insurance vault and reporter
                 let owner_key = bot.owner;
                 let seeds = &[
                     b"bot_escrow",
                     owner_key.as_ref(),
                     &[ctx.bumps.bot_escrow],
                 ];
-                let signer = &[&seeds[..]];
-
-                if insurance_share > 0 {
-                    anchor_lang::system_program::transfer(
-                        CpiContext::new_with_signer(
-                            ctx.accounts.system_program.to_account_info(),
-                            anchor_lang::system_program::Transfer {
-                                from: ctx.accounts.bot_escrow.to_account_info(),
-                                to: ctx.accounts.insurance_vault.to_account_info(),
-                            },
-                            signer,
-                        ),
-                        insurance_share,
-                    )?;
-                }
-
-                if reporter_share > 0 {
-                    anchor_lang::system_program::transfer(
-                        CpiContext::new_with_signer(
-                            ctx.accounts.system_program.to_account_info(),
-                            anchor_lang::system_program::Transfer {
-                                from: ctx.accounts.bot_escrow.to_account_info(),
-                                to: ctx.accounts.reporter.to_account_info(),
-                            },
-                            signer,
-                        ),
-                        reporter_share,
-                    )?;
-                }
+                route_fee(
+                    &ctx.accounts.bot_escrow,
+                    &ctx.accounts.system_program.to_account_info(),
+                    &[&seeds[..]],
+                    &[
+                        (&ctx.accounts.insurance_vault, insurance_share),
+                        (&ctx.accounts.reporter, reporter_share),
+                    ],
+                )?;
 
                 bot.stake = 0;
                 bot.slashing_incidents = bot.slashing_incidents.checked_add(1).unwrap();
@@ -1598,10 +2554,9 @@ pub mod djinn_market {
         require!(!vault.is_liquidating, DjinnError::VaultLiquidating);
         require!(profit_amount > 0, DjinnError::MathError);
 
-        // Split: 70% depositors pool, 20% bot owner, 10% insurance
-        let depositor_share = (profit_amount as u128 * 70) / 100;
-        let bot_share = (profit_amount as u128 * 20) / 100;
-        let insurance_share = profit_amount as u128 - depositor_share - bot_share;
+        // Split per ProtocolConfig: depositors pool, bot owner, insurance keeps the rest
+        let (depositor_share, bot_share, insurance_share) =
+            ctx.accounts.protocol_config.split_vault_profit(profit_amount as u128)?;
 
         let vault_key = vault.key();
         let seeds = &[
@@ -1609,39 +2564,19 @@ pub mod djinn_market {
             vault_key.as_ref(),
             &[ctx.bumps.vault_sol],
         ];
-        let signer = &[&seeds[..]];
-
-        // 20% to bot owner
-        if bot_share > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.vault_sol.to_account_info(),
-                        to: ctx.accounts.bot_owner.to_account_info(),
-                    },
-                    signer,
-                ),
-                bot_share as u64,
-            )?;
-        }
 
-        // 10% to insurance
-        if insurance_share > 0 {
-            anchor_lang::system_program::transfer(
-                CpiContext::new_with_signer(
-                    ctx.accounts.system_program.to_account_info(),
-                    anchor_lang::system_program::Transfer {
-                        from: ctx.accounts.vault_sol.to_account_info(),
-                        to: ctx.accounts.insurance_vault.to_account_info(),
-                    },
-                    signer,
-                ),
-                insurance_share as u64,
-            )?;
-        }
+        // Bot owner and insurance shares leave the vault
+        route_fee(
+            &ctx.accounts.vault_sol,
+            &ctx.accounts.system_program.to_account_info(),
+            &[&seeds[..]],
+            &[
+                (&ctx.accounts.bot_owner, bot_share),
+                (&ctx.accounts.insurance_vault, insurance_share),
+            ],
+        )?;
 
-        // 70% stays in vault as depositor share (increases AUM pro-rata)
+        // Depositor share stays in vault (increases AUM pro-rata)
         vault.total_aum = vault.total_aum.checked_add(depositor_share).unwrap();
         vault.total_profit = vault.total_profit.checked_add(profit_amount as u128).unwrap();
 
@@ -1724,136 +2659,1480 @@ pub mod djinn_market {
             )?;
         }
 
-        deposit.amount = deposit.amount.saturating_sub(actual_payout as u128);
-        vault.total_aum = vault.total_aum.saturating_sub(actual_payout as u128);
+        deposit.amount = deposit.amount.saturating_sub(actual_payout as u128);
+        vault.total_aum = vault.total_aum.saturating_sub(actual_payout as u128);
+
+        if deposit.amount == 0 {
+            vault.num_depositors = vault.num_depositors.saturating_sub(1);
+        }
+
+        // Record loss
+        vault.total_loss = vault.total_loss.checked_add(
+            (deposit.amount as u128).saturating_sub(actual_payout as u128)
+        ).unwrap_or(vault.total_loss);
+
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRADE SETTLEMENT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Apply a priced buy: supply / vault / position accounting, net SOL into the vault,
+/// then the fee split out of the buyer
+fn settle_buy(accounts: &mut BuyShares, outcome_index: u8, quote: &TradeQuote, sol_in: u128, now: i64) -> Result<()> {
+    let market = &mut accounts.market;
+
+    // 4. Update state
+    market.apply_buy(outcome_index, quote)?;
+    market.add_cost_basis(quote.net_sol)?;
+    
+    // 5. Update user position (creator-set trading limits)
+    let position = &mut accounts.user_position;
+    position.market = market.key();
+    position.outcome = outcome_index;
+    position.record_buy(&market.limits, sol_in, quote.shares, quote.net_sol, now)?;
+    
+    // 6. Transfer SOL
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: accounts.user.to_account_info(),
+                to: accounts.market_vault.to_account_info(),
+            },
+        ),
+        quote.net_sol as u64,
+    )?;
+    
+    // 7. Transfer fee (split per ProtocolConfig, referrer's cut out of the treasury share)
+    // Note: bot tier rate limits would need the Bot PDA here, which buy_shares doesn't take.
+    let user = accounts.user.key();
+    let (quote, referral) = referral::take_referral(
+        &mut accounts.referrer,
+        &accounts.referral_vault,
+        &accounts.protocol_config,
+        &user,
+        quote,
+    )?;
+    let user = accounts.user.to_account_info();
+    let system_program = accounts.system_program.to_account_info();
+    route_trading_fee(
+        &quote,
+        &user,
+        &accounts.protocol_treasury,
+        &accounts.market_creator,
+        &accounts.insurance_vault,
+        &system_program,
+        &[],
+    )?;
+    if let Some((referral_vault, cut)) = referral {
+        transfer_lamports(&user, &referral_vault, &system_program, &[], cut)?;
+    }
+    Ok(())
+}
+
+/// Apply a priced sell: supply / vault / position accounting, net SOL out of the vault
+/// to the seller, then the fee split out of the vault
+fn settle_sell(accounts: &mut SellShares, outcome_index: u8, quote: &TradeQuote) -> Result<()> {
+    let market = &mut accounts.market;
+    let position = &mut accounts.user_position;
+    let net_refund = quote.net_sol;
+
+    // 4. Update state
+    market.apply_sell(outcome_index, quote)?;
+    let basis = position.debit(quote.shares)?;
+    market.remove_cost_basis(basis)?;
+    
+    // 5. Transfer SOL to User
+    let market_key = market.key();
+    let seeds = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[market.vault_bump],
+    ];
+    let signer = &[&seeds[..]];
+    
+    if net_refund > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: accounts.market_vault.to_account_info(),
+                    to: accounts.user.to_account_info(),
+                },
+                signer,
+            ),
+            net_refund as u64,
+        )?;
+    }
+
+    // 6. Transfer Fee (split per ProtocolConfig, referrer's cut out of the treasury share)
+    let user = accounts.user.key();
+    let (quote, referral) = referral::take_referral(
+        &mut accounts.referrer,
+        &accounts.referral_vault,
+        &accounts.protocol_config,
+        &user,
+        quote,
+    )?;
+    let system_program = accounts.system_program.to_account_info();
+    route_trading_fee(
+        &quote,
+        &accounts.market_vault,
+        &accounts.protocol_treasury,
+        &accounts.market_creator,
+        &accounts.insurance_vault,
+        &system_program,
+        signer,
+    )?;
+    if let Some((referral_vault, cut)) = referral {
+        transfer_lamports(&accounts.market_vault, &referral_vault, &system_program, signer, cut)?;
+    }
+    Ok(())
+}
+
+/// Route a quoted trading fee out of `from`: treasury, creator (skipped on G1 markets)
+/// and insurance. `signer` holds the vault seeds when the fee leaves the market vault.
+pub(crate) fn route_trading_fee<'info>(
+    quote: &TradeQuote,
+    from: &AccountInfo<'info>,
+    protocol_treasury: &AccountInfo<'info>,
+    market_creator: &AccountInfo<'info>,
+    insurance_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+) -> Result<()> {
+    route_fee(
+        from,
+        system_program,
+        signer,
+        &[
+            (protocol_treasury, quote.treasury_fee),
+            (market_creator, quote.creator_fee),
+            (insurance_vault, quote.insurance_fee),
+        ],
+    )
+}
+
+/// Pay out every (recipient, lamports) leg of a fee split from `from`. The one path
+/// every fee, profit share and slash leaves through.
+pub(crate) fn route_fee<'info>(
+    from: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    legs: &[(&AccountInfo<'info>, u128)],
+) -> Result<()> {
+    for &(to, amount) in legs {
+        let amount = u64::try_from(amount).map_err(|_| DjinnError::MathError)?;
+        transfer_lamports(from, to, system_program, signer, amount)?;
+    }
+    Ok(())
+}
+
+/// System transfer of `amount` lamports (no-op for 0). `signer` holds the PDA seeds when
+/// `from` is one of the program's system-owned vaults or escrows.
+pub(crate) fn transfer_lamports<'info>(
+    from: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    signer: &[&[&[u8]]],
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: from.clone(),
+                to: to.clone(),
+            },
+            signer,
+        ),
+        amount,
+    )
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MARKET RESOLUTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Accounts a resolution pays out of the market vault to
+pub(crate) struct ResolutionAccounts<'info> {
+    pub market_vault: AccountInfo<'info>,
+    pub protocol_treasury: AccountInfo<'info>,
+    pub insurance_vault: AccountInfo<'info>,
+    pub market_creator: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Declare `winning_outcome` on an unresolved market: take the resolution fee, hand an
+/// LMSR creator the rest of its subsidy and snapshot the pot for claims. Every path that
+/// resolves a categorical market (admin, optimistic proposal, oracle vote) ends here.
+pub(crate) fn settle_resolution(
+    market: &mut Account<Market>,
+    config: &ProtocolConfig,
+    accounts: &ResolutionAccounts,
+    winning_outcome: u8,
+) -> Result<()> {
+    require!(market.awaiting_resolution(), DjinnError::MarketNotActive);
+    require!(!market.is_scalar(), DjinnError::ScalarMarket);
+    require!(winning_outcome < market.num_outcomes, DjinnError::InvalidOutcome);
+
+    // Extract resolution fee (ProtocolConfig bps of the pot)
+    let fee_bps = config.params.resolution_fee_bps as u128;
+    let (resolution_fee, lmsr_refund) = match market.pricing {
+        // LMSR: winners are owed exactly 1 lamport per share, so the fee may only
+        // come out of the surplus above that liability
+        PricingModel::Lmsr { .. } => {
+            let liability = market.outcome_supplies[winning_outcome as usize];
+            let settlement = curve_result(lmsr_settlement_at(market.vault_balance, liability, fee_bps))?;
+            (settlement.fee, settlement.creator_refund)
+        }
+        PricingModel::Curve => (curve_result(fee_from_bps(market.vault_balance, fee_bps))?, 0),
+    };
+
+    pay_out_resolution(market, config, accounts, resolution_fee, lmsr_refund)?;
+    market.winning_outcome = Some(winning_outcome);
+    Ok(())
+}
+
+/// Declare a scalar market's `resolved_value`: take the resolution fee and snapshot the
+/// pot that LONG and SHORT split at claim time
+pub(crate) fn settle_scalar_resolution(
+    market: &mut Account<Market>,
+    config: &ProtocolConfig,
+    accounts: &ResolutionAccounts,
+    resolved_value: i64,
+) -> Result<()> {
+    require!(market.awaiting_resolution(), DjinnError::MarketNotActive);
+    require!(market.is_scalar(), DjinnError::NotScalarMarket);
+
+    // Scalar markets are curve-priced, so the fee is a plain cut of the pot
+    let fee_bps = config.params.resolution_fee_bps as u128;
+    let resolution_fee = curve_result(fee_from_bps(market.vault_balance, fee_bps))?;
+
+    pay_out_resolution(market, config, accounts, resolution_fee, 0)?;
+    market.resolved_value = Some(resolved_value);
+    Ok(())
+}
+
+/// Route the resolution fee and any LMSR refund out of the vault, snapshot the pot and
+/// mark the market resolved
+fn pay_out_resolution(
+    market: &mut Account<Market>,
+    config: &ProtocolConfig,
+    accounts: &ResolutionAccounts,
+    resolution_fee: u128,
+    lmsr_refund: u128,
+) -> Result<()> {
+    require!(market.condition_met(), DjinnError::ConditionNotMet);
+
+    let market_key = market.key();
+    let seeds = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[market.vault_bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    if resolution_fee > 0 {
+        // Split between Treasury and the Bounty Pool share
+        let (treasury_cut, bounty_cut) = config.split_resolution_fee(resolution_fee)?;
+
+        // Bounty pools are per-pool accounts created separately from the market, so the
+        // bounty share is held in the insurance vault, which funds bounties.
+        route_fee(
+            &accounts.market_vault,
+            &accounts.system_program,
+            signer,
+            &[
+                (&accounts.protocol_treasury, treasury_cut),
+                (&accounts.insurance_vault, bounty_cut),
+            ],
+        )?;
+
+        market.vault_balance = market.vault_balance.checked_sub(resolution_fee).ok_or(DjinnError::MathError)?;
+    }
+
+    // LMSR: whatever is left of the creator's subsidy goes back to the creator
+    if lmsr_refund > 0 {
+        transfer_lamports(
+            &accounts.market_vault,
+            &accounts.market_creator,
+            &accounts.system_program,
+            signer,
+            lmsr_refund as u64,
+        )?;
+        market.vault_balance -= lmsr_refund;
+    }
+
+    // (Point 2) Snapshot the Pot Balance for fair Claiming
+    market.total_pot_at_resolution = market.vault_balance as u64;
+
+    market.status = MarketStatus::Resolved;
+    Ok(())
+}
+
+/// Void an unresolved market: hand an LMSR creator its unspent subsidy and snapshot the
+/// pot for cost-basis refunds. Used by void_market and by oracle votes that never
+/// reached quorum.
+pub(crate) fn settle_void<'info>(
+    market: &mut Account<Market>,
+    market_vault: &AccountInfo<'info>,
+    market_creator: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> Result<()> {
+    let subsidy_left = market.void()?;
+    let market_key = market.key();
+    let seeds = &[
+        b"market_vault",
+        market_key.as_ref(),
+        &[market.vault_bump],
+    ];
+    transfer_lamports(market_vault, market_creator, system_program, &[&seeds[..]], subsidy_left as u64)
+}
+
+/// Accounts a BountyPool's bounty moves between
+pub(crate) struct BountyAccounts<'info> {
+    pub bounty_vault: AccountInfo<'info>,
+    pub insurance_vault: AccountInfo<'info>,
+    pub insurance_bump: u8,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Move an expired market into PendingVerification and open its BountyPool (refused
+/// while its optimistic `proposal` is unchallenged), funded by the insurance vault with ProtocolConfig.verification_bounty (or what the vault holds
+/// above its rent, if less)
+pub(crate) fn open_bounty_pool(
+    market: &mut Account<Market>,
+    proposal: Option<&ResolutionProposal>,
+    pool: &mut Account<BountyPool>,
+    pool_bump: u8,
+    config: &ProtocolConfig,
+    accounts: &BountyAccounts,
+    now: i64,
+) -> Result<()> {
+    market.enter_verification(proposal, now)?;
+
+    // A bounty has to cover the bounty vault's rent to land in it at all
+    let rent = Rent::get()?.minimum_balance(0);
+    let available = accounts.insurance_vault.lamports().saturating_sub(rent);
+    let bounty = config.params.verification_bounty.min(available);
+    let bounty = if bounty < rent { 0 } else { bounty };
+    pool.open(market.key(), bounty, now, pool_bump);
+
+    let seeds: &[&[u8]] = &[b"insurance_vault", &[accounts.insurance_bump]];
+    transfer_lamports(&accounts.insurance_vault, &accounts.bounty_vault, &accounts.system_program, &[seeds], bounty)
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+// InitializeMarket context with nonce in PDA seeds
+#[derive(Accounts)]
+#[instruction(title: String, resolution_time: i64, nonce: i64, num_outcomes: u8)]
+pub struct InitializeMarket<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Market::LEN,
+        seeds = [b"market", creator.key().as_ref(), &hash::hash(title.as_bytes()).to_bytes(), &nonce.to_le_bytes()],
+        bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// CHECK: Vault PDA (will be created on first buy)
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+    
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    /// CHECK: Protocol treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+    
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// Market this one depends on, required with params.parent_outcome
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct BuyShares<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// CHECK: Vault PDA
+    #[account(mut)]
+    pub market_vault: AccountInfo<'info>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+    
+    /// CHECK: Market Creator (for fee split)
+    #[account(
+        mut, 
+        address = market.creator // (Point 1) Anti-Spoofing Fix
+    )]
+    pub market_creator: AccountInfo<'info>,
+    
+    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+    
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// Optional frontend / integrator that routed the trade
+    #[account(mut)]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+    
+    /// CHECK: The referrer's "referral_vault" PDA (verified against its vault_bump)
+    #[account(mut)]
+    pub referral_vault: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct SellShares<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// CHECK: Vault PDA
+    #[account(mut)]
+    pub market_vault: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Market Creator for fee split
+    #[account(
+        mut, 
+        address = market.creator // (Point 1) Anti-Spoofing Fix
+    )]
+    pub market_creator: AccountInfo<'info>,
+    
+    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+    
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// Optional frontend / integrator that routed the trade
+    #[account(mut)]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+    
+    /// CHECK: The referrer's "referral_vault" PDA (verified against its vault_bump)
+    #[account(mut)]
+    pub referral_vault: Option<UncheckedAccount<'info>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(from_index: u8, to_index: u8)]
+pub struct SwapOutcome<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[from_index]],
+        bump
+    )]
+    pub from_position: Box<Account<'info, UserPosition>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[to_index]],
+        bump
+    )]
+    pub to_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Market Creator for fee split
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Optional frontend / integrator that routed the trade
+    #[account(mut)]
+    pub referrer: Option<Box<Account<'info, Referrer>>>,
+
+    /// CHECK: The referrer's "referral_vault" PDA (verified against its vault_bump)
+    #[account(mut)]
+    pub referral_vault: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CompleteSet<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct QuoteTrade<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Seller's position on sell quotes, for the short-hold exit surcharge
+    pub user_position: Option<Box<Account<'info, UserPosition>>>,
+}
+
+impl<'info> QuoteTrade<'info> {
+    /// Last buy time from the optional UserPosition of `outcome_index`, 0 without one
+    fn last_buy_ts(&self, outcome_index: u8) -> Result<i64> {
+        match &self.user_position {
+            Some(position) => {
+                require!(
+                    position.market == self.market.key() && position.outcome == outcome_index,
+                    DjinnError::InvalidPositionAccount
+                );
+                Ok(position.last_trade_ts)
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolveMarket<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// CHECK: Vault PDA
+    #[account(mut)]
+    pub market_vault: AccountInfo<'info>,
+    
+    /// CHECK: Only treasury/oracle can resolve
+    #[account(address = G1_TREASURY)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+    
+    /// CHECK: Insurance Pool Vault PDA (receives 50% of resolution fees for Bounties)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+    
+    /// CHECK: Market Creator (receives leftover LMSR subsidy)
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+    
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
+    /// Parent market, required to resolve a conditional market whose condition
+    /// has not been settled yet
+    pub parent_market: Option<Box<Account<'info, Market>>>,
+    
+    /// The market's BountyPool, required by resolve_market and resolve_scalar_market
+    #[account(
+        mut,
+        seeds = [b"bounty_pool", market.key().as_ref()],
+        bump = bounty_pool.bump
+    )]
+    pub bounty_pool: Option<Box<Account<'info, BountyPool>>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct ClaimWinnings<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// CHECK: Vault PDA
+    #[account(mut)]
+    pub market_vault: AccountInfo<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
+        bump,
+        close = user // (Point 6) Rent Refund Optimization
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefund<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    /// Any of the caller's positions in the market, whatever its outcome
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[user_position.outcome]],
+        bump,
+        close = user
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VoidMarket<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+    
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+    
+    /// CHECK: Only treasury/oracle can void
+    #[account(address = G1_TREASURY)]
+    pub authority: Signer<'info>,
+    
+    /// CHECK: Market Creator (receives leftover LMSR subsidy)
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// OPTIMISTIC RESOLUTION ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+pub struct ProposeResolution<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = ResolutionProposal::LEN,
+        seeds = [b"resolution", market.key().as_ref()],
+        bump
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// CHECK: Bond escrow PDA (SOL holder for both bonds)
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", proposal.key().as_ref()],
+        bump
+    )]
+    pub bond_escrow: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ChallengeResolution<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution", market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// CHECK: Bond escrow PDA
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", proposal.key().as_ref()],
+        bump = proposal.bond_bump
+    )]
+    pub bond_escrow: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    /// The market's BountyPool, opened here unless verification already started
+    #[account(
+        init_if_needed,
+        payer = challenger,
+        space = 256, // enough for BountyPool
+        seeds = [b"bounty_pool", market.key().as_ref()],
+        bump
+    )]
+    pub bounty_pool: Box<Account<'info, BountyPool>>,
+
+    /// CHECK: Bounty vault PDA
+    #[account(
+        mut,
+        seeds = [b"bounty_vault", bounty_pool.key().as_ref()],
+        bump
+    )]
+    pub bounty_vault: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (funds the bounty)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// Global bond and verification configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"resolution", market.key().as_ref()],
+        bump = proposal.bump,
+        has_one = market,
+        has_one = proposer,
+        close = proposer
+    )]
+    pub proposal: Box<Account<'info, ResolutionProposal>>,
+
+    /// CHECK: Bond escrow PDA
+    #[account(
+        mut,
+        seeds = [b"resolution_bond", proposal.key().as_ref()],
+        bump = proposal.bond_bump
+    )]
+    pub bond_escrow: AccountInfo<'info>,
+
+    /// CHECK: Proposer (bond and rent refunds)
+    #[account(mut)]
+    pub proposer: AccountInfo<'info>,
+
+    /// CHECK: Challenger, required once the proposal was challenged
+    #[account(mut)]
+    pub challenger: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (resolution bounty share, forfeited bonds)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// CHECK: Market Creator (receives leftover LMSR subsidy)
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REFERRAL ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = Referrer::LEN,
+        seeds = [b"referrer", owner.key().as_ref()],
+        bump
+    )]
+    pub referrer: Box<Account<'info, Referrer>>,
+
+    /// CHECK: Referral vault PDA (SOL holder for accrued cuts)
+    #[account(
+        mut,
+        seeds = [b"referral_vault", referrer.key().as_ref()],
+        bump
+    )]
+    pub referral_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"referrer", owner.key().as_ref()],
+        bump = referrer.bump,
+        has_one = owner
+    )]
+    pub referrer: Box<Account<'info, Referrer>>,
+
+    /// CHECK: Referral vault PDA
+    #[account(
+        mut,
+        seeds = [b"referral_vault", referrer.key().as_ref()],
+        bump = referrer.vault_bump
+    )]
+    pub referral_vault: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LIMIT ORDER ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8, order_id: u64)]
+pub struct PlaceLimitOrder<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = LimitOrder::LEN,
+        seeds = [b"limit_order", market.key().as_ref(), owner.key().as_ref(), &order_id.to_le_bytes()],
+        bump
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    /// CHECK: System-owned escrow PDA holding the order's SOL and tip
+    #[account(
+        mut,
+        seeds = [b"order_escrow", limit_order.key().as_ref()],
+        bump
+    )]
+    pub order_escrow: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserPosition::LEN,
+        seeds = [b"user_pos", market.key().as_ref(), owner.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"limit_order", limit_order.market.as_ref(), owner.key().as_ref(), &limit_order.order_id.to_le_bytes()],
+        bump = limit_order.bump,
+        close = owner
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    /// CHECK: Escrow PDA, drained back to the owner
+    #[account(
+        mut,
+        seeds = [b"order_escrow", limit_order.key().as_ref()],
+        bump = limit_order.escrow_bump
+    )]
+    pub order_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_pos", limit_order.market.as_ref(), owner.key().as_ref(), &[limit_order.outcome]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CrankOrders<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Market Creator for fee split
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// Anyone can crank — paid each order's tip
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// POSITION EXIT ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct SetExitThresholds<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub user: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8)]
+pub struct ExecuteExit<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), owner.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// CHECK: Position owner (bound by the user_position seeds), receives the proceeds
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Market Creator for fee split
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// Anyone can execute a crossed exit — paid up to keeper_tip
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// DCA ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+#[instruction(outcome_index: u8, plan_id: u64)]
+pub struct CreateDcaPlan<'info> {
+    pub market: Box<Account<'info, Market>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = DcaPlan::LEN,
+        seeds = [b"dca_plan", market.key().as_ref(), owner.key().as_ref(), &plan_id.to_le_bytes()],
+        bump
+    )]
+    pub dca_plan: Box<Account<'info, DcaPlan>>,
+
+    /// CHECK: System-owned escrow PDA holding the plan's budget
+    #[account(
+        mut,
+        seeds = [b"dca_escrow", dca_plan.key().as_ref()],
+        bump
+    )]
+    pub dca_escrow: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = UserPosition::LEN,
+        seeds = [b"user_pos", market.key().as_ref(), owner.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDca<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        has_one = market,
+        seeds = [b"dca_plan", market.key().as_ref(), dca_plan.owner.as_ref(), &dca_plan.plan_id.to_le_bytes()],
+        bump = dca_plan.bump
+    )]
+    pub dca_plan: Box<Account<'info, DcaPlan>>,
+
+    /// CHECK: Escrow PDA funding the buy
+    #[account(
+        mut,
+        seeds = [b"dca_escrow", dca_plan.key().as_ref()],
+        bump = dca_plan.escrow_bump
+    )]
+    pub dca_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_pos", market.key().as_ref(), dca_plan.owner.as_ref(), &[dca_plan.outcome]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Market Creator for fee split
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// Anyone can execute a due interval — paid the plan's keeper_tip
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CloseDcaPlan<'info> {
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"dca_plan", dca_plan.market.as_ref(), owner.key().as_ref(), &dca_plan.plan_id.to_le_bytes()],
+        bump = dca_plan.bump,
+        close = owner
+    )]
+    pub dca_plan: Box<Account<'info, DcaPlan>>,
+
+    /// CHECK: Escrow PDA, drained back to the owner
+    #[account(
+        mut,
+        seeds = [b"dca_escrow", dca_plan.key().as_ref()],
+        bump = dca_plan.escrow_bump
+    )]
+    pub dca_escrow: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROTOCOL CONFIG ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = ProtocolConfig::LEN,
+        seeds = [b"protocol_config"],
+        bump
+    )]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Only admin can initialize
+    #[account(mut, address = G1_TREASURY)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Governance-only: fee / split parameters and the authority itself
+#[derive(Accounts)]
+pub struct UpdateProtocolConfig<'info> {
+    #[account(mut, seeds = [b"protocol_config"], bump = protocol_config.bump, has_one = authority)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    pub authority: Signer<'info>,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// MARKET SETTINGS ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
 
-        if deposit.amount == 0 {
-            vault.num_depositors = vault.num_depositors.saturating_sub(1);
-        }
+// Creator-only market settings: batch mode, trading limits, fee schedule
+#[derive(Accounts)]
+pub struct UpdateMarketConfig<'info> {
+    #[account(mut, has_one = creator)]
+    pub market: Box<Account<'info, Market>>,
 
-        // Record loss
-        vault.total_loss = vault.total_loss.checked_add(
-            (deposit.amount as u128).saturating_sub(actual_payout as u128)
-        ).unwrap_or(vault.total_loss);
+    pub creator: Signer<'info>,
+}
 
-        Ok(())
-    }
+#[derive(Accounts)]
+pub struct SetChronosTradingLimits<'info> {
+    #[account(mut, has_one = keeper)]
+    pub chronos_market: Box<Account<'info, chronos_market::ChronosMarket>>,
+
+    pub keeper: Signer<'info>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
-// ACCOUNT CONTEXTS
+// CONDITIONAL MARKET ACCOUNT CONTEXTS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-// InitializeMarket context with nonce in PDA seeds
 #[derive(Accounts)]
-#[instruction(title: String, resolution_time: i64, nonce: i64, num_outcomes: u8)]
-pub struct InitializeMarket<'info> {
+pub struct SettleMarketCondition<'info> {
+    #[account(mut)]
+    pub market: Box<Account<'info, Market>>,
+
     #[account(
-        init,
-        payer = creator,
-        // (Point 2) Added +8 bytes for total_pot_at_resolution
-        space = 8 + 32 + (4 + 64) + 8 + 1 + (6 * 16) + 16 + 8 + 1 + 8 + 2 + 1 + 1,
-        // 8 (discriminator) + 32 (creator) + (4 + 64) (title) + 8 (nonce) + 1 (num_outcomes)
-        // + (6 * 16) (outcome_supplies array) + 16 (vault_balance) + 8 (total_pot) + 1 (status)
-        // + 8 (resolution_time) + 2 (winning_outcome) + 1 (bump) + 1 (vault_bump)
-        seeds = [b"market", creator.key().as_ref(), &hash::hash(title.as_bytes()).to_bytes(), &nonce.to_le_bytes()],
-        bump
+        constraint = market.condition.map(|condition| condition.parent) == Some(parent_market.key())
+            @ DjinnError::InvalidParentMarket
     )]
-    pub market: Box<Account<'info, Market>>,
-    
-    /// CHECK: Vault PDA (will be created on first buy)
+    pub parent_market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA
     #[account(
         mut,
         seeds = [b"market_vault", market.key().as_ref()],
-        bump
+        bump = market.vault_bump
     )]
     pub market_vault: AccountInfo<'info>,
-    
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    /// CHECK: Protocol treasury
-    #[account(mut, address = G1_TREASURY)]
-    pub protocol_treasury: AccountInfo<'info>,
-    
+
+    /// CHECK: Market Creator (receives leftover LMSR subsidy on a void)
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// BATCH AUCTION ACCOUNT CONTEXTS
+// ═══════════════════════════════════════════════════════════════════════════════
+
 #[derive(Accounts)]
-#[instruction(outcome_index: u8)]
-pub struct BuyShares<'info> {
-    #[account(mut)]
+#[instruction(batch_id: u64, outcome_index: u8)]
+pub struct SubmitBatchOrder<'info> {
     pub market: Box<Account<'info, Market>>,
-    
-    /// CHECK: Vault PDA
-    #[account(mut)]
-    pub market_vault: AccountInfo<'info>,
-    
+
     #[account(
         init_if_needed,
         payer = user,
-        space = 8 + 32 + 1 + 16 + 1,
-        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
+        space = Batch::LEN,
+        seeds = [b"batch", market.key().as_ref(), &batch_id.to_le_bytes()],
         bump
     )]
-    pub user_position: Box<Account<'info, UserPosition>>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    /// CHECK: Treasury
-    #[account(mut, address = G1_TREASURY)]
-    pub protocol_treasury: AccountInfo<'info>,
-    
-    /// CHECK: Market Creator (for fee split)
+    pub batch: Box<Account<'info, Batch>>,
+
+    /// CHECK: System-owned escrow PDA holding the batch's SOL until settlement
     #[account(
-        mut, 
-        address = market.creator // (Point 1) Anti-Spoofing Fix
+        mut,
+        seeds = [b"batch_escrow", batch.key().as_ref()],
+        bump
     )]
-    pub market_creator: AccountInfo<'info>,
-    
-    /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
+    pub batch_escrow: AccountInfo<'info>,
+
     #[account(
-        mut,
-        seeds = [b"insurance_vault"],
+        init,
+        payer = user,
+        space = BatchOrder::LEN,
+        seeds = [b"batch_order", batch.key().as_ref(), user.key().as_ref()],
         bump
     )]
-    pub insurance_vault: AccountInfo<'info>,
-    
+    pub batch_order: Box<Account<'info, BatchOrder>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserPosition::LEN,
+        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-#[instruction(outcome_index: u8)]
-pub struct SellShares<'info> {
+pub struct SettleBatch<'info> {
     #[account(mut)]
     pub market: Box<Account<'info, Market>>,
-    
+
+    #[account(
+        mut,
+        has_one = market,
+        has_one = opener,
+        seeds = [b"batch", market.key().as_ref(), &batch.batch_id.to_le_bytes()],
+        bump = batch.bump
+    )]
+    pub batch: Box<Account<'info, Batch>>,
+
+    /// CHECK: Escrow PDA funding the batch
+    #[account(
+        mut,
+        seeds = [b"batch_escrow", batch.key().as_ref()],
+        bump = batch.escrow_bump
+    )]
+    pub batch_escrow: AccountInfo<'info>,
+
     /// CHECK: Vault PDA
-    #[account(mut)]
-    pub market_vault: AccountInfo<'info>,
-    
     #[account(
         mut,
-        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
-        bump
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
     )]
-    pub user_position: Box<Account<'info, UserPosition>>,
-    
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
+    pub market_vault: AccountInfo<'info>,
+
     /// CHECK: Treasury
     #[account(mut, address = G1_TREASURY)]
     pub protocol_treasury: AccountInfo<'info>,
 
     /// CHECK: Market Creator for fee split
     #[account(
-        mut, 
-        address = market.creator // (Point 1) Anti-Spoofing Fix
+        mut,
+        address = market.creator
     )]
     pub market_creator: AccountInfo<'info>,
-    
+
     /// CHECK: Insurance Pool Vault PDA (receives 10% of fees)
     #[account(
         mut,
@@ -1861,59 +4140,67 @@ pub struct SellShares<'info> {
         bump
     )]
     pub insurance_vault: AccountInfo<'info>,
-    
+
+    /// CHECK: Opened the batch, gets the escrow rent back
+    #[account(mut)]
+    pub opener: AccountInfo<'info>,
+
+    /// Anyone can settle a closed batch
+    pub settler: Signer<'info>,
+
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
-pub struct ResolveMarket<'info> {
-    #[account(mut)]
-    pub market: Box<Account<'info, Market>>,
-    
-    /// CHECK: Vault PDA
-    #[account(mut)]
-    pub market_vault: AccountInfo<'info>,
-    
-    /// CHECK: Only treasury/oracle can resolve
-    #[account(address = G1_TREASURY)]
-    pub authority: Signer<'info>,
-    
-    /// CHECK: Treasury
-    #[account(mut, address = G1_TREASURY)]
-    pub protocol_treasury: AccountInfo<'info>,
-    
-    /// CHECK: Insurance Pool Vault PDA (receives 50% of resolution fees for Bounties)
+pub struct ClaimBatchFill<'info> {
     #[account(
         mut,
-        seeds = [b"insurance_vault"],
-        bump
+        has_one = opener,
+        seeds = [b"batch", batch.market.as_ref(), &batch.batch_id.to_le_bytes()],
+        bump = batch.bump
     )]
-    pub insurance_vault: AccountInfo<'info>,
-    
-    pub system_program: Program<'info, System>,
-}
+    pub batch: Box<Account<'info, Batch>>,
 
-#[derive(Accounts)]
-#[instruction(outcome_index: u8)]
-pub struct ClaimWinnings<'info> {
-    #[account(mut)]
-    pub market: Box<Account<'info, Market>>,
-    
-    /// CHECK: Vault PDA
-    #[account(mut)]
-    pub market_vault: AccountInfo<'info>,
-    
     #[account(
         mut,
-        seeds = [b"user_pos", market.key().as_ref(), user.key().as_ref(), &[outcome_index]],
-        bump,
-        close = user // (Point 6) Rent Refund Optimization
+        has_one = batch,
+        has_one = owner,
+        seeds = [b"batch_order", batch.key().as_ref(), owner.key().as_ref()],
+        bump = batch_order.bump,
+        close = owner
+    )]
+    pub batch_order: Box<Account<'info, BatchOrder>>,
+
+    /// CHECK: Escrow PDA refunding cancelled batches
+    #[account(
+        mut,
+        seeds = [b"batch_escrow", batch.key().as_ref()],
+        bump = batch.escrow_bump
+    )]
+    pub batch_escrow: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"user_pos", batch.market.as_ref(), owner.key().as_ref(), &[batch_order.outcome]],
+        bump
     )]
     pub user_position: Box<Account<'info, UserPosition>>,
-    
+
+    /// CHECK: Order owner (bound by the batch_order seeds)
     #[account(mut)]
-    pub user: Signer<'info>,
-    
+    pub owner: AccountInfo<'info>,
+
+    /// CHECK: Opened the batch, gets the escrow rent back after the last refund
+    #[account(mut)]
+    pub opener: AccountInfo<'info>,
+
+    /// Anyone can push a fill to its owner
+    pub claimer: Signer<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1987,6 +4274,10 @@ pub struct BuyChronosShares<'info> {
     #[account(mut, address = G1_TREASURY)]
     pub protocol_treasury: AccountInfo<'info>,
     
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -2006,6 +4297,10 @@ pub struct ResolveChronosMarket<'info> {
     #[account(mut, address = G1_TREASURY)]
     pub protocol_treasury: AccountInfo<'info>,
     
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+    
     pub system_program: Program<'info, System>,
 }
 
@@ -2130,7 +4425,7 @@ pub struct InitializeInsurancePool<'info> {
 pub struct InitializeBountyPool<'info> {
     #[account(
         init,
-        payer = authority,
+        payer = payer,
         space = 256, // enough for BountyPool
         seeds = [b"bounty_pool", market.key().as_ref()],
         bump
@@ -2148,16 +4443,26 @@ pub struct InitializeBountyPool<'info> {
     #[account(mut)]
     pub market: Box<Account<'info, Market>>,
 
-    /// CHECK: Market vault PDA
+    /// CHECK: The market's ResolutionProposal PDA, if any (an unchallenged one blocks
+    /// the pool)
+    #[account(seeds = [b"resolution", market.key().as_ref()], bump)]
+    pub proposal: UncheckedAccount<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (funds the bounty)
     #[account(
         mut,
-        seeds = [b"market_vault", market.key().as_ref()],
+        seeds = [b"insurance_vault"],
         bump
     )]
-    pub market_vault: AccountInfo<'info>,
+    pub insurance_vault: AccountInfo<'info>,
 
-    #[account(mut, address = G1_TREASURY)]
-    pub authority: Signer<'info>,
+    /// Global verification configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
+    /// Anyone: pays the pool's rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -2168,6 +4473,9 @@ pub struct SubmitVerification<'info> {
     #[account(mut)]
     pub bounty_pool: Box<Account<'info, BountyPool>>,
 
+    #[account(address = bounty_pool.market)]
+    pub market: Box<Account<'info, Market>>,
+
     #[account(
         mut,
         seeds = [b"bot_profile", bot_owner_signer.key().as_ref()],
@@ -2195,6 +4503,9 @@ pub struct CerberusOverride<'info> {
     #[account(mut)]
     pub bounty_pool: Box<Account<'info, BountyPool>>,
 
+    #[account(address = bounty_pool.market)]
+    pub market: Box<Account<'info, Market>>,
+
     #[account(address = G1_TREASURY)]
     pub authority: Signer<'info>,
 }
@@ -2204,9 +4515,43 @@ pub struct FinalizeBountyPool<'info> {
     #[account(mut)]
     pub bounty_pool: Box<Account<'info, BountyPool>>,
 
+    #[account(mut, address = bounty_pool.market)]
     pub market: Box<Account<'info, Market>>,
 
+    /// CHECK: Vault PDA
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    /// CHECK: Treasury
+    #[account(mut, address = G1_TREASURY)]
+    pub protocol_treasury: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (resolution bounty share)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
+
+    /// CHECK: Market Creator (receives leftover LMSR subsidy)
+    #[account(
+        mut,
+        address = market.creator
+    )]
+    pub market_creator: AccountInfo<'info>,
+
+    /// Global fee and verification configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -2249,9 +4594,24 @@ pub struct ExpireBounty<'info> {
     )]
     pub bounty_vault: AccountInfo<'info>,
 
-    /// CHECK: Treasury
-    #[account(mut, address = G1_TREASURY)]
-    pub protocol_treasury: AccountInfo<'info>,
+    #[account(mut, address = bounty_pool.market)]
+    pub market: Box<Account<'info, Market>>,
+
+    /// CHECK: Vault PDA (refund pot of a voided market)
+    #[account(
+        mut,
+        seeds = [b"market_vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub market_vault: AccountInfo<'info>,
+
+    /// CHECK: Insurance Pool Vault PDA (funded the bounty)
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -2327,6 +4687,10 @@ pub struct ResolveDispute<'info> {
     #[account(address = G1_TREASURY)]
     pub authority: Signer<'info>,
 
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -2444,6 +4808,10 @@ pub struct DistributeProfits<'info> {
 
     pub authority: Signer<'info>,
 
+    /// Global fee and split configuration
+    #[account(seeds = [b"protocol_config"], bump = protocol_config.bump)]
+    pub protocol_config: Box<Account<'info, ProtocolConfig>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -2597,6 +4965,7 @@ pub struct BountyPool {
     pub market: Pubkey,             // 32
     pub total_bounty: u64,          // 8
     pub total_weight: u64,          // 8 — sum of all submission weights
+    pub outcome_weights: [u64; 6],  // 48 — submission weights per proposed outcome
     pub winning_outcome: Option<u8>,// 2
     pub cerberus_override: bool,    // 1
     pub cerberus_outcome: Option<u8>, // 2
@@ -2757,6 +5126,18 @@ pub enum DjinnError {
     MathError,
     #[msg("Market has expired")]
     MarketExpired,
+    #[msg("Invalid bonding curve parameters")]
+    InvalidCurveParams,
+    #[msg("LMSR liquidity parameter out of range")]
+    InvalidLiquidity,
+    #[msg("Amount must be greater than zero")]
+    InvalidAmount,
+    #[msg("Requested amount exceeds the market vault balance")]
+    InsufficientVaultBalance,
+    #[msg("The outcome curves price this complete set on the wrong side of par")]
+    CompleteSetOffPar,
+    #[msg("Remaining accounts must be the caller's UserPosition PDAs in outcome order")]
+    InvalidPositionAccount,
 
     // Bot errors (Phase 1)
     #[msg("Bot name too long (max 32 chars)")]
@@ -2817,6 +5198,140 @@ pub enum DjinnError {
     DepositExceedsVaultCap,
     #[msg("Insufficient deposit to withdraw")]
     InsufficientDeposit,
+
+    // Limit order errors
+    #[msg("Limit order accounts must be [order, escrow, position, owner] groups for this market")]
+    InvalidOrderAccount,
+    #[msg("Trigger price must be greater than zero")]
+    InvalidTriggerPrice,
+    #[msg("Crank tip below the minimum")]
+    CrankTipTooLow,
+    #[msg("Order expiry must be in the future")]
+    OrderExpired,
+    #[msg("Position has open sell orders; cancel or crank them before claiming")]
+    OpenSellOrders,
+
+    // Position exit errors
+    #[msg("Stop-loss must be below take-profit")]
+    InvalidExitThresholds,
+    #[msg("Position has no stop-loss or take-profit set")]
+    NoExitThresholds,
+    #[msg("Neither exit threshold has been crossed")]
+    ExitThresholdNotCrossed,
+
+    // DCA errors
+    #[msg("DCA amount and interval must be greater than zero and end in the future")]
+    InvalidDcaSchedule,
+    #[msg("DCA plan has ended")]
+    DcaPlanEnded,
+    #[msg("DCA interval has not elapsed yet")]
+    DcaIntervalNotElapsed,
+    #[msg("DCA escrow cannot fund another interval")]
+    DcaEscrowDepleted,
+
+    // Batch auction errors
+    #[msg("Market trades in batch auctions; submit a batch order instead")]
+    BatchModeActive,
+    #[msg("Market is not in batch auction mode")]
+    BatchModeDisabled,
+    #[msg("Batch id is not the market's open batch")]
+    InvalidBatch,
+    #[msg("Batch is no longer open")]
+    BatchNotOpen,
+    #[msg("Batch window has closed")]
+    BatchWindowClosed,
+    #[msg("Batch window is still open")]
+    BatchWindowOpen,
+    #[msg("Batch has not been settled yet")]
+    BatchNotSettled,
+    #[msg("Batch mode can only change before the market's first trade")]
+    BatchModeLocked,
+
+    // Trading limit errors
+    #[msg("Trading limit cooldown must not be negative")]
+    InvalidTradingLimits,
+    #[msg("Trade exceeds the market's maximum SOL per transaction")]
+    MaxSolPerTradeExceeded,
+    #[msg("Trade would exceed the market's maximum shares per wallet for this outcome")]
+    WalletShareLimitExceeded,
+    #[msg("Wallet is still in the market's cooldown since its last buy")]
+    TradeCooldownActive,
+    #[msg("Trading limits can only change before the market's first trade")]
+    TradingLimitsLocked,
+
+    // Fee schedule errors
+    #[msg("Fee schedule can only change before the market's first trade")]
+    FeeScheduleLocked,
+
+    // Protocol config errors
+    #[msg("Protocol config fee above 10%, split above 100%, zero bond / challenge period / quorum, or majority outside 50-100%")]
+    InvalidProtocolConfig,
+
+    // Referral errors
+    #[msg("Referrer and referral vault must be passed together and match")]
+    InvalidReferrer,
+    #[msg("Cannot refer your own trades")]
+    SelfReferral,
+    #[msg("No referral fees to claim")]
+    NoReferralFees,
+
+    // Void market errors
+    #[msg("Market is not voided")]
+    MarketNotVoided,
+
+    // Optimistic resolution errors
+    #[msg("Resolution bond is below the protocol minimum")]
+    ResolutionBondTooLow,
+    #[msg("Challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Proposal has already been challenged")]
+    ProposalAlreadyChallenged,
+    #[msg("Cannot challenge your own proposal")]
+    SelfChallenge,
+    #[msg("Market is waiting on the oracle network's vote")]
+    EscalationPending,
+    #[msg("Challenger account does not match the proposal")]
+    InvalidChallenger,
+    #[msg("Market has an unchallenged resolution proposal; challenge it to escalate")]
+    ProposalPending,
+
+    // Verification errors
+    #[msg("Verification vote has no quorum or majority yet")]
+    QuorumNotReached,
+    #[msg("Market must be under oracle verification (initialize_bounty_pool)")]
+    VerificationRequired,
+
+    // Scalar market errors
+    #[msg("Scalar markets need 2 outcomes, curve pricing and lower bound < upper bound")]
+    InvalidScalarRange,
+    #[msg("Market is not a scalar market")]
+    NotScalarMarket,
+    #[msg("Scalar markets resolve to a value, not an outcome")]
+    ScalarMarket,
+
+    // Conditional market errors
+    #[msg("Parent market must be another open, categorical market with that outcome")]
+    InvalidParentMarket,
+    #[msg("Market has no condition")]
+    NotConditionalMarket,
+    #[msg("Parent market has not resolved yet")]
+    ParentNotResolved,
+    #[msg("Parent market resolved against the condition; settle it to void this market")]
+    ConditionFailed,
+    #[msg("Conditional market's parent has not resolved to its condition yet")]
+    ConditionNotMet,
 }
 
 
+
+impl From<djinn_curve::CurveError> for DjinnError {
+    fn from(error: djinn_curve::CurveError) -> Self {
+        match error {
+            djinn_curve::CurveError::MathError => DjinnError::MathError,
+            djinn_curve::CurveError::InvalidParams => DjinnError::InvalidCurveParams,
+            djinn_curve::CurveError::InsufficientSupply => DjinnError::InsufficientShares,
+        }
+    }
+}
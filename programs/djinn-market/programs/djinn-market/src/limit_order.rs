@@ -0,0 +1,468 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// LIMIT ORDER MODULE - Price-triggered orders executed by permissionless cranks
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// A LimitOrder PDA rests on one outcome of a Market until the outcome's spot price
+// crosses its trigger:
+// - Buy:  escrowed SOL buys when spot <= trigger_price
+// - Sell: escrowed shares sell when spot >= trigger_price
+//
+// SOL (the buy amount, the cranker tip and the escrow's own rent) sits in a
+// system-owned "order_escrow" PDA, paid out with signed transfers like the market
+// vault. Sell orders move their shares out of the owner's UserPosition at placement.
+//
+// crank_orders runs every triggered order through the same quote and settlement
+// path as buy_shares / sell_shares, pays the cranker the order's tip and closes the
+// order back to its owner. Expired orders, and orders on markets that stopped
+// trading, are refunded instead (the cranker still earns the tip). Buy orders rest
+// while the market is in batch mode, since only batches may buy.
+//
+// claim_winnings and claim_refund close the UserPosition a sell order refunds into,
+// so each position counts its open sell orders and both claims refuse until every
+// one of them has been cancelled, filled or refunded.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::protocol_config::ProtocolConfig;
+use crate::quote::TradeQuote;
+use crate::{route_trading_fee, transfer_lamports, DjinnError, Market, MarketStatus, UserPosition};
+
+// Smallest tip an order may offer: one signature fee, so cranking never loses money
+pub const MIN_CRANK_TIP: u64 = 5_000;
+
+// --- ORDER SIDE ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSide {
+    Buy = 0,  // Spend escrowed SOL once spot <= trigger
+    Sell = 1, // Sell escrowed shares once spot >= trigger
+}
+
+// --- PLACEMENT PARAMETERS ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LimitOrderParams {
+    pub side: OrderSide,
+    pub amount: u64,         // Lamports to spend (Buy) or shares to sell (Sell)
+    pub trigger_price: u128, // Spot price in lamports per whole share
+    pub min_out: u64,        // Minimum shares (Buy) or net lamports (Sell) on execution
+    pub tip: u64,            // Lamports paid to whoever cranks the order
+    pub expiry: i64,         // Unix timestamp after which the order is refunded
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LIMIT ORDER ACCOUNT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[account]
+pub struct LimitOrder {
+    pub owner: Pubkey,
+    pub market: Pubkey,
+    pub order_id: u64,        // Owner-chosen id (PDA seed)
+    pub outcome: u8,
+    pub side: OrderSide,
+    pub amount: u64,          // Escrowed lamports (Buy) or shares (Sell)
+    pub trigger_price: u128,
+    pub min_out: u64,
+    pub tip: u64,
+    pub expiry: i64,
+    pub bump: u8,
+    pub escrow_bump: u8,
+    pub cost_basis: u128,     // Basis of escrowed shares (Sell), see void_market
+}
+
+impl LimitOrder {
+    pub const LEN: usize = 8     // Discriminator
+        + 32                     // owner
+        + 32                     // market
+        + 8                      // order_id
+        + 1                      // outcome
+        + 1                      // side
+        + 8                      // amount
+        + 16                     // trigger_price
+        + 8                      // min_out
+        + 8                      // tip
+        + 8                      // expiry
+        + 1                      // bump
+        + 1                      // escrow_bump
+        + 16;                    // cost_basis
+
+    /// Whether the outcome's spot price has crossed the trigger
+    pub fn is_triggered(&self, spot_price: u128) -> bool {
+        match self.side {
+            OrderSide::Buy => spot_price <= self.trigger_price,
+            OrderSide::Sell => spot_price >= self.trigger_price,
+        }
+    }
+
+    /// Expired, or the market stopped trading: the order can only be refunded
+    pub fn is_refundable(&self, market: &Market, now: i64) -> bool {
+        let trading_open = market.status == MarketStatus::Active && now < market.resolution_time;
+        now >= self.expiry || !trading_open
+    }
+
+    /// What a crank does with this order at `now`, filling into the owner's `position`
+    /// (its last buy sets the short-hold exit surcharge on sells, and buys must stay
+    /// within the market's trading limits)
+    pub fn crank_action(
+        &self,
+        market: &Market,
+        config: &ProtocolConfig,
+        now: i64,
+        position: &UserPosition,
+    ) -> Result<CrankAction> {
+        if self.is_refundable(market, now) {
+            return Ok(CrankAction::Refund);
+        }
+        if !self.is_triggered(market.spot_price(self.outcome)?) {
+            return Ok(CrankAction::Rest);
+        }
+
+        // Same quote path as buy_shares / sell_shares
+        let amount = self.amount as u128;
+        let (quote, out) = match self.side {
+            OrderSide::Buy => {
+                // Batch mode routes every buy through a batch; the order waits it out
+                if market.batch_window_slots > 0 {
+                    return Ok(CrankAction::Rest);
+                }
+                let quote = market.quote_buy(config, self.outcome, amount, now)?;
+                if position.check_buy(&market.limits, amount, quote.shares, now).is_err() {
+                    return Ok(CrankAction::Rest);
+                }
+                (quote, quote.shares)
+            }
+            OrderSide::Sell => {
+                let quote = market.quote_sell(config, self.outcome, amount, now, position.last_trade_ts)?;
+                (quote, quote.net_sol)
+            }
+        };
+        if out < self.min_out as u128 {
+            return Ok(CrankAction::Rest);
+        }
+        Ok(CrankAction::Fill(quote))
+    }
+}
+
+impl UserPosition {
+    /// Move `shares` into a new sell order. Returns the cost basis they take along.
+    pub fn escrow_sell(&mut self, shares: u128) -> Result<u128> {
+        let basis = self.debit(shares)?;
+        self.open_sell_orders = self.open_sell_orders.checked_add(1).ok_or(DjinnError::MathError)?;
+        Ok(basis)
+    }
+
+    /// A sell order was cancelled or refunded: its shares and basis come back
+    pub fn refund_sell(&mut self, shares: u128, basis: u128) -> Result<()> {
+        self.credit(shares, basis)?;
+        self.settle_sell();
+        Ok(())
+    }
+
+    /// A sell order closed; the position no longer waits on it
+    pub fn settle_sell(&mut self) {
+        self.open_sell_orders = self.open_sell_orders.saturating_sub(1);
+    }
+}
+
+/// Outcome of cranking one order
+pub enum CrankAction {
+    Rest,             // Not triggered, or the fill would miss min_out or break a limit
+    Refund,           // Expired, or the market stopped trading
+    Fill(TradeQuote), // Execute at this quote
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// CRANK EXECUTION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Accounts shared by every order in one crank_orders call
+pub struct CrankAccounts<'info> {
+    pub market_key: Pubkey,
+    pub market_vault: AccountInfo<'info>,
+    pub protocol_treasury: AccountInfo<'info>,
+    pub market_creator: AccountInfo<'info>,
+    pub insurance_vault: AccountInfo<'info>,
+    pub cranker: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Execute or refund one order from its [order, escrow, owner's UserPosition, owner]
+/// remaining-account group. Returns false when the order is left resting.
+pub fn crank_order<'info>(
+    market: &mut Market,
+    config: &ProtocolConfig,
+    accounts: &CrankAccounts<'info>,
+    group: &'info [AccountInfo<'info>],
+    now: i64,
+) -> Result<bool> {
+    let [order_info, escrow_info, position_info, owner_info] = group else {
+        return err!(DjinnError::InvalidOrderAccount);
+    };
+
+    let order = Account::<LimitOrder>::try_from(order_info)?;
+    require_keys_eq!(order.market, accounts.market_key, DjinnError::InvalidOrderAccount);
+    require_keys_eq!(owner_info.key(), order.owner, DjinnError::InvalidOrderAccount);
+
+    let escrow_seeds: &[&[u8]] = &[b"order_escrow", order_info.key.as_ref(), &[order.escrow_bump]];
+    let escrow_key = Pubkey::create_program_address(escrow_seeds, &crate::ID)
+        .map_err(|_| error!(DjinnError::InvalidOrderAccount))?;
+    require_keys_eq!(escrow_info.key(), escrow_key, DjinnError::InvalidOrderAccount);
+
+    let (position_key, _) = Pubkey::find_program_address(
+        &[b"user_pos", accounts.market_key.as_ref(), order.owner.as_ref(), &[order.outcome]],
+        &crate::ID,
+    );
+    require_keys_eq!(position_info.key(), position_key, DjinnError::InvalidOrderAccount);
+
+    // Refunds skip the position: a buy order's may already be closed by claim_winnings
+    let vault_seeds: &[&[u8]] = &[b"market_vault", accounts.market_key.as_ref(), &[market.vault_bump]];
+    if order.is_refundable(market, now) {
+        if order.side == OrderSide::Sell {
+            // Sell orders hand their shares back; escrowed SOL goes back below
+            let mut position = Account::<UserPosition>::try_from(position_info)?;
+            position.refund_sell(order.amount as u128, order.cost_basis)?;
+            position.exit(&crate::ID)?;
+        }
+    } else {
+        let mut position = Account::<UserPosition>::try_from(position_info)?;
+        let CrankAction::Fill(quote) = order.crank_action(market, config, now, &position)? else {
+            return Ok(false);
+        };
+        match order.side {
+            OrderSide::Buy => {
+                market.apply_buy(order.outcome, &quote)?;
+                market.add_cost_basis(quote.net_sol)?;
+                position.record_buy(&market.limits, order.amount as u128, quote.shares, quote.net_sol, now)?;
+                position.exit(&crate::ID)?;
+
+                transfer_lamports(
+                    escrow_info,
+                    &accounts.market_vault,
+                    &accounts.system_program,
+                    &[escrow_seeds],
+                    quote.net_sol as u64,
+                )?;
+                route_trading_fee(
+                    &quote,
+                    escrow_info,
+                    &accounts.protocol_treasury,
+                    &accounts.market_creator,
+                    &accounts.insurance_vault,
+                    &accounts.system_program,
+                    &[escrow_seeds],
+                )?;
+            }
+            OrderSide::Sell => {
+                market.apply_sell(order.outcome, &quote)?;
+                market.remove_cost_basis(order.cost_basis)?;
+                position.settle_sell();
+                position.exit(&crate::ID)?;
+
+                transfer_lamports(
+                    &accounts.market_vault,
+                    owner_info,
+                    &accounts.system_program,
+                    &[vault_seeds],
+                    quote.net_sol as u64,
+                )?;
+                route_trading_fee(
+                    &quote,
+                    &accounts.market_vault,
+                    &accounts.protocol_treasury,
+                    &accounts.market_creator,
+                    &accounts.insurance_vault,
+                    &accounts.system_program,
+                    &[vault_seeds],
+                )?;
+            }
+        }
+    }
+
+    // Tip the cranker, hand the rest of the escrow back and close the order
+    transfer_lamports(escrow_info, &accounts.cranker, &accounts.system_program, &[escrow_seeds], order.tip)?;
+    drain_escrow(escrow_info, owner_info, &accounts.system_program, escrow_seeds)?;
+    order.close(owner_info.clone())?;
+    Ok(true)
+}
+
+/// Send everything left in an order escrow to `owner`
+pub fn drain_escrow<'info>(
+    escrow: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    escrow_seeds: &[&[u8]],
+) -> Result<()> {
+    transfer_lamports(escrow, owner, system_program, &[escrow_seeds], escrow.lamports())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, lmsr_market, market, position, NOW, SOL};
+
+    fn order(side: OrderSide, amount: u64, trigger_price: u128) -> LimitOrder {
+        LimitOrder {
+            owner: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+            order_id: 0,
+            outcome: 0,
+            side,
+            amount,
+            trigger_price,
+            min_out: 0,
+            tip: MIN_CRANK_TIP,
+            expiry: NOW + 3_600,
+            bump: 255,
+            escrow_bump: 255,
+            cost_basis: 0,
+        }
+    }
+
+    fn fill(action: CrankAction) -> TradeQuote {
+        match action {
+            CrankAction::Fill(quote) => quote,
+            _ => panic!("expected a fill"),
+        }
+    }
+
+    #[test]
+    fn buy_orders_fill_at_the_trigger_through_the_buy_quote() {
+        let config = config();
+        for market in [market(), lmsr_market(2, 1_000 * SOL)] {
+            let spot = market.spot_price(0).unwrap();
+            let below = order(OrderSide::Buy, SOL as u64, spot - 1);
+            assert!(matches!(below.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Rest));
+
+            let at = order(OrderSide::Buy, SOL as u64, spot);
+            let quote = fill(at.crank_action(&market, &config, NOW, &position(0)).unwrap());
+            let direct = market.quote_buy(&config, 0, SOL, NOW).unwrap();
+            assert_eq!((quote.shares, quote.net_sol, quote.fee), (direct.shares, direct.net_sol, direct.fee));
+        }
+    }
+
+    #[test]
+    fn sell_orders_fill_once_spot_reaches_the_trigger() {
+        let config = config();
+        let mut market = market();
+        let buy = market.quote_buy(&config, 0, 5 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+        let spot = market.spot_price(0).unwrap();
+
+        let above = order(OrderSide::Sell, (buy.shares / 2) as u64, spot + 1);
+        assert!(matches!(above.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Rest));
+
+        let at = order(OrderSide::Sell, (buy.shares / 2) as u64, spot);
+        let quote = fill(at.crank_action(&market, &config, NOW, &position(0)).unwrap());
+        let direct = market.quote_sell(&config, 0, buy.shares / 2, NOW, 0).unwrap();
+        assert_eq!((quote.net_sol, quote.fee), (direct.net_sol, direct.fee));
+    }
+
+    #[test]
+    fn sell_fills_pay_the_short_hold_surcharge() {
+        let config = config();
+        let mut market = market();
+        market.fee_schedule.min_hold_secs = 3_600;
+        market.fee_schedule.short_hold_bps = 400;
+        let buy = market.quote_buy(&config, 0, 5 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+
+        let sell = order(OrderSide::Sell, buy.shares as u64, 0);
+        let mut holder = position(0);
+        holder.last_trade_ts = NOW;
+        let fresh = fill(sell.crank_action(&market, &config, NOW + 60, &holder).unwrap());
+        holder.last_trade_ts = NOW - 3_600;
+        let held = fill(sell.crank_action(&market, &config, NOW + 60, &holder).unwrap());
+        assert!(fresh.fee > held.fee);
+    }
+
+    #[test]
+    fn orders_missing_min_out_keep_resting() {
+        let config = config();
+        let market = market();
+        let mut buy = order(OrderSide::Buy, SOL as u64, u128::MAX);
+        let shares = fill(buy.crank_action(&market, &config, NOW, &position(0)).unwrap()).shares;
+        buy.min_out = shares as u64 + 1;
+        assert!(matches!(buy.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Rest));
+        buy.min_out = shares as u64;
+        assert!(matches!(buy.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Fill(_)));
+    }
+
+    #[test]
+    fn expired_orders_and_closed_markets_refund_even_when_triggered() {
+        let config = config();
+        let mut market = market();
+        let buy = order(OrderSide::Buy, SOL as u64, u128::MAX);
+        assert!(matches!(buy.crank_action(&market, &config, buy.expiry, &position(0)).unwrap(), CrankAction::Refund));
+        assert!(matches!(
+            buy.crank_action(&market, &config, market.resolution_time, &position(0)).unwrap(),
+            CrankAction::Refund
+        ));
+        market.status = MarketStatus::Voided;
+        assert!(matches!(buy.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Refund));
+    }
+
+    #[test]
+    fn a_fill_moves_the_price_past_the_next_order_in_the_crank() {
+        let config = config();
+        let mut market = market();
+        let trigger = market.spot_price(0).unwrap();
+        let first = order(OrderSide::Buy, 10 * SOL as u64, trigger);
+        let second = order(OrderSide::Buy, 10 * SOL as u64, trigger);
+
+        let quote = fill(first.crank_action(&market, &config, NOW, &position(0)).unwrap());
+        market.apply_buy(0, &quote).unwrap();
+        assert!(matches!(second.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Rest));
+    }
+
+    #[test]
+    fn buy_fills_that_would_break_a_trading_limit_keep_resting() {
+        let config = config();
+        let mut market = market();
+        let buy = order(OrderSide::Buy, SOL as u64, u128::MAX);
+        let shares = fill(buy.crank_action(&market, &config, NOW, &position(0)).unwrap()).shares;
+
+        market.limits.max_shares_per_wallet = shares - 1;
+        assert!(matches!(buy.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Rest));
+
+        market.limits.max_shares_per_wallet = 0;
+        market.limits.cooldown_secs = 600;
+        let mut recent = position(0);
+        recent.last_trade_ts = NOW - 60;
+        assert!(matches!(buy.crank_action(&market, &config, NOW, &recent).unwrap(), CrankAction::Rest));
+        assert!(matches!(buy.crank_action(&market, &config, NOW + 540, &recent).unwrap(), CrankAction::Fill(_)));
+    }
+
+    #[test]
+    fn buy_orders_rest_through_batch_mode_while_sells_still_fill() {
+        let config = config();
+        let mut market = market();
+        let buy = market.quote_buy(&config, 0, 5 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+        market.batch_window_slots = 10;
+
+        let triggered_buy = order(OrderSide::Buy, SOL as u64, u128::MAX);
+        assert!(matches!(triggered_buy.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Rest));
+        let triggered_sell = order(OrderSide::Sell, (buy.shares / 2) as u64, 0);
+        assert!(matches!(triggered_sell.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Fill(_)));
+
+        market.batch_window_slots = 0;
+        assert!(matches!(triggered_buy.crank_action(&market, &config, NOW, &position(0)).unwrap(), CrankAction::Fill(_)));
+    }
+
+    #[test]
+    fn positions_count_their_open_sell_orders() {
+        let mut holder = position(0);
+        holder.credit(10 * SOL, SOL).unwrap();
+
+        let first = holder.escrow_sell(4 * SOL).unwrap();
+        let second = holder.escrow_sell(6 * SOL).unwrap();
+        assert_eq!((holder.open_sell_orders, first + second, holder.shares), (2, SOL, 0));
+
+        holder.refund_sell(4 * SOL, first).unwrap();
+        holder.settle_sell();
+        assert_eq!((holder.open_sell_orders, holder.shares, holder.cost_basis), (0, 4 * SOL, first));
+        assert!(holder.escrow_sell(4 * SOL + 1).is_err());
+        assert_eq!(holder.open_sell_orders, 0);
+    }
+}
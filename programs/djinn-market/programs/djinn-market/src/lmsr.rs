@@ -0,0 +1,32 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// LMSR MODULE - Logarithmic Market Scoring Rule for N-outcome markets
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// The cost function and its inverse live in djinn_curve::lmsr; this module holds
+// the on-chain pricing mode stored on each Market.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::DjinnError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PricingModel {
+    Curve,                      // Independent bonding curve per outcome (Market.curve)
+    Lmsr { liquidity_b: u128 }, // Outcomes coupled through the LMSR cost function
+}
+
+impl PricingModel {
+    pub const LEN: usize = 1 + 16; // tag + liquidity_b
+
+    pub fn validate(&self) -> Result<()> {
+        if let PricingModel::Lmsr { liquidity_b } = self {
+            require!(
+                djinn_curve::lmsr::validate_liquidity(*liquidity_b).is_ok(),
+                DjinnError::InvalidLiquidity
+            );
+        }
+        Ok(())
+    }
+}
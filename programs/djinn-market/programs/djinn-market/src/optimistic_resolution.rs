@@ -0,0 +1,162 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// OPTIMISTIC RESOLUTION MODULE - Bonded proposals with a challenge window
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Once a market reaches resolution_time, anyone can resolve it optimistically:
+//
+// 1. propose_resolution posts a winning outcome with a SOL bond of at least
+//    ProtocolConfig.min_resolution_bond, opening a challenge window of
+//    ProtocolConfig.challenge_period_secs (one ResolutionProposal PDA per market).
+// 2. Inside the window anyone else can challenge_resolution by posting a matching
+//    bond. That escalates the market to the oracle network: the challenge opens the
+//    market's BountyPool (unless one is already open) and its vote, or a
+//    cerberus_override, decides the outcome (see verification).
+// 3. finalize_resolution is permissionless. An unchallenged proposal resolves an
+//    Active market once the window closes and gets its bond back. While the market is
+//    PendingVerification the vote has the last word, so finalize waits for
+//    finalize_bounty_pool to resolve it, challenged or not. A challenged proposal then
+//    pays the loser's bond to the winner and the insurance vault
+//    (ProtocolConfig.bond_insurance_bps).
+//
+// While a proposal is unchallenged, initialize_bounty_pool refuses the market: the
+// challenge's matching bond is the only way to force a vote. Bonds are only forfeited
+// to someone who disputed them; if the market is voided in the meantime (void_market,
+// a failed condition) both bonds are refunded. Both bonds sit in a system-owned
+// "resolution_bond" PDA that also holds its own rent (paid by the proposer, who gets
+// it back with the proposal's rent).
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::protocol_config::ProtocolConfig;
+use crate::DjinnError;
+
+// --- PROPOSAL STATUS ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ProposalStatus {
+    Proposed = 0,   // Challenge window running (or closed, awaiting finalize)
+    Challenged = 1, // Escalated to the oracle network
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// RESOLUTION PROPOSAL ACCOUNT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[account]
+pub struct ResolutionProposal {
+    pub market: Pubkey,
+    pub proposer: Pubkey,
+    pub proposed_outcome: u8,
+    pub bond: u64,                   // Posted by the proposer, matched by a challenger
+    pub proposed_at: i64,
+    pub challenge_deadline: i64,     // Challenges accepted while now < deadline
+    pub status: ProposalStatus,
+    pub challenger: Pubkey,          // Default until challenged
+    pub bump: u8,
+    pub bond_bump: u8,
+}
+
+impl ResolutionProposal {
+    pub const LEN: usize = 8     // Discriminator
+        + 32                     // market
+        + 32                     // proposer
+        + 1                      // proposed_outcome
+        + 8                      // bond
+        + 8                      // proposed_at
+        + 8                      // challenge_deadline
+        + 1                      // status
+        + 32                     // challenger
+        + 1                      // bump
+        + 1;                     // bond_bump
+}
+
+impl ResolutionProposal {
+    /// What the bond escrow pays out once the market settled on `outcome` (None =
+    /// voided): (proposer, challenger, insurance). Only a challenged proposal can lose
+    /// its bond, to the challenger who was right.
+    pub fn bond_payouts(&self, outcome: Option<u8>, config: &ProtocolConfig) -> Result<(u128, u128, u128)> {
+        let bond = self.bond as u128;
+        if self.status == ProposalStatus::Proposed {
+            return Ok((bond, 0, 0));
+        }
+        let Some(outcome) = outcome else {
+            return Ok((bond, bond, 0));
+        };
+        let (winner_cut, insurance_cut) = config.split_forfeited_bond(bond)?;
+        let winner_out = bond.checked_add(winner_cut).ok_or(DjinnError::MathError)?;
+        Ok(if outcome == self.proposed_outcome {
+            (winner_out, 0, insurance_cut)
+        } else {
+            (0, winner_out, insurance_cut)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, market, NOW, SOL};
+    use crate::MarketStatus;
+
+    fn proposal(status: ProposalStatus) -> ResolutionProposal {
+        ResolutionProposal {
+            market: Pubkey::new_unique(),
+            proposer: Pubkey::new_unique(),
+            proposed_outcome: 1,
+            bond: SOL as u64,
+            proposed_at: NOW,
+            challenge_deadline: NOW + 86_400,
+            status,
+            challenger: Pubkey::new_unique(),
+            bump: 255,
+            bond_bump: 255,
+        }
+    }
+
+    #[test]
+    fn an_undisputed_bond_always_comes_back() {
+        let proposal = proposal(ProposalStatus::Proposed);
+        // Right, overruled by the admin or the vote, or voided
+        for outcome in [Some(1), Some(0), None] {
+            assert_eq!(proposal.bond_payouts(outcome, &config()).unwrap(), (SOL, 0, 0));
+        }
+    }
+
+    #[test]
+    fn a_disputed_bond_goes_to_whoever_was_right() {
+        let config = config();
+        let proposal = proposal(ProposalStatus::Challenged);
+        let (winner_cut, insurance_cut) = config.split_forfeited_bond(SOL).unwrap();
+        assert_eq!(winner_cut + insurance_cut, SOL);
+
+        assert_eq!(proposal.bond_payouts(Some(1), &config).unwrap(), (SOL + winner_cut, 0, insurance_cut));
+        assert_eq!(proposal.bond_payouts(Some(0), &config).unwrap(), (0, SOL + winner_cut, insurance_cut));
+        // A voided market refunds both sides
+        assert_eq!(proposal.bond_payouts(None, &config).unwrap(), (SOL, SOL, 0));
+    }
+
+    #[test]
+    fn only_an_expired_active_market_escalates_to_a_vote() {
+        let mut market = market();
+        assert!(market.enter_verification(None, market.resolution_time - 1).is_err());
+        market.enter_verification(None, market.resolution_time).unwrap();
+        assert!(market.status == MarketStatus::PendingVerification && market.awaiting_resolution());
+        // Once verifying, a second escalation (challenge or pool) can't reopen it
+        assert!(market.enter_verification(None, market.resolution_time).is_err());
+    }
+
+    #[test]
+    fn a_pending_proposal_escalates_only_through_a_challenge() {
+        let mut market = market();
+        let mut proposal = proposal(ProposalStatus::Proposed);
+        // Inside the challenge window and after it: opening a pool can't skip the bond
+        for now in [market.resolution_time, proposal.challenge_deadline + 1] {
+            assert!(market.enter_verification(Some(&proposal), now).is_err());
+            assert!(market.status == MarketStatus::Active);
+        }
+        proposal.status = ProposalStatus::Challenged;
+        market.enter_verification(Some(&proposal), market.resolution_time).unwrap();
+        assert!(market.status == MarketStatus::PendingVerification);
+    }
+}
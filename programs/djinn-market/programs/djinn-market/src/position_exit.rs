@@ -0,0 +1,149 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// POSITION EXIT MODULE - Stop-loss / take-profit thresholds on a UserPosition
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// An owner attaches thresholds to a position with set_exit_thresholds, measured
+// either in the outcome's spot price or in the position's SOL value (what selling
+// every share would net right now, after the vault clamp and exit fee):
+// - stop_loss:   exit once the measure falls to or below it
+// - take_profit: exit once the measure rises to or above it
+// A threshold of 0 is unset.
+//
+// execute_exit is permissionless. A keeper sells the whole position through the
+// sell_shares path, keeps up to keeper_tip out of the proceeds and must leave the
+// owner at least the min_sol_out set in advance. Thresholds are one-shot: they are
+// cleared once the exit runs.
+//
+// Thresholds are tested against what the exit itself realises, never the pre-trade
+// spot: SpotPrice against the sale's average net price per share, SolValue against
+// its net proceeds. Pushing the price across a threshold then only pays off if the
+// exit really fills there. The tip is also capped at MAX_KEEPER_TIP_BPS of the
+// proceeds (never below MIN_CRANK_TIP), so triggering someone's exit earns little.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use djinn_curve::fees::fee_from_bps;
+
+use crate::limit_order::MIN_CRANK_TIP;
+use crate::quote::TradeQuote;
+use crate::{curve_result, DjinnError, SHARE_SCALE};
+
+// Most a keeper may take from an exit's proceeds (1%), whatever the owner offered
+pub const MAX_KEEPER_TIP_BPS: u128 = 100;
+
+// --- THRESHOLD UNIT ---
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ThresholdKind {
+    #[default]
+    SpotPrice = 0, // Lamports per whole share of the position's outcome
+    SolValue = 1,  // Net lamports from selling the whole position
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExitThresholds {
+    pub kind: ThresholdKind,
+    pub stop_loss: u128,   // 0 = unset
+    pub take_profit: u128, // 0 = unset
+    pub min_sol_out: u64,  // Owner's floor on net proceeds after the keeper tip
+    pub keeper_tip: u64,   // Max lamports the keeper keeps from the proceeds
+}
+
+impl ExitThresholds {
+    // 1 (kind) + 16 (stop_loss) + 16 (take_profit) + 8 (min_sol_out) + 8 (keeper_tip)
+    pub const LEN: usize = 1 + 16 + 16 + 8 + 8;
+
+    pub fn is_set(&self) -> bool {
+        self.stop_loss > 0 || self.take_profit > 0
+    }
+
+    /// Whether `measure` (in `kind` units) has crossed either threshold
+    pub fn is_crossed(&self, measure: u128) -> bool {
+        (self.stop_loss > 0 && measure <= self.stop_loss)
+            || (self.take_profit > 0 && measure >= self.take_profit)
+    }
+
+    /// What the exit priced by `quote` realises, in `kind` units: the average net
+    /// lamports per whole share, or the net lamports
+    pub fn measure(&self, quote: &TradeQuote) -> Result<u128> {
+        match self.kind {
+            ThresholdKind::SpotPrice => {
+                require!(quote.shares > 0, DjinnError::NoShares);
+                Ok(quote.net_sol.checked_mul(SHARE_SCALE).ok_or(DjinnError::MathError)? / quote.shares)
+            }
+            ThresholdKind::SolValue => Ok(quote.net_sol),
+        }
+    }
+
+    /// The keeper's cut of `proceeds`: keeper_tip, capped at MAX_KEEPER_TIP_BPS of the
+    /// proceeds but never below MIN_CRANK_TIP, and never more than the proceeds
+    pub fn tip_for(&self, proceeds: u128) -> Result<u128> {
+        let cap = curve_result(fee_from_bps(proceeds, MAX_KEEPER_TIP_BPS))?.max(MIN_CRANK_TIP as u128);
+        Ok((self.keeper_tip as u128).min(cap).min(proceeds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, market, NOW, SOL};
+    use crate::Market;
+
+    fn thresholds(kind: ThresholdKind, stop_loss: u128, take_profit: u128) -> ExitThresholds {
+        ExitThresholds { kind, stop_loss, take_profit, min_sol_out: 0, keeper_tip: 1_000_000 }
+    }
+
+    /// A market holding one large position of outcome 0, and its full-exit quote
+    fn holder() -> (Market, TradeQuote) {
+        let config = config();
+        let mut market = market();
+        let buy = market.quote_buy(&config, 0, 20 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+        let exit = market.quote_sell(&config, 0, buy.shares, NOW, 0).unwrap();
+        (market, exit)
+    }
+
+    #[test]
+    fn spot_thresholds_measure_the_realised_exit_price() {
+        let (_, exit) = holder();
+        let realised = exit.net_sol * SHARE_SCALE / exit.shares;
+        // The sale walks the curve down: the owner realises less than the pre-trade spot
+        assert!(realised < exit.spot_price_before);
+
+        let spot = thresholds(ThresholdKind::SpotPrice, 0, 0);
+        assert_eq!(spot.measure(&exit).unwrap(), realised);
+        let value = thresholds(ThresholdKind::SolValue, 0, 0);
+        assert_eq!(value.measure(&exit).unwrap(), exit.net_sol);
+    }
+
+    #[test]
+    fn a_pre_trade_spot_above_the_take_profit_does_not_trigger_an_exit_below_it() {
+        let (_, exit) = holder();
+        let take_profit = thresholds(ThresholdKind::SpotPrice, 0, exit.spot_price_before);
+        assert!(!take_profit.is_crossed(take_profit.measure(&exit).unwrap()));
+
+        let realised = take_profit.measure(&exit).unwrap();
+        let reachable = thresholds(ThresholdKind::SpotPrice, 0, realised);
+        assert!(reachable.is_crossed(reachable.measure(&exit).unwrap()));
+    }
+
+    #[test]
+    fn stop_loss_fires_once_the_exit_realises_at_or_below_it() {
+        let (_, exit) = holder();
+        let realised = exit.net_sol * SHARE_SCALE / exit.shares;
+        assert!(thresholds(ThresholdKind::SpotPrice, realised, 0).is_crossed(realised));
+        assert!(!thresholds(ThresholdKind::SpotPrice, realised - 1, 0).is_crossed(realised));
+    }
+
+    #[test]
+    fn keeper_tips_are_capped_at_a_share_of_the_proceeds() {
+        let mut exit = thresholds(ThresholdKind::SolValue, 1, 0);
+        exit.keeper_tip = u64::MAX;
+        assert_eq!(exit.tip_for(10 * SOL).unwrap(), 10 * SOL / 100);
+        // Small exits still pay a signature's worth, but never more than they raise
+        assert_eq!(exit.tip_for(100_000).unwrap(), MIN_CRANK_TIP as u128);
+        assert_eq!(exit.tip_for(1_000).unwrap(), 1_000);
+        exit.keeper_tip = 7_000;
+        assert_eq!(exit.tip_for(10 * SOL).unwrap(), 7_000);
+    }
+}
@@ -0,0 +1,202 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROTOCOL CONFIG MODULE - Governance-editable fees and revenue splits
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// One global ProtocolConfig PDA (seeds ["protocol_config"]) holds the protocol's
+// economics, so changing them is a transaction by the governance authority rather
+// than a program upgrade:
+// - fee bps: default market entry / exit fees (copied into each new market's
+//   FeeSchedule), the Chronos entry fee and the resolution fee
+// - split bps: trading fees (creator / insurance, treasury keeps the rest; on G1
+//   created markets the creator cut also goes to the treasury), the referrer's cut of
+//   the treasury share on referred trades, resolution fees
+//   (treasury, bounty pool keeps the rest), vault profits (depositors / bot owner,
+//   insurance keeps the rest), slashed stakes (insurance, reporter keeps the rest)
+//   and forfeited resolution bonds (insurance, the dispute's winner keeps the rest)
+// - optimistic resolution: the minimum proposal bond and the challenge period
+// - oracle verification: the bounty the insurance vault funds each BountyPool with,
+//   the vote weight a pool needs to resolve a market and the share of it the winning
+//   outcome must hold
+//
+// Every split rounds each cut down and gives the remainder to the last recipient,
+// so the parts always sum to the amount. Funds move through the shared route_fee.
+//
+// When an update takes effect:
+// - market entry / exit fees are snapshotted: initialize_market copies them into the
+//   new market's FeeSchedule, which the creator owns from then on. Updating the
+//   config never reprices an existing market; it sets the default for new ones.
+// - everything else is read at the instruction that uses it: the Chronos entry fee,
+//   the resolution fee, every split, bonds, challenge periods and vote thresholds
+//   apply to existing markets from the next instruction on.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use djinn_curve::fees::{
+    fee_from_bps, FeeSplit, BPS_DENOMINATOR, CREATOR_FEE_PCT, ENTRY_FEE_BPS, EXIT_FEE_BPS, INSURANCE_FEE_PCT,
+    MAX_TRADING_FEE_BPS, RESOLUTION_FEE_BPS,
+};
+
+use crate::{curve_result, DjinnError};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolParams {
+    pub entry_fee_bps: u16,           // New markets' entry fee (snapshotted), Chronos entry fee
+    pub exit_fee_bps: u16,            // New markets' exit fee (snapshotted)
+    pub resolution_fee_bps: u16,      // Of the pot at resolution
+    pub creator_fee_bps: u16,         // Of each trading fee
+    pub insurance_fee_bps: u16,       // Of each trading fee (treasury keeps the rest)
+    pub resolution_treasury_bps: u16, // Of each resolution fee (bounty pool keeps the rest)
+    pub vault_depositor_bps: u16,     // Of vault profits
+    pub vault_bot_owner_bps: u16,     // Of vault profits (insurance keeps the rest)
+    pub slash_insurance_bps: u16,     // Of a slashed stake (reporter keeps the rest)
+    pub referral_bps: u16,            // Of the treasury's trading-fee share on referred trades
+    pub bond_insurance_bps: u16,      // Of a forfeited resolution bond (winner keeps the rest)
+    pub verification_majority_bps: u16, // Share of the vote weight the winning outcome must exceed
+    pub min_resolution_bond: u64,     // Lamports a resolution proposal must post
+    pub challenge_period_secs: i64,   // How long a proposal can be challenged
+    pub verification_quorum: u64,     // Total vote weight a BountyPool needs to resolve a market
+    pub verification_bounty: u64,     // Lamports the insurance vault funds each BountyPool with
+}
+
+impl ProtocolParams {
+    // 12 * 2 (bps fields) + 8 (min_resolution_bond) + 8 (challenge_period_secs)
+    // + 8 (verification_quorum) + 8 (verification_bounty)
+    pub const LEN: usize = 12 * 2 + 8 + 8 + 8 + 8;
+
+    /// The economics the program launched with: 1% / 1% / 2% fees, 40/50/10 trading
+    /// split, 50/50 resolution split, 70/20/10 vault profits, 80/20 slashes, a 20%
+    /// referral cut of the treasury share, 1 SOL bonds challengeable for 24h whose
+    /// forfeits split 50/50, and verification votes for a 0.5 SOL bounty needing 100
+    /// weight (~100 SOL of confident stake) with a strict majority
+    pub fn launch() -> Self {
+        ProtocolParams {
+            entry_fee_bps: ENTRY_FEE_BPS as u16,
+            exit_fee_bps: EXIT_FEE_BPS as u16,
+            resolution_fee_bps: RESOLUTION_FEE_BPS as u16,
+            creator_fee_bps: (CREATOR_FEE_PCT * 100) as u16,
+            insurance_fee_bps: (INSURANCE_FEE_PCT * 100) as u16,
+            resolution_treasury_bps: 5_000,
+            vault_depositor_bps: 7_000,
+            vault_bot_owner_bps: 2_000,
+            slash_insurance_bps: 8_000,
+            referral_bps: 2_000,
+            bond_insurance_bps: 5_000,
+            verification_majority_bps: 5_000,
+            min_resolution_bond: 1_000_000_000,
+            challenge_period_secs: 86_400,
+            verification_quorum: 100,
+            verification_bounty: 500_000_000,
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        let full = BPS_DENOMINATOR as u32;
+        let max_fee = MAX_TRADING_FEE_BPS as u16;
+        require!(
+            self.entry_fee_bps <= max_fee && self.exit_fee_bps <= max_fee && self.resolution_fee_bps <= max_fee,
+            DjinnError::InvalidProtocolConfig
+        );
+        require!(
+            self.creator_fee_bps as u32 + self.insurance_fee_bps as u32 <= full
+                && self.vault_depositor_bps as u32 + self.vault_bot_owner_bps as u32 <= full
+                && self.resolution_treasury_bps as u32 <= full
+                && self.slash_insurance_bps as u32 <= full
+                && self.referral_bps as u32 <= full
+                && self.bond_insurance_bps as u32 <= full,
+            DjinnError::InvalidProtocolConfig
+        );
+        require!(
+            self.min_resolution_bond > 0 && self.challenge_period_secs > 0,
+            DjinnError::InvalidProtocolConfig
+        );
+        // Below half, two outcomes could both hold a "majority"
+        require!(
+            self.verification_majority_bps as u32 >= full / 2
+                && (self.verification_majority_bps as u32) < full
+                && self.verification_quorum > 0,
+            DjinnError::InvalidProtocolConfig
+        );
+        Ok(())
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PROTOCOL CONFIG ACCOUNT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[account]
+pub struct ProtocolConfig {
+    pub authority: Pubkey,       // Governance: the only signer allowed to edit
+    pub params: ProtocolParams,
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    // 8 (discriminator) + 32 (authority) + params + 1 (bump)
+    pub const LEN: usize = 8 + 32 + ProtocolParams::LEN + 1;
+
+    /// Split a trading fee into creator / treasury / insurance
+    pub fn split_trading_fee(&self, fee: u128, creator_is_treasury: bool) -> Result<FeeSplit> {
+        let insurance = bps_of(fee, self.params.insurance_fee_bps)?;
+        let creator = if creator_is_treasury { 0 } else { bps_of(fee, self.params.creator_fee_bps)? };
+        Ok(FeeSplit { creator, treasury: fee - creator - insurance, insurance })
+    }
+
+    /// Split a trading fee's treasury share into (treasury, referrer)
+    pub fn split_referral(&self, treasury_fee: u128) -> Result<(u128, u128)> {
+        let referrer = bps_of(treasury_fee, self.params.referral_bps)?;
+        Ok((treasury_fee - referrer, referrer))
+    }
+
+    /// Split a resolution fee into (treasury, bounty pool)
+    pub fn split_resolution_fee(&self, fee: u128) -> Result<(u128, u128)> {
+        let treasury = bps_of(fee, self.params.resolution_treasury_bps)?;
+        Ok((treasury, fee - treasury))
+    }
+
+    /// Split vault profits into (depositors, bot owner, insurance)
+    pub fn split_vault_profit(&self, profit: u128) -> Result<(u128, u128, u128)> {
+        let depositors = bps_of(profit, self.params.vault_depositor_bps)?;
+        let bot_owner = bps_of(profit, self.params.vault_bot_owner_bps)?;
+        Ok((depositors, bot_owner, profit - depositors - bot_owner))
+    }
+
+    /// Split a forfeited resolution bond into (dispute winner, insurance)
+    pub fn split_forfeited_bond(&self, bond: u128) -> Result<(u128, u128)> {
+        let insurance = bps_of(bond, self.params.bond_insurance_bps)?;
+        Ok((bond - insurance, insurance))
+    }
+
+    /// Split a slashed stake into (insurance, reporter)
+    pub fn split_slash(&self, stake: u128) -> Result<(u128, u128)> {
+        let insurance = bps_of(stake, self.params.slash_insurance_bps)?;
+        Ok((insurance, stake - insurance))
+    }
+}
+
+fn bps_of(amount: u128, bps: u16) -> Result<u128> {
+    curve_result(fee_from_bps(amount, bps as u128))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_fixtures::{config, market, NOW, SOL};
+
+    #[test]
+    fn updates_reach_splits_but_not_an_existing_markets_fees() {
+        let mut config = config();
+        let market = market();
+        let before = market.quote_buy(&config, 0, SOL, NOW).unwrap();
+
+        config.params.entry_fee_bps *= 3;
+        config.params.insurance_fee_bps *= 2;
+        let after = market.quote_buy(&config, 0, SOL, NOW).unwrap();
+
+        // The market's snapshotted FeeSchedule still prices the trade...
+        assert_eq!((after.fee, after.shares), (before.fee, before.shares));
+        // ...while the split of that fee follows the live config
+        assert_eq!(after.insurance_fee, 2 * before.insurance_fee);
+        assert_eq!(after.creator_fee + after.treasury_fee + after.insurance_fee, after.fee);
+    }
+}
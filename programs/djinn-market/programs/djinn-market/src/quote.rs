@@ -0,0 +1,411 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// QUOTE MODULE - Read-only trade previews
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// buy_shares / buy_exact_shares / sell_shares / sell_for_sol / swap_outcome price
+// every trade through the Market::quote_* methods, and the matching quote_* instructions
+// return the same TradeQuote through return data. Clients simulate the quote
+// against live Market state and derive min_shares_out / min_sol_out from it
+// instead of reimplementing the fee and curve math.
+//
+// Fees follow the market's FeeSchedule at `now`; sells also take the seller's last
+// buy time (`last_buy_ts`, 0 = unknown) for the short-hold exit surcharge. The fee
+// split comes from the global ProtocolConfig.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use djinn_curve::fees::{fee_for_net, fee_from_bps, price_impact_bps, refund_for_net};
+
+use crate::protocol_config::ProtocolConfig;
+use crate::trading_limits::TradingLimits;
+use crate::{curve_result, DjinnError, Market, G1_TREASURY};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TradeQuote {
+    pub shares: u128,            // Shares bought (buy) or burned (sell)
+    pub net_sol: u128,           // Lamports into the vault (buy) or out to the user (sell)
+    pub fee: u128,               // Total trading fee in lamports
+    pub creator_fee: u128,       // ProtocolConfig creator cut (0 on G1 created markets)
+    pub treasury_fee: u128,      // The rest
+    pub insurance_fee: u128,     // ProtocolConfig insurance cut
+    pub spot_price_before: u128, // Lamports per whole share before the trade
+    pub spot_price_after: u128,  // Lamports per whole share after the trade
+    pub price_impact_bps: u128,  // |after - before| / before
+    pub limits: TradingLimits,   // The market's buy limits (0 = off)
+}
+
+impl Market {
+    /// What buy_shares does with `sol_in` lamports: entry fee, then shares for the rest
+    pub fn quote_buy(&self, config: &ProtocolConfig, outcome_index: u8, sol_in: u128, now: i64) -> Result<TradeQuote> {
+        let fee = curve_result(fee_from_bps(sol_in, self.entry_fee_bps(now)))?;
+        let net_sol = sol_in - fee;
+        let shares = self.shares_for_sol(outcome_index, net_sol)?;
+
+        let mut supplies = self.outcome_supplies;
+        let outcome = outcome_index as usize;
+        supplies[outcome] = supplies[outcome].checked_add(shares).ok_or(DjinnError::MathError)?;
+        self.finish_quote(config, outcome_index, shares, net_sol, fee, &supplies)
+    }
+
+    /// What buy_exact_shares charges for `shares`: curve cost plus the grossed-up entry fee
+    pub fn quote_buy_exact(
+        &self,
+        config: &ProtocolConfig,
+        outcome_index: u8,
+        shares: u128,
+        now: i64,
+    ) -> Result<TradeQuote> {
+        let net_sol = self.sol_to_buy(outcome_index, shares)?;
+        let fee = curve_result(fee_for_net(net_sol, self.entry_fee_bps(now)))?;
+
+        let mut supplies = self.outcome_supplies;
+        let outcome = outcome_index as usize;
+        supplies[outcome] = supplies[outcome].checked_add(shares).ok_or(DjinnError::MathError)?;
+        self.finish_quote(config, outcome_index, shares, net_sol, fee, &supplies)
+    }
+
+    /// What sell_shares does with `shares`: curve refund clamped to the vault, then exit fee
+    pub fn quote_sell(
+        &self,
+        config: &ProtocolConfig,
+        outcome_index: u8,
+        shares: u128,
+        now: i64,
+        last_buy_ts: i64,
+    ) -> Result<TradeQuote> {
+        let refund_gross = self.sol_for_shares(outcome_index, shares)?;
+
+        // SAFETY CLAMP: never refund more than what's in the vault
+        let actual_refund = refund_gross.min(self.vault_balance);
+        let fee = curve_result(fee_from_bps(actual_refund, self.exit_fee_bps(now, last_buy_ts)))?;
+
+        let mut supplies = self.outcome_supplies;
+        let outcome = outcome_index as usize;
+        supplies[outcome] = supplies[outcome].checked_sub(shares).ok_or(DjinnError::InsufficientShares)?;
+        self.finish_quote(config, outcome_index, shares, actual_refund - fee, fee, &supplies)
+    }
+
+    /// What sell_for_sol burns to net `sol_out`: the refund grossed up for the exit fee,
+    /// then the fewest shares releasing it, priced through quote_sell
+    pub fn quote_sell_for_sol(
+        &self,
+        config: &ProtocolConfig,
+        outcome_index: u8,
+        sol_out: u128,
+        now: i64,
+        last_buy_ts: i64,
+    ) -> Result<TradeQuote> {
+        let refund = curve_result(refund_for_net(sol_out, self.exit_fee_bps(now, last_buy_ts)))?;
+
+        // SAFETY CLAMP: sell_shares never pays out more than the vault holds
+        require!(refund <= self.vault_balance, DjinnError::InsufficientVaultBalance);
+
+        let shares = self.shares_to_release(outcome_index, refund)?;
+        self.quote_sell(config, outcome_index, shares, now, last_buy_ts)
+    }
+
+    /// What swap_outcome does with `shares_in` of `from_index`: the sell leg's refund
+    /// (clamped to the vault) pays a single exit fee, and the rest buys `to_index`
+    /// against the supplies left by the sell leg. `shares` is the `to_index` side.
+    pub fn quote_swap(
+        &self,
+        config: &ProtocolConfig,
+        from_index: u8,
+        to_index: u8,
+        shares_in: u128,
+        now: i64,
+        last_buy_ts: i64,
+    ) -> Result<TradeQuote> {
+        let refund_gross = self.sol_for_shares(from_index, shares_in)?;
+        let actual_refund = refund_gross.min(self.vault_balance);
+        let fee = curve_result(fee_from_bps(actual_refund, self.exit_fee_bps(now, last_buy_ts)))?;
+        let net_sol = actual_refund - fee;
+
+        let mut after_sell = self.clone();
+        let from = from_index as usize;
+        after_sell.outcome_supplies[from] =
+            self.outcome_supplies[from].checked_sub(shares_in).ok_or(DjinnError::InsufficientShares)?;
+        let shares = after_sell.shares_for_sol(to_index, net_sol)?;
+
+        let mut supplies = after_sell.outcome_supplies;
+        let to = to_index as usize;
+        supplies[to] = supplies[to].checked_add(shares).ok_or(DjinnError::MathError)?;
+        self.finish_quote(config, to_index, shares, net_sol, fee, &supplies)
+    }
+
+    /// Fee-only TradeQuote, for routing fees collected outside a single trade
+    pub fn quote_fee(&self, config: &ProtocolConfig, fee: u128) -> Result<TradeQuote> {
+        let split = config.split_trading_fee(fee, self.creator == G1_TREASURY)?;
+        Ok(TradeQuote {
+            fee,
+            creator_fee: split.creator,
+            treasury_fee: split.treasury,
+            insurance_fee: split.insurance,
+            limits: self.limits,
+            ..TradeQuote::default()
+        })
+    }
+
+    fn finish_quote(
+        &self,
+        config: &ProtocolConfig,
+        outcome_index: u8,
+        shares: u128,
+        net_sol: u128,
+        fee: u128,
+        supplies_after: &[u128; 6],
+    ) -> Result<TradeQuote> {
+        let split = config.split_trading_fee(fee, self.creator == G1_TREASURY)?;
+        let spot_price_before = self.spot_price(outcome_index)?;
+        let spot_price_after = self.spot_price_at(&supplies_after[..self.num_outcomes as usize], outcome_index)?;
+
+        Ok(TradeQuote {
+            shares,
+            net_sol,
+            fee,
+            creator_fee: split.creator,
+            treasury_fee: split.treasury,
+            insurance_fee: split.insurance,
+            spot_price_before,
+            spot_price_after,
+            price_impact_bps: curve_result(price_impact_bps(spot_price_before, spot_price_after))?,
+            limits: self.limits,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, lmsr_market, market, NOW, SOL};
+
+    fn markets() -> [Market; 2] {
+        [market(), lmsr_market(3, 1_000 * SOL)]
+    }
+
+    fn assert_split(quote: &TradeQuote) {
+        assert_eq!(quote.creator_fee + quote.treasury_fee + quote.insurance_fee, quote.fee);
+    }
+
+    #[test]
+    fn buy_quote_matches_the_executed_buy() {
+        let config = config();
+        for mut market in markets() {
+            for sol_in in [1_000u128, SOL / 10, 5 * SOL] {
+                let quote = market.quote_buy(&config, 1, sol_in, NOW).unwrap();
+                assert_eq!(quote.net_sol + quote.fee, sol_in);
+                assert_split(&quote);
+
+                let (supply, vault) = (market.outcome_supplies[1], market.vault_balance);
+                assert_eq!(quote.spot_price_before, market.spot_price(1).unwrap());
+                market.apply_buy(1, &quote).unwrap();
+                assert_eq!(market.outcome_supplies[1], supply + quote.shares);
+                assert_eq!(market.vault_balance, vault + quote.net_sol);
+                assert_eq!(quote.spot_price_after, market.spot_price(1).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn buy_exact_quote_costs_no_more_than_a_buy_of_the_same_size() {
+        let config = config();
+        for market in markets() {
+            for shares in [SOL, 1_000 * SOL, 1_000_000 * SOL] {
+                let exact = market.quote_buy_exact(&config, 0, shares, NOW).unwrap();
+                assert_eq!(exact.shares, shares);
+                assert_eq!(exact.net_sol, market.sol_to_buy(0, shares).unwrap());
+                assert_split(&exact);
+
+                // Spending the quoted total through quote_buy fills at least as many shares
+                let filled = market.quote_buy(&config, 0, exact.net_sol + exact.fee, NOW).unwrap();
+                assert!(filled.shares >= shares);
+            }
+        }
+    }
+
+    #[test]
+    fn buy_exact_quote_fills_exactly_and_grosses_up_the_fee() {
+        let config = config();
+        for mut market in markets() {
+            let shares = 250 * SOL;
+            let quote = market.quote_buy_exact(&config, 1, shares, NOW).unwrap();
+            let sol_in = quote.net_sol + quote.fee;
+            // The entry fee buy_shares would take on the same total is never higher
+            assert!(fee_from_bps(sol_in, market.entry_fee_bps(NOW)).unwrap() <= quote.fee);
+
+            market.apply_buy(1, &quote).unwrap();
+            assert_eq!(market.outcome_supplies[1], shares);
+            assert_eq!(market.vault_balance, quote.net_sol);
+            assert_eq!(quote.spot_price_after, market.spot_price(1).unwrap());
+        }
+    }
+
+    #[test]
+    fn buy_exact_quote_picks_up_the_late_entry_ramp() {
+        let config = config();
+        let mut market = market();
+        market.fee_schedule.ramp_window_secs = 3_600;
+        market.fee_schedule.ramp_max_bps = 500;
+        let early = market.quote_buy_exact(&config, 0, 100 * SOL, NOW).unwrap();
+        let late = market.quote_buy_exact(&config, 0, 100 * SOL, market.resolution_time - 60).unwrap();
+        assert_eq!(early.net_sol, late.net_sol);
+        assert!(late.fee > early.fee);
+    }
+
+    #[test]
+    fn sell_quote_matches_the_executed_sell() {
+        let config = config();
+        for mut market in markets() {
+            let buy = market.quote_buy(&config, 0, 2 * SOL, NOW).unwrap();
+            market.apply_buy(0, &buy).unwrap();
+
+            let sell = market.quote_sell(&config, 0, buy.shares, NOW, NOW).unwrap();
+            assert_split(&sell);
+            assert!(sell.net_sol + sell.fee <= buy.net_sol);
+            market.apply_sell(0, &sell).unwrap();
+            assert_eq!(market.outcome_supplies[0], 0);
+            assert_eq!(market.vault_balance, buy.net_sol - sell.net_sol - sell.fee);
+            assert_eq!(sell.spot_price_after, market.spot_price(0).unwrap());
+        }
+    }
+
+    #[test]
+    fn sell_for_sol_quote_nets_at_least_the_target() {
+        let config = config();
+        for mut market in markets() {
+            let buy = market.quote_buy(&config, 0, 3 * SOL, NOW).unwrap();
+            market.apply_buy(0, &buy).unwrap();
+
+            for sol_out in [1u128, SOL / 1_000, SOL] {
+                let sell = market.quote_sell_for_sol(&config, 0, sol_out, NOW, 0).unwrap();
+                assert!(sell.net_sol >= sol_out);
+                // The fewest shares releasing the grossed-up refund
+                let refund = refund_for_net(sol_out, market.exit_fee_bps(NOW, 0)).unwrap();
+                assert!(market.sol_for_shares(0, sell.shares - 1).unwrap() < refund);
+            }
+            assert!(market.quote_sell_for_sol(&config, 0, buy.net_sol + 1, NOW, 0).is_err());
+        }
+    }
+
+    #[test]
+    fn sell_for_sol_pays_the_target_out_of_the_vault() {
+        let config = config();
+        for mut market in markets() {
+            let buy = market.quote_buy(&config, 1, 4 * SOL, NOW).unwrap();
+            market.apply_buy(1, &buy).unwrap();
+
+            let sol_out = SOL / 2;
+            let sell = market.quote_sell_for_sol(&config, 1, sol_out, NOW, 0).unwrap();
+            let vault = market.vault_balance;
+            market.apply_sell(1, &sell).unwrap();
+            assert_eq!(market.outcome_supplies[1], buy.shares - sell.shares);
+            assert_eq!(market.vault_balance, vault - sell.net_sol - sell.fee);
+            // One share unit is worth under a lamport here, so the overshoot is rounding only
+            assert!(sell.net_sol - sol_out <= 2);
+        }
+    }
+
+    #[test]
+    fn sell_for_sol_burns_more_shares_under_the_short_hold_surcharge() {
+        let config = config();
+        let mut market = market();
+        market.fee_schedule.min_hold_secs = 3_600;
+        market.fee_schedule.short_hold_bps = 400;
+        let buy = market.quote_buy(&config, 0, 2 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+
+        let held = market.quote_sell_for_sol(&config, 0, SOL / 2, NOW + 3_600, NOW).unwrap();
+        let flipped = market.quote_sell_for_sol(&config, 0, SOL / 2, NOW + 60, NOW).unwrap();
+        assert!(held.net_sol >= SOL / 2 && flipped.net_sol >= SOL / 2);
+        assert!(flipped.shares > held.shares);
+    }
+
+    #[test]
+    fn sell_for_sol_never_asks_the_vault_for_more_than_it_holds() {
+        let config = config();
+        let mut market = market();
+        let buy = market.quote_buy(&config, 0, SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+        // A clamped vault (e.g. after a partial refund) caps what can be netted
+        market.vault_balance /= 2;
+        assert!(market.quote_sell_for_sol(&config, 0, market.vault_balance, NOW, 0).is_err());
+        assert!(market.quote_sell_for_sol(&config, 0, market.vault_balance / 2, NOW, 0).is_ok());
+    }
+
+    #[test]
+    fn swap_quote_is_a_sell_then_a_fee_free_buy() {
+        let config = config();
+        for mut market in markets() {
+            let buy = market.quote_buy(&config, 0, 2 * SOL, NOW).unwrap();
+            market.apply_buy(0, &buy).unwrap();
+
+            let swap = market.quote_swap(&config, 0, 1, buy.shares / 2, NOW, 0).unwrap();
+            let sell = market.quote_sell(&config, 0, buy.shares / 2, NOW, 0).unwrap();
+            assert_eq!((swap.net_sol, swap.fee), (sell.net_sol, sell.fee));
+            assert_split(&swap);
+
+            market.apply_sell(0, &sell).unwrap();
+            assert_eq!(swap.shares, market.shares_for_sol(1, sell.net_sol).unwrap());
+        }
+    }
+
+    #[test]
+    fn swap_quote_matches_the_executed_swap() {
+        let config = config();
+        for mut market in markets() {
+            let buy = market.quote_buy(&config, 0, 3 * SOL, NOW).unwrap();
+            market.apply_buy(0, &buy).unwrap();
+
+            let vault = market.vault_balance;
+            let swap = market.quote_swap(&config, 0, 1, buy.shares, NOW, 0).unwrap();
+            market.apply_swap(0, 1, buy.shares, &swap).unwrap();
+            assert_eq!(market.outcome_supplies[..2], [0, swap.shares]);
+            assert_eq!(market.vault_balance, vault - swap.fee);
+            assert_eq!(swap.spot_price_after, market.spot_price(1).unwrap());
+        }
+    }
+
+    #[test]
+    fn swap_charges_one_exit_fee_not_a_round_trip() {
+        let config = config();
+        let mut market = market();
+        let buy = market.quote_buy(&config, 0, 2 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+
+        let swap = market.quote_swap(&config, 0, 1, buy.shares, NOW, 0).unwrap();
+        let sell = market.quote_sell(&config, 0, buy.shares, NOW, 0).unwrap();
+        market.apply_sell(0, &sell).unwrap();
+        let rebuy = market.quote_buy(&config, 1, sell.net_sol, NOW).unwrap();
+        assert_eq!(swap.fee, sell.fee);
+        assert!(swap.shares > rebuy.shares);
+    }
+
+    #[test]
+    fn quotes_follow_the_fee_schedule() {
+        let config = config();
+        let mut market = market();
+        market.fee_schedule.min_hold_secs = 3_600;
+        market.fee_schedule.short_hold_bps = 400;
+        let buy = market.quote_buy(&config, 0, SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+
+        let held = market.quote_sell(&config, 0, buy.shares, NOW + 3_600, NOW).unwrap();
+        let flipped = market.quote_sell(&config, 0, buy.shares, NOW + 60, NOW).unwrap();
+        assert_eq!(flipped.net_sol + flipped.fee, held.net_sol + held.fee);
+        assert!(flipped.fee > held.fee);
+    }
+
+    #[test]
+    fn g1_markets_route_the_creator_cut_to_the_treasury() {
+        let config = config();
+        let mut market = market();
+        let user = market.quote_fee(&config, 1_000_000).unwrap();
+        market.creator = G1_TREASURY;
+        let g1 = market.quote_fee(&config, 1_000_000).unwrap();
+        assert!(user.creator_fee > 0);
+        assert_eq!(g1.creator_fee, 0);
+        assert_eq!(g1.treasury_fee, user.treasury_fee + user.creator_fee);
+        assert_eq!(g1.insurance_fee, user.insurance_fee);
+    }
+}
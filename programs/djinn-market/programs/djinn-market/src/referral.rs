@@ -0,0 +1,151 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// REFERRAL MODULE - Fee sharing with the frontends that route trades
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// An integrator (web UI, Telegram bot, ...) registers a Referrer PDA (seeds
+// ["referrer", owner]) and passes it, with its "referral_vault", on the trades it
+// routes. buy_shares / buy_exact_shares / sell_shares / sell_for_sol / swap_outcome
+// then carve ProtocolConfig.referral_bps out of the treasury's share of the trading
+// fee (the creator and insurance cuts are untouched) and pay it into the referral vault.
+//
+// Only trades the trader signs carry a referrer. Fees charged when a keeper cranks a
+// limit order, DCA interval or exit threshold, and the pooled fee of a settled batch,
+// go to the treasury in full: the trader is not there to name a referrer, and letting
+// the keeper name one would let it pay the cut to itself.
+//
+// The vault is a system-owned PDA that holds its own rent, so any cut can land in it;
+// claim_referral_fees pays the owner everything accrued since the last claim.
+// Referrers also track the volume and trade count they routed. Trading through your
+// own Referrer is rejected.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::protocol_config::ProtocolConfig;
+use crate::quote::TradeQuote;
+use crate::DjinnError;
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// REFERRER ACCOUNT
+// ═══════════════════════════════════════════════════════════════════════════════
+
+#[account]
+pub struct Referrer {
+    pub owner: Pubkey,
+    pub total_volume: u128,      // Lamports traded through this referrer (fees included)
+    pub trade_count: u64,
+    pub total_earned: u64,       // Lifetime referral fees
+    pub unclaimed: u64,          // Waiting in the referral vault
+    pub created_at: i64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+impl Referrer {
+    pub const LEN: usize = 8     // Discriminator
+        + 32                     // owner
+        + 16                     // total_volume
+        + 8                      // trade_count
+        + 8                      // total_earned
+        + 8                      // unclaimed
+        + 8                      // created_at
+        + 1                      // bump
+        + 1;                     // vault_bump
+}
+
+/// Take the referrer's cut of a priced trade, if one was passed. Returns the quote to
+/// route (treasury share reduced by the cut) and the referral vault with its cut.
+pub fn take_referral<'info>(
+    referrer: &mut Option<Box<Account<'info, Referrer>>>,
+    referral_vault: &Option<UncheckedAccount<'info>>,
+    config: &ProtocolConfig,
+    trader: &Pubkey,
+    quote: &TradeQuote,
+) -> Result<(TradeQuote, Option<(AccountInfo<'info>, u64)>)> {
+    let (Some(referrer), Some(vault)) = (referrer.as_mut(), referral_vault.as_ref()) else {
+        require!(referrer.is_none() && referral_vault.is_none(), DjinnError::InvalidReferrer);
+        return Ok((*quote, None));
+    };
+    require_keys_neq!(referrer.owner, *trader, DjinnError::SelfReferral);
+
+    let referrer_key = referrer.key();
+    let vault_key = Pubkey::create_program_address(
+        &[b"referral_vault", referrer_key.as_ref(), &[referrer.vault_bump]],
+        &crate::ID,
+    )
+    .map_err(|_| error!(DjinnError::InvalidReferrer))?;
+    require_keys_eq!(vault.key(), vault_key, DjinnError::InvalidReferrer);
+
+    let (quote, cut) = referrer.record_trade(config, quote)?;
+    Ok((quote, Some((vault.to_account_info(), cut))))
+}
+
+impl Referrer {
+    /// Book one referred trade: its volume, and the referral cut of the treasury's fee
+    /// share. Returns the quote to route (treasury share reduced by the cut) and the cut.
+    pub fn record_trade(&mut self, config: &ProtocolConfig, quote: &TradeQuote) -> Result<(TradeQuote, u64)> {
+        let (treasury_fee, cut) = config.split_referral(quote.treasury_fee)?;
+        let cut = u64::try_from(cut).map_err(|_| DjinnError::MathError)?;
+        let volume = quote.net_sol.checked_add(quote.fee).ok_or(DjinnError::MathError)?;
+
+        self.total_volume = self.total_volume.checked_add(volume).ok_or(DjinnError::MathError)?;
+        self.trade_count = self.trade_count.saturating_add(1);
+        self.total_earned = self.total_earned.checked_add(cut).ok_or(DjinnError::MathError)?;
+        self.unclaimed = self.unclaimed.checked_add(cut).ok_or(DjinnError::MathError)?;
+
+        Ok((TradeQuote { treasury_fee, ..*quote }, cut))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, market, NOW, SOL};
+
+    fn referrer() -> Referrer {
+        Referrer {
+            owner: Pubkey::new_unique(),
+            total_volume: 0,
+            trade_count: 0,
+            total_earned: 0,
+            unclaimed: 0,
+            created_at: NOW,
+            bump: 255,
+            vault_bump: 255,
+        }
+    }
+
+    #[test]
+    fn the_cut_comes_out_of_the_treasury_share_only() {
+        let config = config();
+        let quote = market().quote_buy(&config, 0, 10 * SOL, NOW).unwrap();
+        let mut referrer = referrer();
+        let (routed, cut) = referrer.record_trade(&config, &quote).unwrap();
+
+        assert!(cut > 0);
+        assert_eq!(routed.treasury_fee + cut as u128, quote.treasury_fee);
+        assert_eq!((routed.creator_fee, routed.insurance_fee), (quote.creator_fee, quote.insurance_fee));
+        assert_eq!((routed.fee, routed.net_sol, routed.shares), (quote.fee, quote.net_sol, quote.shares));
+        assert_eq!(cut as u128, quote.treasury_fee * config.params.referral_bps as u128 / 10_000);
+    }
+
+    #[test]
+    fn referred_trades_accrue_volume_and_unclaimed_fees() {
+        let config = config();
+        let mut market = market();
+        let buy = market.quote_buy(&config, 0, 10 * SOL, NOW).unwrap();
+        market.apply_buy(0, &buy).unwrap();
+        let swap = market.quote_swap(&config, 0, 1, buy.shares / 2, NOW, 0).unwrap();
+
+        let mut referrer = referrer();
+        let (_, buy_cut) = referrer.record_trade(&config, &buy).unwrap();
+        let (_, swap_cut) = referrer.record_trade(&config, &swap).unwrap();
+
+        assert!(swap_cut > 0);
+        assert_eq!(referrer.trade_count, 2);
+        assert_eq!(referrer.total_volume, buy.net_sol + buy.fee + swap.net_sol + swap.fee);
+        assert_eq!(referrer.unclaimed, buy_cut + swap_cut);
+        assert_eq!(referrer.total_earned, referrer.unclaimed);
+    }
+}
@@ -0,0 +1,102 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// SCALAR MARKET MODULE - Range markets with a linear payout between bounds
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// "BTC price on Dec 31 between $50k and $150k": a scalar market resolves to a value
+// rather than an outcome index. It is an ordinary two-outcome curve market (outcome 0
+// = LONG, 1 = SHORT) created with MarketKind::Scalar in its initialize_market params,
+// which validate the bounds; a market's kind never changes after creation.
+//
+// Like any market, a scalar one goes to the oracle network once it expires
+// (initialize_bounty_pool), and resolve_scalar_market is Cerberus' ruling on the value
+// of a market PendingVerification: it finalizes the market's BountyPool, takes the
+// usual resolution fee and snapshots the pot. At claim_winnings LONG's side of the
+// pot is the fraction of the range below the resolved value (clamped to the bounds),
+// SHORT gets the rest, and each side is split pro rata to its shares. A side nobody holds leaves its part to the other side.
+//
+// Optimistic proposals and the bots' vote are on outcome indices, so neither applies:
+// a scalar pool nobody rules on voids the market when it expires (see verification).
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use djinn_curve::payout::{pro_rata_payout, scalar_long_bps, scalar_side_pots};
+
+use crate::{curve_result, DjinnError, Market, PricingModel};
+
+pub const SCALAR_LONG: u8 = 0;
+pub const SCALAR_SHORT: u8 = 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MarketKind {
+    Categorical,                                  // Pays the winning outcome index
+    Scalar { lower_bound: i64, upper_bound: i64 }, // Pays LONG / SHORT by the resolved value
+}
+
+impl MarketKind {
+    pub const LEN: usize = 1 + 8 + 8; // tag + lower_bound + upper_bound
+
+    /// Scalar markets need two outcomes, the bonding curve and a non-empty range
+    pub fn validate(&self, num_outcomes: u8, pricing: &PricingModel) -> Result<()> {
+        if let MarketKind::Scalar { lower_bound, upper_bound } = self {
+            require!(
+                num_outcomes == 2 && *pricing == PricingModel::Curve && lower_bound < upper_bound,
+                DjinnError::InvalidScalarRange
+            );
+        }
+        Ok(())
+    }
+}
+
+impl Market {
+    pub fn is_scalar(&self) -> bool {
+        matches!(self.kind, MarketKind::Scalar { .. })
+    }
+
+    /// What `shares` of side `outcome_index` are paid out of a resolved scalar
+    /// market's pot snapshot
+    pub fn scalar_payout(&self, outcome_index: u8, shares: u128) -> Result<u128> {
+        let MarketKind::Scalar { lower_bound, upper_bound } = self.kind else {
+            return err!(DjinnError::NotScalarMarket);
+        };
+        let value = self.resolved_value.ok_or(DjinnError::MarketNotResolved)?;
+        let long_bps = curve_result(scalar_long_bps(value, lower_bound, upper_bound))?;
+
+        let long_supply = self.outcome_supplies[SCALAR_LONG as usize];
+        let short_supply = self.outcome_supplies[SCALAR_SHORT as usize];
+        let pot = self.total_pot_at_resolution as u128;
+        let (long_pot, short_pot) = curve_result(scalar_side_pots(pot, long_bps, long_supply, short_supply))?;
+
+        let (side_pot, side_supply) = if outcome_index == SCALAR_LONG {
+            (long_pot, long_supply)
+        } else {
+            (short_pot, short_supply)
+        };
+        curve_result(pro_rata_payout(side_pot, shares, side_supply))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bonding_curve::CurveConfig;
+    use crate::MarketParams;
+
+    fn params(pricing: PricingModel, kind: MarketKind) -> MarketParams {
+        MarketParams { curve: CurveConfig::piecewise(), pricing, kind, parent_outcome: None }
+    }
+
+    #[test]
+    fn scalar_markets_are_validated_at_creation() {
+        let range = MarketKind::Scalar { lower_bound: 50_000, upper_bound: 150_000 };
+        params(PricingModel::Curve, range).validate(2).unwrap();
+        params(PricingModel::Curve, MarketKind::Categorical).validate(6).unwrap();
+
+        // Two outcomes, curve pricing and a non-empty range
+        assert!(params(PricingModel::Curve, range).validate(3).is_err());
+        assert!(params(PricingModel::Lmsr { liquidity_b: 1_000_000_000 }, range).validate(2).is_err());
+        let empty = MarketKind::Scalar { lower_bound: 150_000, upper_bound: 150_000 };
+        assert!(params(PricingModel::Curve, empty).validate(2).is_err());
+        assert!(params(PricingModel::Curve, MarketKind::Categorical).validate(7).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// TEST FIXTURES - Account state for the modules' pure-logic unit tests
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Instruction handlers need a validator; the state transitions they delegate to
+// (quotes, fills, payouts, thresholds) are plain methods on these structs, so the
+// unit tests next to each module build the accounts directly.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::bonding_curve::CurveConfig;
+use crate::fee_schedule::FeeSchedule;
+use crate::position_exit::ExitThresholds;
+use crate::lmsr::PricingModel;
+use crate::protocol_config::{ProtocolConfig, ProtocolParams};
+use crate::scalar_market::MarketKind;
+use crate::trading_limits::TradingLimits;
+use crate::{Market, MarketStatus, UserPosition};
+
+pub const NOW: i64 = 1_700_000_000;
+pub const SOL: u128 = 1_000_000_000;
+
+/// ProtocolConfig at the launch economics
+pub fn config() -> ProtocolConfig {
+    ProtocolConfig { authority: Pubkey::new_unique(), params: ProtocolParams::launch(), bump: 255 }
+}
+
+/// Fresh, empty, user-created binary market on the global curve, resolving in a week
+pub fn market() -> Market {
+    Market {
+        creator: Pubkey::new_unique(),
+        title: "Test market".to_string(),
+        nonce: 0,
+        num_outcomes: 2,
+        outcome_supplies: [0; 6],
+        vault_balance: 0,
+        total_pot_at_resolution: 0,
+        status: MarketStatus::Active,
+        resolution_time: NOW + 7 * 86_400,
+        winning_outcome: None,
+        bump: 255,
+        vault_bump: 255,
+        curve: CurveConfig::piecewise(),
+        pricing: PricingModel::Curve,
+        batch_window_slots: 0,
+        open_batch_id: 0,
+        limits: TradingLimits::default(),
+        fee_schedule: FeeSchedule::flat(&ProtocolParams::launch()),
+        total_volume: 0,
+        total_cost_basis: 0,
+        kind: MarketKind::Categorical,
+        resolved_value: None,
+        condition: None,
+    }
+}
+
+/// The same market with `num_outcomes` outcomes priced by LMSR at liquidity `liquidity_b`
+pub fn lmsr_market(num_outcomes: u8, liquidity_b: u128) -> Market {
+    Market { num_outcomes, pricing: PricingModel::Lmsr { liquidity_b }, ..market() }
+}
+
+/// Empty position in outcome `outcome`, never traded
+pub fn position(outcome: u8) -> UserPosition {
+    UserPosition {
+        market: Pubkey::new_unique(),
+        outcome,
+        shares: 0,
+        claimed: false,
+        exit_thresholds: ExitThresholds::default(),
+        last_trade_ts: 0,
+        cost_basis: 0,
+        open_sell_orders: 0,
+    }
+}
@@ -0,0 +1,138 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// TRADING LIMITS MODULE - Creator-set caps against whale manipulation
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Each Market and ChronosMarket carries a TradingLimits set by its creator (the
+// keeper for Chronos markets). Every field is optional, 0 = no limit:
+// - max_shares_per_wallet: shares one wallet may hold of a single outcome
+// - max_sol_per_tx:        SOL in (fee included) per buy
+// - cooldown_secs:         minimum gap between one wallet's buys of an outcome
+//
+// Every path that adds shares to a position enforces them through
+// UserPosition::record_buy: buy_shares, buy_exact_shares, swap_outcome,
+// mint_complete_set, execute_dca, limit-order buy fills and buy_chronos_shares.
+// Batch orders are checked when submitted (UserPosition::check_buy), against the
+// shares the order would buy alone at the current supply (other orders on the
+// outcome only shrink its pro-rata fill). Their claim credits through credit_buy,
+// stamped with the batch's settlement time rather than the claimer's. A limit-order
+// fill that would break a limit is left resting. The quote_* instructions return the limits in the
+// TradeQuote so clients can size trades up front.
+//
+// A creator may change the limits only before the market's first trade, so nobody
+// is held to caps that did not exist when they bought.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use crate::{DjinnError, UserPosition};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TradingLimits {
+    pub max_shares_per_wallet: u128, // 0 = unlimited
+    pub max_sol_per_tx: u64,         // Lamports, 0 = unlimited
+    pub cooldown_secs: i64,          // 0 = no cooldown
+}
+
+impl TradingLimits {
+    // 16 (max_shares_per_wallet) + 8 (max_sol_per_tx) + 8 (cooldown_secs)
+    pub const LEN: usize = 16 + 8 + 8;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(self.cooldown_secs >= 0, DjinnError::InvalidTradingLimits);
+        Ok(())
+    }
+
+    /// Check one buy: `sol_in` lamports in (fee included), leaving the wallet holding
+    /// `shares_after` of the outcome, `now - last_trade_ts` after its previous buy
+    pub fn check_buy(&self, sol_in: u128, shares_after: u128, last_trade_ts: i64, now: i64) -> Result<()> {
+        require!(
+            self.max_sol_per_tx == 0 || sol_in <= self.max_sol_per_tx as u128,
+            DjinnError::MaxSolPerTradeExceeded
+        );
+        require!(
+            self.max_shares_per_wallet == 0 || shares_after <= self.max_shares_per_wallet,
+            DjinnError::WalletShareLimitExceeded
+        );
+        require!(
+            self.cooldown_secs == 0 || last_trade_ts == 0 || now >= last_trade_ts.saturating_add(self.cooldown_secs),
+            DjinnError::TradeCooldownActive
+        );
+        Ok(())
+    }
+}
+
+impl UserPosition {
+    /// Credit a buy of `shares` for `basis` net lamports, paid with `sol_in` lamports
+    /// (fee included), after checking it against the market's limits. Stamps
+    /// last_trade_ts for the cooldown and the short-hold exit surcharge.
+    pub fn record_buy(&mut self, limits: &TradingLimits, sol_in: u128, shares: u128, basis: u128, now: i64) -> Result<()> {
+        self.check_buy(limits, sol_in, shares, now)?;
+        self.credit_buy(shares, basis, now)
+    }
+
+    /// Check a buy of `shares` for `sol_in` lamports (fee included) against the
+    /// market's limits without crediting it
+    pub fn check_buy(&self, limits: &TradingLimits, sol_in: u128, shares: u128, now: i64) -> Result<()> {
+        let shares_after = self.shares.checked_add(shares).ok_or(DjinnError::MathError)?;
+        limits.check_buy(sol_in, shares_after, self.last_trade_ts, now)
+    }
+
+    /// Credit bought `shares` for `basis` net lamports and stamp last_trade_ts. Every
+    /// buy-side credit goes through here, so neither the cooldown nor the short-hold
+    /// exit surcharge can be dodged by buying through a keeper, a batch or a swap.
+    pub fn credit_buy(&mut self, shares: u128, basis: u128, now: i64) -> Result<()> {
+        self.credit(shares, basis)?;
+        self.last_trade_ts = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{position, NOW, SOL};
+
+    fn limits() -> TradingLimits {
+        TradingLimits { max_shares_per_wallet: 100 * SOL, max_sol_per_tx: SOL as u64, cooldown_secs: 600 }
+    }
+
+    #[test]
+    fn record_buy_credits_and_stamps_the_position() {
+        let mut position = position(0);
+        position.record_buy(&limits(), SOL, 60 * SOL, SOL, NOW).unwrap();
+        assert_eq!((position.shares, position.cost_basis, position.last_trade_ts), (60 * SOL, SOL, NOW));
+    }
+
+    #[test]
+    fn record_buy_enforces_every_limit() {
+        let mut position = position(0);
+        assert!(position.record_buy(&limits(), SOL + 1, SOL, SOL, NOW).is_err());
+        assert!(position.record_buy(&limits(), SOL, 100 * SOL + 1, SOL, NOW).is_err());
+        // Rejected buys leave the position untouched
+        assert_eq!((position.shares, position.last_trade_ts), (0, 0));
+
+        position.record_buy(&limits(), SOL, 60 * SOL, SOL, NOW).unwrap();
+        assert!(position.record_buy(&limits(), SOL, SOL, SOL, NOW + 599).is_err());
+        assert!(position.record_buy(&limits(), SOL, 40 * SOL + 1, SOL, NOW + 600).is_err());
+        position.record_buy(&limits(), SOL, 40 * SOL, SOL, NOW + 600).unwrap();
+        assert_eq!(position.last_trade_ts, NOW + 600);
+    }
+
+    #[test]
+    fn check_buy_leaves_the_position_unstamped() {
+        let position = position(0);
+        position.check_buy(&limits(), SOL, 60 * SOL, NOW).unwrap();
+        assert!(position.check_buy(&limits(), SOL, 100 * SOL + 1, NOW).is_err());
+        assert_eq!((position.shares, position.last_trade_ts), (0, 0));
+    }
+
+    #[test]
+    fn zero_limits_are_off() {
+        let mut position = position(0);
+        for i in 0..3 {
+            position.record_buy(&TradingLimits::default(), 1_000 * SOL, 1_000_000 * SOL, SOL, NOW + i).unwrap();
+        }
+        assert_eq!(position.shares, 3_000_000 * SOL);
+    }
+}
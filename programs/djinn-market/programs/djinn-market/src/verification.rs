@@ -0,0 +1,283 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// VERIFICATION MODULE - Oracle-network votes that resolve markets
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// Once a market reaches resolution_time, anyone can initialize_bounty_pool: it opens
+// the market's BountyPool and moves the market to MarketStatus::PendingVerification, so
+// trading is over and the outcome comes from the oracle network rather than an admin
+// call. A market with an optimistic proposal escalates only when the proposal is
+// challenged (challenge_resolution opens the pool), never through
+// initialize_bounty_pool, so forcing a vote always costs a matching bond. The insurance vault funds every pool with ProtocolConfig.verification_bounty (or
+// what it holds, if less); users' vaults never pay for verification.
+//
+// 1. Bots submit_verification for an outcome; each vote's skin-weighted weight is
+//    tallied per outcome on the pool.
+// 2. After the 24h voting window, finalize_bounty_pool (permissionless) resolves the
+//    market through settle_resolution. The winner is the outcome holding more than
+//    ProtocolConfig.verification_majority_bps of the weight, provided the total weight
+//    reached ProtocolConfig.verification_quorum.
+// 3. Cerberus (G1) takes precedence over the vote until the pool is finalized, either
+//    with cerberus_override (finalized later) or with resolve_market, which rules and
+//    finalizes in one step. Markets never resolve without a pool.
+// 4. Low participation (no quorum or no majority): Cerberus can still rule until the
+//    pool expires (48h). After that finalize_bounty_pool voids the market and positions
+//    take their cost-basis refunds through claim_refund.
+//
+// Once the pool expires, expire_bounty returns what the bots did not claim: into the
+// refund pot if the market was voided, otherwise back to the insurance vault.
+//
+// Scalar markets go through the same pool, but the vote is on outcome indices and
+// they resolve to a value: their pools take no votes and no cerberus_override.
+// resolve_scalar_market is Cerberus' ruling on the value and finalizes the pool with
+// nobody to score. A scalar pool nobody ruled on voids the market when it expires.
+//
+// A market voided while its pool is open (void_market, a failed condition) finalizes
+// the pool on the void, even over a Cerberus ruling. Pools finalized on a void score
+// nobody.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+use djinn_curve::fees::BPS_DENOMINATOR;
+
+use crate::optimistic_resolution::{ProposalStatus, ResolutionProposal};
+use crate::protocol_config::ProtocolParams;
+use crate::{BountyPool, DjinnError, Market, MarketStatus};
+
+/// Votes are accepted for 24h; Cerberus can still rule on a pool without a majority
+/// until it expires at 48h
+pub const VOTING_WINDOW_SECS: i64 = 86_400;
+pub const POOL_LIFETIME_SECS: i64 = 172_800;
+
+impl Market {
+    /// Trading is over or about to be, but no outcome has been declared yet
+    pub fn awaiting_resolution(&self) -> bool {
+        matches!(self.status, MarketStatus::Active | MarketStatus::PendingVerification)
+    }
+
+    /// Hand an expired market to the oracle network. A market with an optimistic
+    /// `proposal` only escalates once the proposal is challenged, so nobody can force a
+    /// vote without matching the bond.
+    pub fn enter_verification(&mut self, proposal: Option<&ResolutionProposal>, now: i64) -> Result<()> {
+        require!(self.status == MarketStatus::Active, DjinnError::MarketNotActive);
+        require!(now >= self.resolution_time, DjinnError::MarketNotExpired);
+        if let Some(proposal) = proposal {
+            require!(proposal.status == ProposalStatus::Challenged, DjinnError::ProposalPending);
+        }
+        self.status = MarketStatus::PendingVerification;
+        Ok(())
+    }
+}
+
+impl BountyPool {
+    /// Open an empty pool for `market` with `bounty` lamports to pay correct votes
+    pub fn open(&mut self, market: Pubkey, bounty: u64, now: i64, bump: u8) {
+        self.market = market;
+        self.total_bounty = bounty;
+        self.total_weight = 0;
+        self.outcome_weights = [0; 6];
+        self.winning_outcome = None;
+        self.cerberus_override = false;
+        self.cerberus_outcome = None;
+        self.num_submissions = 0;
+        self.is_finalized = false;
+        self.created_at = now;
+        self.expires_at = now + POOL_LIFETIME_SECS;
+        self.bump = bump;
+    }
+
+    /// Cerberus rules `outcome` on an unfinalized pool, taking precedence over the vote
+    pub fn rule(&mut self, outcome: u8, num_outcomes: u8) -> Result<()> {
+        require!(!self.is_finalized, DjinnError::BountyAlreadyFinalized);
+        require!(outcome < num_outcomes, DjinnError::InvalidOutcome);
+        self.cerberus_override = true;
+        self.cerberus_outcome = Some(outcome);
+        Ok(())
+    }
+
+    /// Cerberus rules on a scalar `market`'s value, finalizing the pool. Scalar pools
+    /// take no votes, so there is no outcome to score anyone against.
+    pub fn rule_scalar(&mut self, market: &Market) -> Result<()> {
+        require!(market.is_scalar(), DjinnError::NotScalarMarket);
+        require!(!self.is_finalized, DjinnError::BountyAlreadyFinalized);
+        self.cerberus_override = true;
+        self.settle(None);
+        Ok(())
+    }
+
+    /// The outcome finalizing the pool at `now` settles `market` on (None = void): an
+    /// outcome the market already settled on (a void scores nobody, even after a
+    /// Cerberus ruling), then Cerberus, then the vote. The vote is read after its 24h
+    /// window; without a majority, only once the pool expired.
+    pub fn verdict(&self, market: &Market, params: &ProtocolParams, now: i64) -> Result<Option<u8>> {
+        require!(!self.is_finalized, DjinnError::BountyAlreadyFinalized);
+        match market.status {
+            MarketStatus::Resolved => return Ok(market.winning_outcome),
+            MarketStatus::Voided => return Ok(None),
+            MarketStatus::Active | MarketStatus::PendingVerification => {}
+        }
+        if self.cerberus_override {
+            return Ok(self.cerberus_outcome);
+        }
+        require!(now >= self.created_at + VOTING_WINDOW_SECS, DjinnError::BountyNotReady);
+        let majority = self.majority_outcome(params.verification_quorum, params.verification_majority_bps);
+        // Low participation: Cerberus may still rule until the pool expires
+        require!(majority.is_some() || now >= self.expires_at, DjinnError::QuorumNotReached);
+        Ok(majority)
+    }
+
+    /// Record the outcome the pool finalized on; bots are scored against it
+    pub fn settle(&mut self, winning: Option<u8>) {
+        self.winning_outcome = winning;
+        self.is_finalized = true;
+    }
+
+    /// The outcome the vote settled on: the heaviest outcome, if the total weight
+    /// reached `quorum` and its share of it is above `majority_bps`
+    pub fn majority_outcome(&self, quorum: u64, majority_bps: u16) -> Option<u8> {
+        if self.total_weight == 0 || self.total_weight < quorum {
+            return None;
+        }
+        let (outcome, weight) = self
+            .outcome_weights
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, weight)| **weight)?;
+        let share_bps = *weight as u128 * BPS_DENOMINATOR / self.total_weight as u128;
+        (share_bps > majority_bps as u128).then_some(outcome as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar_market::MarketKind;
+    use crate::test_fixtures::{config, market, NOW, SOL};
+
+    fn pool(weights: [u64; 6]) -> BountyPool {
+        let mut pool = BountyPool {
+            market: Pubkey::default(),
+            total_bounty: 0,
+            total_weight: 0,
+            outcome_weights: [0; 6],
+            winning_outcome: None,
+            cerberus_override: false,
+            cerberus_outcome: None,
+            num_submissions: 0,
+            is_finalized: false,
+            created_at: 0,
+            expires_at: 0,
+            bump: 255,
+        };
+        pool.open(Pubkey::new_unique(), SOL as u64, NOW, 255);
+        pool.outcome_weights = weights;
+        pool.total_weight = weights.iter().sum();
+        pool
+    }
+
+    #[test]
+    fn the_vote_needs_the_quorum() {
+        // 100 weight quorum at launch
+        assert_eq!(pool([99, 0, 0, 0, 0, 0]).majority_outcome(100, 5_000), None);
+        assert_eq!(pool([100, 0, 0, 0, 0, 0]).majority_outcome(100, 5_000), Some(0));
+        assert_eq!(pool([0; 6]).majority_outcome(0, 5_000), None);
+    }
+
+    #[test]
+    fn the_winner_must_hold_more_than_the_majority_share() {
+        // Exactly half is not a strict majority
+        assert_eq!(pool([60, 60, 0, 0, 0, 0]).majority_outcome(100, 5_000), None);
+        assert_eq!(pool([61, 60, 0, 0, 0, 0]).majority_outcome(100, 5_000), Some(0));
+        // A plurality of a split vote is not enough either
+        assert_eq!(pool([0, 50, 40, 30, 0, 0]).majority_outcome(100, 5_000), None);
+        // Supermajority thresholds
+        assert_eq!(pool([0, 70, 30, 0, 0, 0]).majority_outcome(100, 7_000), None);
+        assert_eq!(pool([0, 71, 29, 0, 0, 0]).majority_outcome(100, 7_000), Some(1));
+    }
+
+    #[test]
+    fn the_vote_is_read_after_its_window_and_voids_only_at_expiry() {
+        let params = config().params;
+        let mut market = market();
+        market.enter_verification(None, market.resolution_time).unwrap();
+
+        let decided = pool([150, 10, 0, 0, 0, 0]);
+        assert!(decided.verdict(&market, &params, NOW + VOTING_WINDOW_SECS - 1).is_err());
+        assert_eq!(decided.verdict(&market, &params, NOW + VOTING_WINDOW_SECS).unwrap(), Some(0));
+
+        let thin = pool([40, 10, 0, 0, 0, 0]);
+        assert!(thin.verdict(&market, &params, NOW + VOTING_WINDOW_SECS).is_err());
+        assert_eq!(thin.verdict(&market, &params, NOW + POOL_LIFETIME_SECS).unwrap(), None);
+    }
+
+    #[test]
+    fn cerberus_rules_a_valid_outcome_before_finalization() {
+        let params = config().params;
+        let mut market = market();
+        market.enter_verification(None, market.resolution_time).unwrap();
+
+        let mut pool = pool([150, 0, 0, 0, 0, 0]);
+        assert!(pool.rule(2, market.num_outcomes).is_err());
+        pool.rule(1, market.num_outcomes).unwrap();
+        // Overrides the vote, without waiting for the window
+        assert_eq!(pool.verdict(&market, &params, NOW).unwrap(), Some(1));
+
+        pool.settle(Some(1));
+        assert!(pool.rule(0, market.num_outcomes).is_err());
+        assert!(pool.verdict(&market, &params, NOW + POOL_LIFETIME_SECS).is_err());
+    }
+
+    #[test]
+    fn scalar_markets_are_ruled_by_cerberus_or_voided_at_expiry() {
+        let params = config().params;
+        let mut market = market();
+        market.kind = MarketKind::Scalar { lower_bound: 50_000, upper_bound: 150_000 };
+        market.enter_verification(None, market.resolution_time).unwrap();
+        assert!(market.status == MarketStatus::PendingVerification);
+
+        // Nobody ruled: the empty pool voids the market once it expires
+        let unruled = pool([0; 6]);
+        assert!(unruled.verdict(&market, &params, NOW + VOTING_WINDOW_SECS).is_err());
+        assert_eq!(unruled.verdict(&market, &params, NOW + POOL_LIFETIME_SECS).unwrap(), None);
+
+        let mut ruled = pool([0; 6]);
+        ruled.rule_scalar(&market).unwrap();
+        assert!(ruled.is_finalized && ruled.cerberus_override);
+        assert_eq!(ruled.winning_outcome, None);
+        assert!(ruled.rule_scalar(&market).is_err());
+
+        // Categorical markets are ruled on an outcome instead
+        market.kind = MarketKind::Categorical;
+        assert!(pool([0; 6]).rule_scalar(&market).is_err());
+    }
+
+    #[test]
+    fn a_cerberus_ruling_on_a_market_voided_since_scores_nobody() {
+        let params = config().params;
+        let mut market = market();
+        market.enter_verification(None, market.resolution_time).unwrap();
+
+        let mut pool = pool([150, 0, 0, 0, 0, 0]);
+        pool.rule(0, market.num_outcomes).unwrap();
+        // void_market or a failed condition lands before finalize_bounty_pool
+        market.void().unwrap();
+        assert_eq!(pool.verdict(&market, &params, NOW).unwrap(), None);
+    }
+
+    #[test]
+    fn a_voided_market_finalizes_its_pool_on_the_void() {
+        let params = config().params;
+        let mut market = market();
+        market.enter_verification(None, market.resolution_time).unwrap();
+        market.void().unwrap();
+
+        let pool = pool([150, 0, 0, 0, 0, 0]);
+        assert_eq!(pool.verdict(&market, &params, NOW + VOTING_WINDOW_SECS).unwrap(), None);
+
+        // An unclaimed bounty then joins the refund pot
+        let pot = market.total_pot_at_resolution;
+        market.add_to_refund_pot(SOL as u64).unwrap();
+        assert_eq!(market.total_pot_at_resolution, pot + SOL as u64);
+        assert_eq!(market.vault_balance, pot as u128 + SOL);
+    }
+}
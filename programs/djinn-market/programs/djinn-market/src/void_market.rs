@@ -0,0 +1,167 @@
+// ═══════════════════════════════════════════════════════════════════════════════
+// VOID MARKET MODULE - Cancelled markets unwound by cost-basis refunds
+// ═══════════════════════════════════════════════════════════════════════════════
+//
+// When an event is cancelled or ambiguous, void_market moves the market to
+// MarketStatus::Voided instead of picking a winner, and every UserPosition reclaims
+// its share of the pot through claim_refund. No resolution fee is taken.
+//
+// Refunds are weighted by cost basis, not shares: each position tracks the net
+// lamports it put into the vault for the shares it holds, and the Market tracks the
+// sum. Buys add what went into the vault; sells, swaps and redemptions release the
+// sold fraction of the basis. A swap carries the basis it released over to the
+// position it buys. Shares escrowed by a sell limit order keep their basis on the
+// order (still counted in the market total) until the order fills or comes back.
+//
+// On LMSR markets whatever the vault holds above the total basis is the creator's
+// unspent subsidy and goes back to the creator when the market is voided.
+//
+// ═══════════════════════════════════════════════════════════════════════════════
+
+use anchor_lang::prelude::*;
+
+use djinn_curve::payout::pro_rata_payout;
+
+use crate::lmsr::PricingModel;
+use crate::{curve_result, DjinnError, Market, MarketStatus, UserPosition};
+
+impl UserPosition {
+    /// Add `shares` that cost `basis` net lamports (position only)
+    pub fn credit(&mut self, shares: u128, basis: u128) -> Result<()> {
+        self.shares = self.shares.checked_add(shares).ok_or(DjinnError::MathError)?;
+        self.cost_basis = self.cost_basis.checked_add(basis).ok_or(DjinnError::MathError)?;
+        Ok(())
+    }
+
+    /// Remove `shares` and the same fraction of the cost basis (position only).
+    /// Returns the basis released.
+    pub fn debit(&mut self, shares: u128) -> Result<u128> {
+        require!(self.shares >= shares, DjinnError::InsufficientShares);
+        let basis = if shares == self.shares {
+            self.cost_basis
+        } else {
+            self.cost_basis.checked_mul(shares).ok_or(DjinnError::MathError)? / self.shares
+        };
+        self.shares -= shares;
+        self.cost_basis -= basis;
+        Ok(basis)
+    }
+}
+
+impl Market {
+    pub fn add_cost_basis(&mut self, basis: u128) -> Result<()> {
+        self.total_cost_basis = self.total_cost_basis.checked_add(basis).ok_or(DjinnError::MathError)?;
+        Ok(())
+    }
+
+    pub fn remove_cost_basis(&mut self, basis: u128) -> Result<()> {
+        self.total_cost_basis = self.total_cost_basis.checked_sub(basis).ok_or(DjinnError::MathError)?;
+        Ok(())
+    }
+
+    /// Void the market and snapshot the refund pot. Returns the LMSR subsidy left over
+    /// for the creator, already taken out of the vault.
+    pub fn void(&mut self) -> Result<u128> {
+        require!(self.awaiting_resolution(), DjinnError::MarketNotActive);
+
+        // LMSR: the vault above what traders paid in is the creator's unspent subsidy
+        let mut subsidy_left = 0;
+        if let PricingModel::Lmsr { .. } = self.pricing {
+            subsidy_left = self.vault_balance.saturating_sub(self.total_cost_basis);
+            self.vault_balance -= subsidy_left;
+        }
+
+        // (Point 2) Snapshot the Pot Balance for fair refunds
+        self.total_pot_at_resolution = self.vault_balance as u64;
+        self.status = MarketStatus::Voided;
+        self.winning_outcome = None;
+        Ok(subsidy_left)
+    }
+
+    /// Add `amount` lamports, already paid into the vault, to a voided market's refund
+    /// pot (an unclaimed verification bounty)
+    pub fn add_to_refund_pot(&mut self, amount: u64) -> Result<()> {
+        require!(self.status == MarketStatus::Voided, DjinnError::MarketNotVoided);
+        self.vault_balance = self.vault_balance.checked_add(amount as u128).ok_or(DjinnError::MathError)?;
+        self.total_pot_at_resolution =
+            self.total_pot_at_resolution.checked_add(amount).ok_or(DjinnError::MathError)?;
+        Ok(())
+    }
+
+    /// Book one position's refund of a voided market: its cost-basis share of the
+    /// snapshotted pot, never more than the vault still holds. Returns the lamports.
+    pub fn take_refund(&mut self, cost_basis: u128) -> Result<u128> {
+        require!(self.status == MarketStatus::Voided, DjinnError::MarketNotVoided);
+        require!(cost_basis > 0, DjinnError::NoShares);
+
+        let pot = self.total_pot_at_resolution as u128;
+        let refund = curve_result(pro_rata_payout(pot, cost_basis, self.total_cost_basis))?.min(self.vault_balance);
+        self.vault_balance -= refund;
+        Ok(refund)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{config, lmsr_market, market, position, NOW, SOL};
+
+    /// Buy `sol_in` of `outcome` into `position`, booking the basis like settle_buy
+    fn buy(market: &mut Market, position: &mut UserPosition, sol_in: u128) {
+        let quote = market.quote_buy(&config(), position.outcome, sol_in, NOW).unwrap();
+        market.apply_buy(position.outcome, &quote).unwrap();
+        market.add_cost_basis(quote.net_sol).unwrap();
+        position.credit_buy(quote.shares, quote.net_sol, NOW).unwrap();
+    }
+
+    #[test]
+    fn void_splits_the_pot_by_cost_basis() {
+        let mut market = market();
+        let (mut yes, mut no) = (position(0), position(1));
+        buy(&mut market, &mut yes, 3 * SOL);
+        buy(&mut market, &mut no, SOL);
+        // A partial sell releases its fraction of the basis
+        let sell = market.quote_sell(&config(), 0, yes.shares / 3, NOW, 0).unwrap();
+        market.apply_sell(0, &sell).unwrap();
+        market.remove_cost_basis(yes.debit(sell.shares).unwrap()).unwrap();
+
+        assert_eq!(market.void().unwrap(), 0);
+        assert!(market.status == MarketStatus::Voided && market.winning_outcome.is_none());
+
+        let pot = market.total_pot_at_resolution as u128;
+        let (yes_refund, no_refund) = (market.take_refund(yes.cost_basis).unwrap(), market.take_refund(no.cost_basis).unwrap());
+        assert!(yes_refund + no_refund <= pot);
+        assert!(pot - (yes_refund + no_refund) <= 1);
+        // The sell moved the pot off the total basis; what is left splits by basis
+        assert_eq!(yes_refund, pot * yes.cost_basis / (yes.cost_basis + no.cost_basis));
+        assert_eq!(no_refund, pot * no.cost_basis / (yes.cost_basis + no.cost_basis));
+    }
+
+    #[test]
+    fn lmsr_void_returns_the_unspent_subsidy_to_the_creator() {
+        let mut market = lmsr_market(3, 1_000 * SOL);
+        market.vault_balance = 700 * SOL; // Creator's subsidy
+        let mut position = position(2);
+        buy(&mut market, &mut position, 10 * SOL);
+
+        assert_eq!(market.void().unwrap(), 700 * SOL);
+        assert_eq!(market.vault_balance, position.cost_basis);
+        assert_eq!(market.take_refund(position.cost_basis).unwrap(), position.cost_basis);
+        assert_eq!(market.vault_balance, 0);
+    }
+
+    #[test]
+    fn refunds_need_a_voided_market_and_a_basis() {
+        let mut market = market();
+        let mut position = position(0);
+        buy(&mut market, &mut position, SOL);
+        assert!(market.take_refund(position.cost_basis).is_err());
+
+        market.void().unwrap();
+        assert!(market.void().is_err());
+        assert!(market.take_refund(0).is_err());
+        // Never more than the vault holds, even if the books disagree
+        market.vault_balance /= 2;
+        assert_eq!(market.take_refund(position.cost_basis).unwrap(), position.cost_basis / 2);
+    }
+}
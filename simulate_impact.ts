@@ -1,5 +1,5 @@
 
-import { simulateBuy, calculateImpliedProbability, MarketState } from './lib/core-amm';
+import { simulateBuy, calculateImpliedProbability, MarketState } from './lib/djinn-curve.ts';
 
 const amounts = [1, 2, 4, 5, 10];
 
@@ -1,5 +1,5 @@
 
-import { getSpotPrice, simulateBuy, simulateSell, MarketState } from './lib/core-amm.ts';
+import { estimatePayout, getSpotPrice, simulateBuy, simulateSell, MarketState } from './lib/djinn-curve.ts';
 
 // ------------------------------------------------------------------
 // CONFIG
@@ -199,8 +199,7 @@ agents.forEach(a => {
 
     // Claim YES
     if (a.yesShares > 0) {
-        const share = a.yesShares / stateYes.totalSharesMinted;
-        const payout = share * TOTAL_POT;
+        const payout = estimatePayout(a.yesShares, stateYes.totalSharesMinted, TOTAL_POT);
         a.solBalance += payout;
         a.yesShares = 0; // Burn
     }